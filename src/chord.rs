@@ -0,0 +1,274 @@
+//! Provides a struct for working with chords made up of several JI [Ratios][Ratio].
+use crate::{
+    numtheory::{gcd, lcm},
+    play::difference_tones,
+    ratio::Ratio,
+};
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::Frequency,
+    play::{ChordStyle, Play, Player},
+};
+use num::traits::PrimInt;
+use std::fmt::Display;
+
+mod voice_leading;
+pub use voice_leading::VoiceLeading;
+
+/// Models a chord as a set of JI ratios sounding together above a common root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chord<T: PrimInt = i32> {
+    pub ratios: Vec<Ratio<T>>,
+}
+
+impl<T: PrimInt> Chord<T> {
+    /// Construct a new [Chord] from the given ratios.
+    ///
+    /// The root is not added to the chord, so should be included as `1/1` if it is to
+    /// sound.
+    pub fn new(ratios: Vec<Ratio<T>>) -> Self {
+        Self { ratios }
+    }
+
+    /// Construct a new [Chord] from a sequence of harmonics, such as `4:5:6:7`, with the
+    /// first harmonic as the root. No harmonics make an empty chord.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, chord::Chord};
+    /// let chord = Chord::from_harmonics(&[4, 5, 6, 7]);
+    /// assert_eq!(
+    ///     chord.ratios,
+    ///     vec![
+    ///         Ratio::new(1, 1),
+    ///         Ratio::new(5, 4),
+    ///         Ratio::new(3, 2),
+    ///         Ratio::new(7, 4)
+    ///     ]
+    /// );
+    /// ```
+    pub fn from_harmonics(harmonics: &[T]) -> Self {
+        let Some(&root) = harmonics.first() else {
+            return Self::new(vec![]);
+        };
+        Self::new(harmonics.iter().map(|h| Ratio::new(*h, root)).collect())
+    }
+
+    /// Returns the chord moved up by the given ratio.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, chord::Chord};
+    /// let chord = Chord::from_harmonics(&[4, 5, 6]).transpose(Ratio::new(3, 2));
+    /// assert_eq!(chord.to_string(), "3/2 15/8 9/8");
+    /// ```
+    pub fn transpose(&self, ratio: Ratio<T>) -> Self {
+        Self::new(self.ratios.iter().map(|r| *r * ratio).collect())
+    }
+
+    /// Returns the first-order difference tones, in Hz, produced by the chord sounding
+    /// above the given root frequency.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::chord::Chord;
+    /// let chord = Chord::from_harmonics(&[4, 5, 6]);
+    /// assert_eq!(chord.difference_tones(200.), vec![50., 100.]);
+    /// ```
+    pub fn difference_tones(&self, root_hz: f64) -> Vec<f64> {
+        let freqs: Vec<f64> = self
+            .ratios
+            .iter()
+            .map(|r| root_hz * r.to_float::<f64>())
+            .collect();
+        difference_tones(&freqs)
+    }
+
+    /// Returns the chord in lowest integer form, as the harmonics of a common
+    /// fundamental, in the order the ratios are given.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, chord::Chord};
+    /// let minor = Chord::new(vec![Ratio::new(1, 1), Ratio::new(6, 5), Ratio::new(3, 2)]);
+    /// assert_eq!(minor.harmonics(), vec![10, 12, 15]);
+    /// ```
+    pub fn harmonics(&self) -> Vec<T> {
+        let denom = self.ratios.iter().fold(T::one(), |l, r| lcm(l, r.denom));
+        let harmonics: Vec<T> = self
+            .ratios
+            .iter()
+            .map(|r| r.numer * (denom / r.denom))
+            .collect();
+        let common = harmonics.iter().fold(T::zero(), |g, h| gcd(*h, g));
+        harmonics.iter().map(|h| *h / common).collect()
+    }
+
+    /// Returns the chord in lowest integer form as the subharmonics of a common
+    /// guide tone, so that each ratio is the reciprocal of its subharmonic, up to a
+    /// common factor.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, chord::Chord};
+    /// let minor = Chord::new(vec![Ratio::new(1, 1), Ratio::new(6, 5), Ratio::new(3, 2)]);
+    /// assert_eq!(minor.subharmonics(), vec![6, 5, 4]);
+    /// ```
+    pub fn subharmonics(&self) -> Vec<T> {
+        let harmonics = self.harmonics();
+        let multiple = harmonics.iter().fold(T::one(), |l, h| lcm(l, *h));
+        harmonics.iter().map(|h| multiple / *h).collect()
+    }
+
+    /// Returns the fundamental of the chord, the greatest common divisor of its
+    /// ratios, as a multiple of the root's frequency.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::chord::Chord;
+    /// let chord = Chord::from_harmonics(&[4, 5, 6]);
+    /// assert_eq!(chord.fundamental(), 0.25);
+    /// ```
+    pub fn fundamental(&self) -> f64 {
+        let first: f64 = self.ratios[0].to_float();
+        let harmonic: f64 = num::cast(self.harmonics()[0]).unwrap();
+        first / harmonic
+    }
+
+    /// Returns whether the chord is otonal, that is, whether it is no more complex
+    /// written as [harmonics][Chord::harmonics] than as
+    /// [subharmonics][Chord::subharmonics].
+    ///
+    /// Dyads, and chords that are symmetrical, are both otonal and utonal.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::chord::Chord;
+    /// let chord = Chord::from_harmonics(&[4, 5, 6, 7]);
+    /// assert!(chord.is_otonal());
+    /// assert!(!chord.is_utonal());
+    /// ```
+    pub fn is_otonal(&self) -> bool {
+        largest(&self.harmonics()) <= largest(&self.subharmonics())
+    }
+
+    /// Returns whether the chord is utonal, that is, whether it is no more complex
+    /// written as [subharmonics][Chord::subharmonics] than as
+    /// [harmonics][Chord::harmonics].
+    pub fn is_utonal(&self) -> bool {
+        largest(&self.subharmonics()) <= largest(&self.harmonics())
+    }
+}
+
+fn largest<T: PrimInt>(values: &[T]) -> T {
+    values.iter().fold(T::zero(), |max, v| max.max(*v))
+}
+
+#[cfg(feature = "audio")]
+impl<T: PrimInt> Play for Chord<T> {
+    /// Plays the chord in the [ChordStyle] given in the player's
+    /// [PlaybackConfig](crate::play::PlaybackConfig). A [block chord][ChordStyle::Block]
+    /// is preceded by each of its notes in turn, so that they can be heard apart first.
+    fn play(&self, player: &Player) {
+        let root = player.config.root;
+        if player.config.chord_style == ChordStyle::Block {
+            let freqs: Vec<f32> = self
+                .ratios
+                .iter()
+                .map(|r| (Frequency(root) * *r).0)
+                .collect();
+
+            player.play_sequence(&freqs);
+            player.rest();
+        }
+        player.play_chord(root, &self.ratios);
+    }
+}
+
+impl<T: PrimInt + Display> Display for Chord<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ratios: Vec<String> = self.ratios.iter().map(|r| r.to_string()).collect();
+        write!(f, "{}", ratios.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn from_harmonics() {
+        let chord = Chord::from_harmonics(&[4, 5, 6, 7]);
+
+        assert_eq!(
+            chord,
+            Chord::new(vec![
+                Ratio::new(1, 1),
+                Ratio::new(5, 4),
+                Ratio::new(3, 2),
+                Ratio::new(7, 4)
+            ])
+        );
+    }
+
+    #[test]
+    fn from_harmonics_reduces_to_the_octave() {
+        let chord = Chord::from_harmonics(&[2, 3, 5]);
+
+        assert_eq!(
+            chord.ratios,
+            vec![Ratio::new(1, 1), Ratio::new(3, 2), Ratio::new(5, 4)]
+        );
+    }
+
+    #[test]
+    fn from_no_harmonics() {
+        let chord = Chord::<i32>::from_harmonics(&[]);
+
+        assert_eq!(chord, Chord::new(vec![]));
+        assert_eq!(chord.harmonics(), vec![]);
+    }
+
+    #[test]
+    fn difference_tones_of_an_otonal_chord_are_harmonics() {
+        let chord = Chord::from_harmonics(&[4, 5, 6, 7]);
+
+        assert_eq!(chord.difference_tones(100.), vec![25., 50., 75.]);
+    }
+
+    #[test]
+    fn lowest_integer_form() {
+        let chord = Chord::new(vec![Ratio::new(3, 2), Ratio::new(1, 1), Ratio::new(5, 4)]);
+
+        assert_eq!(chord.harmonics(), vec![6, 4, 5]);
+        assert_eq!(chord.subharmonics(), vec![10, 15, 12]);
+        assert_eq!(chord.fundamental(), 0.25);
+    }
+
+    #[test]
+    fn otonal_and_utonal() {
+        let major = Chord::from_harmonics(&[4, 5, 6]);
+        let minor = Chord::new(vec![Ratio::new(1, 1), Ratio::new(6, 5), Ratio::new(3, 2)]);
+        let fifth = Chord::from_harmonics(&[2, 3]);
+
+        assert!(major.is_otonal() && !major.is_utonal());
+        assert!(minor.is_utonal() && !minor.is_otonal());
+        assert!(fifth.is_otonal() && fifth.is_utonal());
+        assert_eq!(minor.fundamental(), 0.1);
+    }
+
+    #[test]
+    fn display() {
+        let chord = Chord::from_harmonics(&[4, 5, 6]);
+
+        assert_eq!(chord.to_string(), "1/1 5/4 3/2");
+    }
+}
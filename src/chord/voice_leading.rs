@@ -0,0 +1,136 @@
+use super::Chord;
+use num::traits::PrimInt;
+
+/// The smoothest way of moving the voices of one chord to those of another, found by
+/// [Chord::voice_leading].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoiceLeading {
+    /// The index of the voice of the second chord that each voice of the first moves to.
+    pub targets: Vec<usize>,
+    /// The signed motion, in cents, of each voice of the first chord.
+    pub motions: Vec<f64>,
+}
+
+impl VoiceLeading {
+    /// Returns the total motion, in cents, of every voice.
+    pub fn distance(&self) -> f64 {
+        self.motions.iter().map(|m| m.abs()).sum()
+    }
+}
+
+impl<T: PrimInt> Chord<T> {
+    /// Returns the [VoiceLeading] to the given chord with the least total motion.
+    ///
+    /// Since a chord's ratios lie within the octave, each voice moves by the smaller of
+    /// the two ways, up or down, to its target, so no voice moves by more than a
+    /// tritone.
+    ///
+    /// Panics if the chords have different numbers of voices.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Chord, Ratio};
+    /// let c_major = Chord::from_harmonics(&[4, 5, 6]);
+    /// let a_minor = Chord::new(vec![Ratio::new(5, 4), Ratio::new(5, 3), Ratio::new(1, 1)]);
+    /// let leading = c_major.voice_leading(&a_minor);
+    /// assert_eq!(leading.targets, vec![2, 0, 1]);
+    /// assert!((leading.distance() - 182.404).abs() < 0.001);
+    /// ```
+    pub fn voice_leading(&self, other: &Chord<T>) -> VoiceLeading {
+        let voices = self.ratios.len();
+        assert_eq!(
+            voices,
+            other.ratios.len(),
+            "chords must have the same number of voices"
+        );
+
+        let motion = |from: usize, to: usize| {
+            let motion = (other.ratios[to].cents() - self.ratios[from].cents()).rem_euclid(1200.);
+            if motion > 600. {
+                motion - 1200.
+            } else {
+                motion
+            }
+        };
+
+        // The least total motion of the first `count_ones(targets)` voices onto each set
+        // of targets, and the last target chosen to reach it.
+        let mut best = vec![(f64::INFINITY, 0); 1 << voices];
+        best[0].0 = 0.;
+        for targets in 0..best.len() {
+            let voice = (targets as u32).count_ones() as usize;
+            if voice == voices {
+                continue;
+            }
+            for target in (0..voices).filter(|t| targets & (1 << t) == 0) {
+                let distance = best[targets].0 + motion(voice, target).abs();
+                let next = targets | (1 << target);
+                if distance < best[next].0 {
+                    best[next] = (distance, target);
+                }
+            }
+        }
+
+        let mut targets = vec![0; voices];
+        let mut remaining = best.len() - 1;
+        for voice in (0..voices).rev() {
+            targets[voice] = best[remaining].1;
+            remaining &= !(1 << targets[voice]);
+        }
+        let motions = targets
+            .iter()
+            .enumerate()
+            .map(|(voice, &target)| motion(voice, target))
+            .collect();
+
+        VoiceLeading { targets, motions }
+    }
+
+    /// Returns the total motion, in cents, of the smoothest
+    /// [voice leading][Chord::voice_leading] to the given chord.
+    pub fn voice_leading_distance(&self, other: &Chord<T>) -> f64 {
+        self.voice_leading(other).distance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn identical_chords_do_not_move() {
+        let chord = Chord::from_harmonics(&[4, 5, 6, 7]);
+        let leading = chord.voice_leading(&chord);
+
+        assert_eq!(leading.targets, vec![0, 1, 2, 3]);
+        assert_eq!(leading.distance(), 0.);
+    }
+
+    #[test]
+    fn voices_move_across_the_octave() {
+        let from = Chord::new(vec![Ratio::new(1, 1), Ratio::new(3, 2)]);
+        let to = Chord::new(vec![Ratio::new(8, 5), Ratio::new(15, 8)]);
+        let leading = from.voice_leading(&to);
+
+        assert_eq!(leading.targets, vec![1, 0]);
+        assert!((leading.motions[0] + 111.731).abs() < 0.001);
+        assert!((leading.motions[1] - 111.731).abs() < 0.001);
+    }
+
+    #[test]
+    fn commas_are_small_motions() {
+        let from = Chord::new(vec![Ratio::new(5, 3), Ratio::new(1, 1), Ratio::new(4, 3)]);
+        let to = Chord::new(vec![Ratio::new(4, 3), Ratio::new(27, 16), Ratio::new(1, 1)]);
+
+        assert!((from.voice_leading_distance(&to) - 21.506).abs() < 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "chords must have the same number of voices")]
+    fn different_sizes() {
+        Chord::from_harmonics(&[4, 5, 6]).voice_leading(&Chord::from_harmonics(&[4, 5]));
+    }
+}
@@ -1,13 +1,50 @@
 //! the rust-intonation CLI. See [rust-intonation][crate] for documentation.
 
-use crate::diamond::Diamond;
-use crate::lattice::{Lattice, LatticeDimension, LatticeDimensionBounds::*};
-use crate::play::Play;
+use crate::chord::Chord;
+use crate::comma_pump::CommaPump;
+use crate::diamond::{Diamond, Identity};
+use crate::html;
+use crate::interval::{constants, name_of};
+use crate::json;
+use crate::lattice::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Placement};
+use crate::midi::MidiExport;
+#[cfg(feature = "mpe")]
+use crate::midi::MpeOutput;
+use crate::notation::{lilypond, musicxml};
+use crate::pitch::Note;
+#[cfg(feature = "audio")]
+use crate::pitch::{Frequency, Pitch};
+#[cfg(feature = "audio")]
+use crate::play::{
+    compare, ChordStyle, Drone, Envelope, PitchComparison, Play, PlaybackConfig, Player,
+    ScaleDirection, Waveform,
+};
 use crate::ratio::Ratio;
+#[cfg(feature = "audio")]
+use crate::render::Render;
+use crate::scale::{Degree, Scale};
 use crate::temperaments::edo::EdoInterval;
 use crate::Edo;
-use clap::{Parser, Subcommand};
-use std::time::Duration;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{
+    ArgGroup, ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum,
+    ValueHint,
+};
+use clap_complete::Shell;
+use std::io::{IsTerminal, Read};
+use std::ops::RangeInclusive;
+
+mod config;
+#[cfg(feature = "audio")]
+mod nudge;
+mod output;
+mod repl;
+use config::Config;
+#[cfg(feature = "audio")]
+use nudge::Nudge;
+use output::{print_records, Format, Record, Value, Verbosity};
+use repl::Repl;
 
 #[derive(Parser, Debug)]
 #[clap(author = "Michael Berkowitz", version)]
@@ -25,10 +62,24 @@ use std::time::Duration;
 struct Cli {
     #[command(subcommand)]
     cmd: SubCommand,
+    /// The format to print results in
+    #[clap(long = "format", global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Print nothing besides the results, e.g. no message for each file written
+    #[clap(short = 'q', long = "quiet", global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Also describe each sound, with its frequencies and length, as it is played
+    #[clap(short = 'v', long = "verbose", global = true)]
+    verbose: bool,
+    #[cfg(feature = "audio")]
+    /// Print what would be played, rather than opening the audio device
+    #[clap(long = "no-audio", global = true)]
+    no_audio: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum SubCommand {
+    #[cfg(feature = "audio")]
     /// Play a given ratio as sine waves.
     ///
     /// Will play a root pitch (middle C), the result of
@@ -36,19 +87,554 @@ enum SubCommand {
     /// the two pitches together as a dyad.
     ///
     /// Ex. `rust-intonation play -r 3/2`
+    ///
+    /// The root pitch, note length and volume can be changed with
+    /// `--root`, `--duration` and `--volume`.
+    ///
+    /// Ex. `rust-intonation play -r 3/2 --root 440 --duration 1`
+    ///
+    /// Passing `--beats` will instead sustain the dyad long enough to
+    /// hear the beating between its partials.
+    ///
+    /// Ex. `rust-intonation play -r 81/64 --beats --waveform harmonic`
     Play {
-        #[clap(short = 'r', long = "ratio")]
+        /// The ratio to play, e.g. `3/2`
+        #[clap(short = 'r', long = "ratio", value_name = "N/D", value_parser = ratio_arg)]
         ratio: String,
+        /// Sustain the dyad long enough to hear the beating between its partials
+        #[clap(long = "beats")]
+        beats: bool,
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    #[cfg(feature = "audio")]
+    /// Play a single tone, like a tuning fork.
+    ///
+    /// The tone can be given as a frequency in Hz, or as a note name, tuned
+    /// in 12 EDO relative to `--a4`, and can be offset by a number of cents.
+    /// Prints the frequency of the tone, and its nearest note and deviation.
+    ///
+    /// Ex. `rust-intonation tone -f 432`
+    ///
+    /// Ex. `rust-intonation tone -n A4 --offset-cents -14`
+    #[command(group(ArgGroup::new("tone").required(true)))]
+    Tone {
+        /// The frequency of the tone, in Hz, e.g. `432`
+        #[clap(
+            short = 'f',
+            long = "frequency",
+            group = "tone",
+            value_name = "HZ",
+            value_parser = positive_arg,
+        )]
+        frequency: Option<f32>,
+        /// The note of the tone, e.g. `A4` or `Bb3`
+        #[clap(short = 'n', long = "note", group = "tone", value_parser = note_arg)]
+        note: Option<String>,
+        /// The number of cents to raise (or, if negative, lower) the tone by
+        #[clap(
+            long = "offset-cents",
+            default_value = "0",
+            allow_hyphen_values = true,
+            value_name = "CENTS"
+        )]
+        offset_cents: f64,
+        /// The frequency of A4, in Hz, that notes are tuned relative to
+        #[clap(
+            long = "a4",
+            default_value = "440",
+            value_name = "HZ",
+            value_parser = positive_arg,
+        )]
+        a4: f32,
+        #[command(flatten)]
+        playback: PlaybackArgs,
     },
-    /// Compare given ratio as sine waves with the nearest ET interval.
+    #[cfg(feature = "audio")]
+    /// Sustain a JI drone above the root to practice intonation against.
     ///
-    /// Plays the same sequence as **play**, but will follow it by playing
-    /// the nearest ET interval to your ratio
+    /// The root sounds along with the given harmonics of it, which are not
+    /// reduced to within the octave, or the given ratios above it, until
+    /// Ctrl-C is pressed, or for `--for` seconds.
     ///
-    /// Ex. `rust-intonation compare -r 3/2`
+    /// Ex. `rust-intonation drone --harmonics 2 3 5 --root 110`
+    ///
+    /// The amplitude of each voice, from 0 to 1, starting with the root,
+    /// can be given with `--amplitudes`, and scales the `--volume`.
+    ///
+    /// Ex. `rust-intonation drone -r 3/2 --amplitudes 1 0.5 --for 30`
+    #[command(group(ArgGroup::new("voices")))]
+    Drone {
+        /// The harmonics of the root to sound above it, e.g. `2 3 5`
+        #[clap(
+            long = "harmonics",
+            num_args = 1..,
+            group = "voices",
+            value_parser = clap::value_parser!(u32).range(1..),
+        )]
+        harmonics: Vec<u32>,
+        /// The ratios to sound above the root, e.g. `5/4 3/2`
+        #[clap(
+            short = 'r',
+            long = "ratios",
+            num_args = 1..,
+            group = "voices",
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        ratios: Vec<String>,
+        /// The amplitude of each voice, from 0 to 1, starting with the root
+        #[clap(long = "amplitudes", num_args = 1.., value_parser = unit_arg)]
+        amplitudes: Vec<f32>,
+        /// The number of seconds to sustain the drone for (until Ctrl-C if not
+        /// given)
+        #[clap(long = "for", value_name = "SECONDS", value_parser = positive_arg)]
+        seconds: Option<f32>,
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    #[cfg(feature = "audio")]
+    /// Nudge a tone up and down against a reference tone sustained at the
+    /// root, to train the ear to hear small intervals, such as commas.
+    ///
+    /// Enter `+` or `-` to raise or lower the tone by `--step` cents, `+5`
+    /// or `-5` to move it by 5 cents, or a number of cents or a ratio to
+    /// retune it to. After each nudge the interval between the tones is
+    /// shown, with the ratio it approximates. Enter `quit` to stop.
+    ///
+    /// Ex. `rust-intonation nudge --start 15 --step 0.5`
+    Nudge {
+        /// The number of cents the tone starts above the reference
+        #[clap(
+            long = "start",
+            default_value = "0",
+            allow_hyphen_values = true,
+            value_name = "CENTS"
+        )]
+        start: f64,
+        /// The number of cents each `+` or `-` moves the tone by
+        #[clap(
+            long = "step",
+            default_value = "1",
+            value_name = "CENTS",
+            value_parser = positive_arg,
+        )]
+        step: f32,
+        /// How many cents from the interval a ratio can be to approximate it
+        #[clap(
+            long = "tolerance",
+            default_value = "1",
+            value_name = "CENTS",
+            value_parser = positive_arg,
+        )]
+        tolerance: f32,
+        /// The largest denominator of a ratio approximating the interval
+        #[clap(
+            long = "max-denominator",
+            default_value = "128",
+            value_parser = clap::value_parser!(i32).range(1..),
+        )]
+        max_denominator: i32,
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    /// Compare two intervals.
+    ///
+    /// Each interval can be a JI ratio, a step of an EDO, written as
+    /// `<divisions>edo:<steps>`, or a size in cents, written with an
+    /// optional trailing `c`. Prints the size of each interval in cents,
+    /// and the difference in cents from the first to the second. If only
+    /// one interval is given, it is compared to the nearest 12 EDO interval.
+    ///
+    /// Ex. `rust-intonation compare -a 5/4 -b 31edo:10`
+    ///
+    /// Ex. `rust-intonation compare -a 7/4 -b 970c`
+    ///
+    /// Passing `--play` also plays the two intervals one after the other.
+    /// Passing `--stereo` will play the first interval in the left ear and
+    /// the second in the right, and `--repeats` will alternate between
+    /// them the given number of times.
+    ///
+    /// Ex. `rust-intonation compare -a 5/4 --play --repeats 3`
     Compare {
-        #[clap(short = 'r', long = "ratio")]
-        ratio: String,
+        /// The first interval, e.g. `5/4`, `31edo:10` or `386c`
+        #[clap(
+            short = 'a',
+            long = "a",
+            short_alias = 'r',
+            alias = "ratio",
+            value_name = "INTERVAL",
+            value_parser = interval_arg,
+        )]
+        a: String,
+        /// The second interval, in the same form as the first (defaults to the
+        /// nearest 12 EDO interval to the first)
+        #[clap(short = 'b', long = "b", value_name = "INTERVAL", value_parser = interval_arg)]
+        b: Option<String>,
+        /// Also play the two intervals, one after the other
+        #[cfg(feature = "audio")]
+        #[clap(long = "play")]
+        play: bool,
+        /// Play the first interval in the left ear and the second in the right
+        #[cfg(feature = "audio")]
+        #[clap(long = "stereo")]
+        stereo: bool,
+        /// The number of times to alternate between the two intervals
+        #[cfg(feature = "audio")]
+        #[clap(long = "repeats", default_value = "1")]
+        repeats: u32,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    /// Show the members of a chord of JI ratios.
+    ///
+    /// The chord can be given as a sequence of colon-separated harmonics,
+    /// with the first harmonic as the root, or as a list of ratios above
+    /// the root. Prints each member of the chord as a ratio over the root,
+    /// with its size in cents, and the nearest 12 EDO interval and the
+    /// cents difference between them.
+    ///
+    /// Ex. `rust-intonation chord -c 4:5:6:7`
+    ///
+    /// Ex. `rust-intonation chord -c 1/1 5/4 3/2 7/4`
+    ///
+    /// Passing `--play` also plays the notes of the chord one at a time,
+    /// and then together.
+    Chord {
+        /// The chord, as colon-separated harmonics, e.g. `4:5:6`, or as ratios
+        /// above the root, e.g. `1/1 5/4 3/2`
+        #[clap(
+            short = 'c',
+            long = "chord",
+            num_args = 1..,
+            value_name = "CHORD",
+            value_parser = chord_arg,
+        )]
+        chord: Vec<String>,
+        /// Also play the notes of the chord one at a time, and then together
+        #[cfg(feature = "audio")]
+        #[clap(long = "play")]
+        play: bool,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    #[cfg(feature = "audio")]
+    /// Render sounds to a WAV file instead of playing them.
+    ///
+    /// Writes exactly what the corresponding command would play: a ratio,
+    /// as with `play`, a chord, as with `chord`, a scale of ratios or an EDO
+    /// from the root up to the octave, or each otonality of a tonality
+    /// diamond. Accepts the same playback options as `play`.
+    ///
+    /// Ex. `rust-intonation wav -r 3/2 -o fifth.wav`
+    ///
+    /// Ex. `rust-intonation wav --diamond 1 3 5 7 --waveform harmonic -o diamond.wav`
+    #[command(group(ArgGroup::new("sound").required(true)))]
+    Wav {
+        /// A ratio, played as with `play`, e.g. `3/2`
+        #[clap(
+            short = 'r',
+            long = "ratio",
+            group = "sound",
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        ratio: Option<String>,
+        /// A chord, in the same form as for `chord`
+        #[clap(
+            short = 'c',
+            long = "chord",
+            num_args = 1..,
+            group = "sound",
+            value_name = "CHORD",
+            value_parser = chord_arg,
+        )]
+        chord: Vec<String>,
+        /// The degrees of a scale, e.g. `9/8 5/4 3/2`
+        #[clap(
+            long = "scale",
+            num_args = 1..,
+            group = "sound",
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        scale: Vec<String>,
+        /// The limits of a tonality diamond
+        #[clap(
+            long = "diamond",
+            num_args = 1..,
+            group = "sound",
+            value_parser = clap::value_parser!(u32).range(1..),
+        )]
+        diamond: Vec<u32>,
+        /// The number of equal divisions of the octave
+        #[clap(long = "edo", group = "sound", value_parser = clap::value_parser!(u32).range(1..))]
+        edo: Option<u32>,
+        /// The path of the WAV file to write
+        #[clap(short = 'o', long = "output", value_hint = ValueHint::FilePath)]
+        output: String,
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    /// Export JI ratios, a scale, or a lattice walk to a MIDI file.
+    ///
+    /// Writes exactly one of the given ratios, the degrees of a scale up to
+    /// its octave, or the ratios at each index of a walk through a lattice,
+    /// one after another, as a Standard MIDI File, with each pitch realized
+    /// as the nearest 12 EDO note, bent to the exact pitch. Passing `--chord`
+    /// writes them sounding together instead.
+    ///
+    /// Ex. `rust-intonation midi -r 1/1 9/8 5/4 4/3 3/2 -o scale.mid`
+    ///
+    /// Ex. `rust-intonation midi -o walk.mid --lattice 3/2 5/4 --walk 0,0 1,0 1,1 0,1`
+    #[command(group(ArgGroup::new("notes").required(true)))]
+    Midi {
+        /// The ratios to write, e.g. `1/1 5/4 3/2`
+        #[clap(
+            short = 'r',
+            long = "ratios",
+            num_args = 1..,
+            group = "notes",
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        ratios: Vec<String>,
+        /// The degrees of a scale, e.g. `9/8 5/4 3/2`, written from the root up to
+        /// the octave
+        #[clap(
+            long = "scale",
+            num_args = 1..,
+            group = "notes",
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        scale: Vec<String>,
+        /// The dimensions of a lattice, in the same form as for `lattice`
+        #[clap(
+            long = "lattice",
+            num_args = 1..,
+            group = "notes",
+            requires = "walk",
+            value_name = "N/D[@OFFSET][:BOUNDS]",
+            value_parser = dimension_arg,
+        )]
+        lattice: Vec<String>,
+        /// The indices of the lattice to walk through, in the same form as for
+        /// `lattice`
+        #[clap(
+            short = 'w',
+            long = "walk",
+            num_args = 1..,
+            requires = "lattice",
+            allow_hyphen_values = true,
+            value_name = "I,J,...",
+            value_parser = index_arg,
+        )]
+        walk: Vec<String>,
+        /// The path of the MIDI file to write
+        #[clap(short = 'o', long = "output", value_hint = ValueHint::FilePath)]
+        output: String,
+        /// Write the pitches sounding together, rather than one after another
+        #[clap(long = "chord")]
+        chord: bool,
+        /// The pitch bend range, in semitones, of the synth playing the file
+        #[clap(
+            long = "bend-range",
+            default_value = "2",
+            value_parser = clap::value_parser!(u8).range(1..),
+        )]
+        bend_range: u8,
+    },
+    #[cfg(feature = "mpe")]
+    /// Play JI ratios live on a MIDI synth using MPE.
+    ///
+    /// Sends the given ratios, one after another, to a MIDI output port,
+    /// with each note on its own channel, bent to the exact pitch. Passing
+    /// `--chord` plays them sounding together instead. The port is the first
+    /// one whose name contains `--port`, or the first available port, and
+    /// `--list` prints the available ports.
+    ///
+    /// Ex. `rust-intonation mpe -r 1/1 5/4 3/2 7/4 --chord --port Surge`
+    Mpe {
+        /// The ratios to play, e.g. `1/1 5/4 3/2`
+        #[clap(
+            short = 'r',
+            long = "ratios",
+            num_args = 1..,
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        ratios: Vec<String>,
+        /// Play the ratios sounding together, rather than one after another
+        #[clap(long = "chord")]
+        chord: bool,
+        /// Part of the name of the MIDI output port to play on
+        #[clap(short = 'p', long = "port")]
+        port: Option<String>,
+        /// Print the available MIDI output ports
+        #[clap(long = "list")]
+        list: bool,
+        /// The pitch bend range, in semitones, of the synth's MPE member channels
+        #[clap(
+            long = "bend-range",
+            default_value = "48",
+            value_parser = clap::value_parser!(u8).range(1..),
+        )]
+        bend_range: u8,
+        /// The length, in seconds, of each note
+        #[clap(long = "duration", default_value = "1", value_parser = positive_arg)]
+        duration: f32,
+    },
+    /// Generate a comma pump around a JI lattice.
+    ///
+    /// Builds the given chord on each point of a path, one step at a time,
+    /// through the lattice constructed from the given ratios, ending a comma
+    /// away from where it started. Prints the root and ratios of each chord,
+    /// then the drift, in cents, between the first and last roots, and, with
+    /// `--edo`, the drift when played in that EDO, which is zero if it tempers
+    /// out the comma. With a structured `--format`, each chord is a record,
+    /// with the drifts repeated in each. Passing `--play` also plays the
+    /// progression. Passing `--export musicxml` prints the progression as a
+    /// MusicXML score, built on middle C, instead, or writes it to the path
+    /// given by `--output`, as for `scale`.
+    ///
+    /// Ex. `rust-intonation pump --comma 81/80 -r 3/2 5/4 -c 4:5:6 --edo 12`
+    ///
+    /// Ex. `rust-intonation pump --comma 81/80 --export musicxml -o pump.musicxml`
+    Pump {
+        /// The comma the pump drifts by, e.g. `81/80`
+        #[clap(long = "comma", value_name = "N/D", value_parser = ratio_arg)]
+        comma: String,
+        /// The ratios of the lattice's dimensions, e.g. `3/2 5/4`
+        #[clap(
+            short = 'r',
+            long = "ratios",
+            num_args = 1..,
+            default_values = ["3/2", "5/4"],
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        ratios: Vec<String>,
+        /// The chord built on each root, in the same form as for `chord`
+        #[clap(
+            short = 'c',
+            long = "chord",
+            num_args = 1..,
+            default_values = ["4:5:6"],
+            value_name = "CHORD",
+            value_parser = chord_arg,
+        )]
+        chord: Vec<String>,
+        /// Also print the drift when the progression is played in the given EDO
+        #[clap(long = "edo", value_parser = clap::value_parser!(u32).range(1..))]
+        edo: Option<u32>,
+        /// The format to export the progression to, in place of printing it
+        #[clap(long = "export", value_enum)]
+        export: Option<PumpExport>,
+        /// The path of the file to export the progression to
+        #[clap(short = 'o', long = "output", requires = "export", value_hint = ValueHint::FilePath)]
+        output: Option<String>,
+        /// Also play the progression
+        #[cfg(feature = "audio")]
+        #[clap(long = "play")]
+        play: bool,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    /// Start an interactive prompt.
+    ///
+    /// Reads commands one line at a time, to look up ratios, build a lattice
+    /// once and query it many times, and compare intervals. With audio, it
+    /// also plays ratios and adjusts the playback settings, keeping the audio
+    /// device open between commands. Enter `help` for the list of commands,
+    /// and `quit` to leave.
+    ///
+    /// Ex. `rust-intonation repl --root 440`
+    Repl {
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    /// Build a scale and print its degrees.
+    ///
+    /// The scale is built from exactly one source: a list of ratios, the
+    /// limits of a tonality diamond, a segment of the harmonic series, a
+    /// region of a lattice, or an EDO. Each degree is printed in ascending
+    /// order with its size in cents and the step up to the next degree.
+    ///
+    /// Ex. `rust-intonation scale --harmonics 8 16`
+    ///
+    /// Ex. `rust-intonation scale --lattice 3/2 5/4 --region -1..1 0..1`
+    ///
+    /// Passing `--matrix` prints the scale's interval matrix instead: a row
+    /// for each degree, giving the interval from it up to each of the
+    /// following degrees in turn, ending with the period.
+    ///
+    /// Ex. `rust-intonation scale --harmonics 4 8 --matrix`
+    ///
+    /// Passing `--compare-edo` instead matches each degree with the nearest
+    /// step of the given EDO, printing the difference in cents from each,
+    /// followed, as text, by their largest, mean, and RMS differences.
+    ///
+    /// Ex. `rust-intonation scale --diamond 1 3 5 7 --compare-edo 31`
+    ///
+    /// Passing `--export scl` prints the scale as a Scala `.scl` file instead,
+    /// or writes it to the path given by `--output`. Passing `--export lilypond`
+    /// does the same with a LilyPond file notating the scale from middle C, with
+    /// the deviation of each degree from 12 EDO marked above it, and
+    /// `--export musicxml` with a MusicXML score, with each deviation encoded in
+    /// the note's alteration, and `--export json` with a JSON file in the schema
+    /// shared by `diamond`, `lattice` and `edo compare`.
+    ///
+    /// Ex. `rust-intonation scale --diamond 1 3 5 --export scl -o diamond.scl`
+    ///
+    /// Ex. `rust-intonation scale --edo 19 --export lilypond -o 19edo.ly`
+    ///
+    /// Passing `--play` also plays the scale, including the period at the
+    /// top, in the order given by `--scale-direction`, and at the tempo
+    /// given by `--bpm`.
+    ///
+    /// Ex. `rust-intonation scale --harmonics 8 16 --play --scale-direction up-down --bpm 90`
+    #[command(group(ArgGroup::new("source").required(true)))]
+    Scale {
+        #[command(flatten)]
+        source: ScaleSource,
+        /// The format to export the scale to, in place of printing it
+        #[clap(long = "export", value_enum)]
+        export: Option<ScaleExport>,
+        /// Print the interval between every pair of degrees
+        #[clap(long = "matrix", conflicts_with = "export")]
+        matrix: bool,
+        /// Compare each degree with the nearest step of the given EDO
+        #[clap(
+            long = "compare-edo",
+            value_name = "DIVISIONS",
+            conflicts_with_all = ["export", "matrix"],
+            value_parser = clap::value_parser!(u32).range(1..),
+        )]
+        compare_edo: Option<u32>,
+        /// The path of the file to export the scale to
+        #[clap(short = 'o', long = "output", requires = "export", value_hint = ValueHint::FilePath)]
+        output: Option<String>,
+        /// Also play the scale, including the period at the top
+        #[cfg(feature = "audio")]
+        #[clap(long = "play")]
+        play: bool,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+    /// Import and export Scala `.scl` files.
+    ///
+    /// Ex. `rust-intonation scl export --diamond 1 3 5 7 -o diamond.scl`
+    ///
+    /// Ex. `rust-intonation scl show diamond.scl`
+    Scl {
+        #[command(subcommand)]
+        mode: SclMode,
     },
     /// Construct a tonality diamond from the given limits.
     ///
@@ -59,9 +645,65 @@ enum SubCommand {
     ///
     /// will return a 3x3 tonality diamond of ratios that have only
     /// 1, 3 or 5 as their largest prime factor
+    ///
+    /// Passing `--cents` annotates each ratio with its size in cents, and
+    /// `--et` with the nearest 12 EDO interval and the cents difference
+    /// between them. Passing `--play` also plays each otonality of the
+    /// diamond, one note at a time.
+    ///
+    /// Ex. `rust-intonation diamond -l 1 5 3 --cents --et`
+    ///
+    /// Passing `--html` also writes the diamond to an HTML chart that shows
+    /// each ratio's size in cents, monzo and 12 EDO approximation on hover.
+    ///
+    /// Ex. `rust-intonation diamond -l 1 3 5 7 --html diamond.html`
+    ///
+    /// Passing `--json` also writes the diamond to a JSON file in the
+    /// schema shared by `lattice`, `scale` and `edo compare`, giving each
+    /// ratio's size in cents, monzo, name and 12 EDO approximation, and the
+    /// identities of its cell, e.g. "5 over 3".
+    ///
+    /// Ex. `rust-intonation diamond -l 1 3 5 7 --json diamond.json`
+    ///
+    /// At a terminal, the diamond is fitted to its width, taken from
+    /// `$COLUMNS`, or 80 columns if it is not set, or to `--max-width`:
+    /// cells are aligned with spaces rather than tabs if the diamond is too
+    /// wide, and split into pages of columns if it is still too wide.
+    ///
+    /// Ex. `rust-intonation diamond -l 1 3 5 7 9 11 13 15 --max-width 60`
     Diamond {
-        #[clap(short = 'l', long = "limits", num_args = 1.., default_values = ["1", "5", "3"])]
+        #[clap(
+            short = 'l',
+            long = "limits",
+            num_args = 1..,
+            default_values = ["1", "5", "3"],
+            value_parser = clap::value_parser!(u32).range(1..),
+        )]
         limits: Vec<u32>,
+        /// Annotate each ratio with its size in cents
+        #[clap(long = "cents")]
+        cents: bool,
+        /// Annotate each ratio with the nearest 12 EDO interval and the cents
+        /// difference between them
+        #[clap(long = "et")]
+        et: bool,
+        /// The path of an HTML chart to write the diamond to
+        #[clap(long = "html", value_name = "PATH", value_hint = ValueHint::FilePath)]
+        html: Option<String>,
+        /// The path of a JSON file to write the diamond to
+        #[clap(long = "json", value_name = "PATH", value_hint = ValueHint::FilePath)]
+        json: Option<String>,
+        /// The width, in characters, to fit the diamond to, in place of the
+        /// width of the terminal
+        #[clap(long = "max-width", value_name = "COLUMNS")]
+        max_width: Option<usize>,
+        /// Also play each otonality of the diamond, one note at a time
+        #[cfg(feature = "audio")]
+        #[clap(long = "play")]
+        play: bool,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
     },
     /// Create and query a JI lattice.
     ///
@@ -75,12 +717,163 @@ enum SubCommand {
     /// for a 3-dimensional lattice constructed from the ratios 3/2, 5/4,
     /// and 7/4.
     ///
+    /// Each dimension is infinite unless its ratio is followed by a bound:
+    /// `:inf` for an infinite dimension, `:n` for one of length `n`, or
+    /// `:a..b` for one spanning the indices `a` to `b` inclusive.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2:inf 5/4:-2..3 7/4:4 -i 1,4,5`
+    ///
+    /// A ratio followed by `@n`, before any bound, shifts the origin of
+    /// its dimension to the `n`th power of the ratio, e.g. `3/2@-2` to
+    /// center the lattice on the tonic two fifths down.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2@-2:-3..3 5/4 -i 0,0 2,1`
+    ///
+    /// Passing `--html` with `--region` also writes the region spanned by
+    /// the given range of indices along each dimension to an HTML chart,
+    /// which shows each ratio's size in cents, monzo and 12 EDO
+    /// approximation on hover.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2 5/4 --html lattice.html --region -3..3 -1..1`
+    ///
+    /// Passing `--json` with `--region` does the same with a JSON file, in
+    /// the schema shared by `diamond`, `scale` and `edo compare`.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2 5/4 7/4 --json lattice.json --region -2..2 -1..1 0..1`
+    ///
+    /// Passing `--stats` with `--region` prints statistics of the region:
+    /// its number of distinct pitch classes, their span in cents, the
+    /// lowest and highest Tenney height among them, and the smallest
+    /// interval between any two.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2 5/4 --stats --region -2..3 -1..1`
+    ///
+    /// Passing `--diamond` with `--region` prints each ratio of the
+    /// tonality diamond of the given limits, with the identities of its
+    /// cells and the indices at which it lies in the region, if any,
+    /// followed by whether the region contains the whole diamond and
+    /// whether the diamond contains the whole region, which a structured
+    /// `--format` repeats in each record instead. Only one of `--indices`,
+    /// `--stats` and `--diamond` can be given with a structured `--format`,
+    /// so that the records printed share their columns.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2 5/4 --diamond 1 3 5 --region -1..1 -1..1`
+    ///
+    /// Passing `--play` plays the ratios at the given indices one after
+    /// another as a walk through the lattice, followed by every ratio in
+    /// the region, if one is given.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2 5/4 --play -i 0,0 1,0 1,1 0,1`
+    ///
+    /// Passing `--file` reads the dimensions from a TOML description,
+    /// with a `[[dimension]]` table for each holding its `ratio`, and
+    /// optionally its `length` or `range` and a `label`, in place of
+    /// `--ratios`. Passing `--toml` writes the description of the
+    /// lattice to a file.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2 5/4:3 7/4:-1..1 --toml lattice.toml`
+    ///
+    /// Ex. `rust-intonation lattice --file lattice.toml -i 1,2,-1`
+    ///
+    /// Passing `--indices-file` reads the indices to look up from a file
+    /// in place of `--indices`, one comma-separated set on each line,
+    /// ignoring blank lines and `#` comments.
+    ///
+    /// Ex. `rust-intonation lattice -r 3/2 5/4 --indices-file walk.txt --play`
+    ///
     /// If no indices are given, there will be no output.
     Lattice {
-        #[clap(short = 'r', long = "ratios", num_args = 1.., default_values = ["3/2", "5/4"])]
+        /// The ratio of each dimension, optionally followed by the offset of
+        /// its origin and its bounds, e.g. `3/2`, `3/2:inf`, `5/4:3`, `7/4:-1..1`
+        /// or `3/2@-2:-3..3`
+        #[clap(
+            short = 'r',
+            long = "ratios",
+            num_args = 1..,
+            default_values = ["3/2", "5/4"],
+            value_name = "N/D[@OFFSET][:BOUNDS]",
+            value_parser = dimension_arg,
+        )]
         ratios: Vec<String>,
-        #[clap(short = 'i', long = "indices", num_args = 0.., allow_hyphen_values = true)]
+        /// The path of a TOML description of the lattice to read the
+        /// dimensions from, in place of `--ratios`
+        #[clap(
+            short = 'f',
+            long = "file",
+            conflicts_with = "ratios",
+            value_name = "PATH",
+            value_hint = ValueHint::FilePath,
+        )]
+        file: Option<String>,
+        /// The path of a TOML file to write the description of the lattice to
+        #[clap(long = "toml", value_name = "PATH", value_hint = ValueHint::FilePath)]
+        toml: Option<String>,
+        /// The indices to look up, with one comma-separated integer for each
+        /// dimension, e.g. `1,0` or `-1,2`
+        #[clap(
+            short = 'i',
+            long = "indices",
+            num_args = 0..,
+            allow_hyphen_values = true,
+            value_name = "I,J,...",
+            value_parser = index_arg,
+        )]
         indices: Vec<String>,
+        /// The path of a file to read the indices to look up from, in place of
+        /// `--indices`, with one comma-separated set on each line
+        #[clap(
+            long = "indices-file",
+            conflicts_with = "indices",
+            value_name = "PATH",
+            value_hint = ValueHint::FilePath,
+        )]
+        indices_file: Option<String>,
+        /// The path of an HTML chart to write the region of the lattice to
+        #[clap(
+            long = "html",
+            requires = "region",
+            value_name = "PATH",
+            value_hint = ValueHint::FilePath,
+        )]
+        html: Option<String>,
+        /// The path of a JSON file to write the region of the lattice to
+        #[clap(
+            long = "json",
+            requires = "region",
+            value_name = "PATH",
+            value_hint = ValueHint::FilePath,
+        )]
+        json: Option<String>,
+        /// The inclusive range of indices, `a..b`, along each dimension of the
+        /// lattice to chart, export or play
+        #[clap(
+            long = "region",
+            num_args = 1..,
+            allow_hyphen_values = true,
+            value_name = "A..B",
+            value_parser = range_arg,
+        )]
+        region: Vec<String>,
+        /// Print statistics of the region of the lattice
+        #[clap(long = "stats", requires = "region")]
+        stats: bool,
+        /// The limits of a tonality diamond to locate in the region of the
+        /// lattice
+        #[clap(
+            long = "diamond",
+            num_args = 1..,
+            requires = "region",
+            value_name = "LIMITS",
+            value_parser = clap::value_parser!(u32).range(1..),
+        )]
+        diamond: Vec<u32>,
+        /// Play the ratios at the given indices, then every ratio in the region
+        #[cfg(feature = "audio")]
+        #[clap(long = "play")]
+        play: bool,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
     },
     /// Find the ET approximation of JI ratios.
     ///
@@ -92,62 +885,1351 @@ enum SubCommand {
     ///
     /// will return `(PerfectFifth, 1.954956)`, that is, the ratio 3/2 is greater
     /// than an ET perfect fifth by ~2 cents.
+    ///
+    /// When no ratios are given, or just `-`, they are read from stdin,
+    /// separated by whitespace or newlines.
+    ///
+    /// Ex. `cat ratios.txt | rust-intonation ratios`
+    ///
+    /// Passing `--root` also names the pitch of each ratio above the given
+    /// note, by its nearest note and its deviation in cents.
+    ///
+    /// Ex. `rust-intonation ratios -r 5/4 7/4 --root A3`
     Ratios {
-        #[clap(short = 'r', long = "ratio", num_args = 0..)]
+        /// The ratios to approximate, e.g. `3/2 7/4`, or `-` to read them from stdin
+        #[clap(short = 'r', long = "ratio", num_args = 0.., value_name = "N/D")]
         ratios: Vec<String>,
+        /// The note the ratios are above, e.g. `C4` or `Bb3`
+        #[clap(long = "root", value_name = "NOTE", value_parser = note_arg)]
+        root: Option<String>,
+    },
+    /// Find the simplest JI ratios near a size in cents.
+    ///
+    /// Lists every ratio with a denominator no greater than `--max-denominator`
+    /// within `--tolerance` cents of the given size, from simplest to most
+    /// complex by Tenney height, with its size in cents, its error from the
+    /// given size, and its Tenney height.
+    ///
+    /// Ex. `rust-intonation approximate -c 386 --max-denominator 64`
+    ///
+    /// Several sizes can be given at once, and are read from stdin when
+    /// none are given, or just `-`, so each ratio is listed after the size
+    /// it approximates.
+    ///
+    /// Ex. `cat cents.txt | rust-intonation approximate`
+    Approximate {
+        /// The sizes, in cents, to approximate, e.g. `386`, or `-` to read them from
+        /// stdin
+        #[clap(
+            short = 'c',
+            long = "cents",
+            num_args = 0..,
+            allow_hyphen_values = true,
+            value_name = "CENTS",
+        )]
+        cents: Vec<String>,
+        /// The largest denominator of the ratios listed
+        #[clap(long = "max-denominator", default_value = "64")]
+        max_denominator: i32,
+        /// The largest error, in cents, of the ratios listed
+        #[clap(short = 't', long = "tolerance", default_value = "10")]
+        tolerance: f64,
+    },
+    /// List named JI intervals, commas included, up to a prime limit.
+    ///
+    /// Prints each interval with its name, ratio and size in cents, grouped
+    /// by prime limit, from smallest to largest within each group.
+    ///
+    /// Ex. `rust-intonation intervals --limit 7`
+    ///
+    /// Passing `--name` instead prints the one interval of that name,
+    /// ignoring case, and treating hyphens and underscores as spaces.
+    ///
+    /// Ex. `rust-intonation intervals --name "septimal minor third"`
+    Intervals {
+        /// The greatest prime limit of the intervals listed
+        #[clap(
+            short = 'l',
+            long = "limit",
+            default_value = "13",
+            conflicts_with = "name"
+        )]
+        limit: i32,
+        /// The name of the interval to print, e.g. `"perfect fifth"`
+        #[clap(short = 'n', long = "name", value_parser = interval_name_arg)]
+        name: Option<String>,
     },
     /// Prints out the harmonic series from the given limit down to 1.
     ///
     /// Prints each harmonic as a JI ratio, along with the nearest 12EDO interval
     /// and the cents difference between them
     Series {
+        /// The highest harmonic to print
         #[clap(short = 'l', long = "limit")]
         limit: i32,
     },
     /// Show the steps of an EDO as compared to 12 EDO
+    ///
+    /// Prints each step of the EDO with a name describing its size,
+    /// along with the nearest 12 EDO interval and the cents difference
+    /// between them.
+    ///
+    /// Additional modes are available as subcommands.
+    ///
+    /// Ex. `rust-intonation edo -e 31 analyze -l 9`
+    ///
+    /// To see how well the EDO approximates JI, the `compare` mode instead
+    /// lists the intervals of an odd limit, or the given ratios, each with
+    /// the nearest step of the EDO and its error.
+    ///
+    /// Ex. `rust-intonation edo -e 31 compare -r 5/4 7/4 11/8`
+    ///
+    /// Passing `--commas` with a prime limit will instead list the commas
+    /// in that limit which the EDO tempers out.
+    ///
+    /// Ex. `rust-intonation edo -e 12 --commas 5`
     Edo {
-        #[clap(short = 'e', long = "edo", num_args = 1)]
+        /// The number of equal divisions of the octave
+        #[clap(short = 'e', long = "edo", num_args = 1, value_parser = clap::value_parser!(u32).range(1..))]
         edo: u32,
+        /// List the commas in the given prime limit that the EDO tempers out
+        #[clap(long = "commas")]
+        commas: Option<u32>,
+        /// The largest numerator of the commas searched for
+        #[clap(long = "max-complexity", default_value = "1000")]
+        max_complexity: u32,
+        #[command(subcommand)]
+        mode: Option<EdoMode>,
+    },
+    /// Print a shell completion script.
+    ///
+    /// Completes subcommands and options, the values of options with a
+    /// fixed set of values, and paths for options taking files.
+    ///
+    /// Ex. `rust-intonation completions bash > /etc/bash_completion.d/rust-intonation`
+    ///
+    /// Ex. `rust-intonation completions fish > ~/.config/fish/completions/rust-intonation.fish`
+    Completions {
+        /// The shell to print the script for
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[cfg(feature = "audio")]
+/// Options controlling audio playback, shared by the subcommands that play sound.
+#[derive(Args, Debug, Clone)]
+struct PlaybackArgs {
+    /// The frequency of the root pitch, in Hz (defaults to middle C)
+    #[clap(long = "root", value_parser = positive_arg)]
+    root: Option<f32>,
+    /// The length of each note, in seconds
+    #[clap(long = "duration", default_value = "2", value_parser = positive_arg)]
+    duration: f32,
+    /// The volume of each note, from 0 to 1
+    #[clap(long = "volume", default_value = "0.2", value_parser = unit_arg)]
+    volume: f32,
+    /// The silence, in seconds, between successive sounds
+    #[clap(long = "gap", default_value = "0.25", value_parser = non_negative_arg)]
+    gap: f32,
+    /// The shape of the wave each note is played with
+    #[clap(long = "waveform", value_enum, default_value_t = WaveformArg::Sine)]
+    waveform: WaveformArg,
+    /// The number of partials in a harmonic waveform
+    #[clap(long = "partials", default_value = "8")]
+    partials: u32,
+    /// How quickly the partials of a harmonic waveform get quieter
+    #[clap(long = "rolloff", default_value = "1")]
+    rolloff: f32,
+    /// The time, in seconds, each note takes to fade in
+    #[clap(long = "attack", default_value = "0.01")]
+    attack: f32,
+    /// The time, in seconds, each note takes to fade out
+    #[clap(long = "release", default_value = "0.05")]
+    release: f32,
+    /// The volume, relative to the notes, at which to mix in the difference
+    /// tones of notes played together
+    #[clap(long = "difference-tones", default_value = "0")]
+    difference_tones: f32,
+    /// The stereo position of each note, from -1 (left) to 1 (right)
+    #[clap(long = "pan", default_value = "0", allow_hyphen_values = true)]
+    pan: f32,
+    /// Spread notes played together from left to right, e.g. to hear the
+    /// root of a dyad in the left ear and the interval in the right
+    #[clap(long = "spread")]
+    spread: bool,
+    /// How the notes of a chord are presented
+    #[clap(long = "chord-style", value_enum, default_value_t = ChordStyleArg::Block)]
+    chord_style: ChordStyleArg,
+    /// The time, in seconds, between the onsets of the notes of a strummed chord
+    #[clap(long = "strum-delay", default_value = "0.05", value_parser = non_negative_arg)]
+    strum_delay: f32,
+    /// The order in which the degrees of a scale are played
+    #[clap(long = "scale-direction", value_enum, default_value_t = ScaleDirectionArg::Ascending)]
+    scale_direction: ScaleDirectionArg,
+    /// The tempo, in beats per minute, at which each note lasts one beat,
+    /// instead of `--duration` seconds
+    #[clap(long = "bpm", conflicts_with = "duration", value_parser = positive_arg)]
+    bpm: Option<f32>,
+}
+
+#[cfg(feature = "audio")]
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ChordStyleArg {
+    /// Every note at once
+    Block,
+    /// One note at a time, from the lowest up
+    Ascending,
+    /// One note at a time, from the highest down
+    Descending,
+    /// Every note held, each starting `--strum-delay` after the one below
+    Strum,
+}
+
+#[cfg(feature = "audio")]
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ScaleDirectionArg {
+    /// From the root up to the period
+    Ascending,
+    /// From the period down to the root
+    Descending,
+    /// From the root up to the period and back down
+    UpDown,
+}
+
+#[cfg(feature = "audio")]
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum WaveformArg {
+    /// A pure sine wave
+    Sine,
+    /// A triangle wave
+    Triangle,
+    /// A sawtooth wave
+    Saw,
+    /// A square wave
+    Square,
+    /// A sum of harmonics, set by `--partials` and `--rolloff`
+    Harmonic,
+}
+
+#[cfg(feature = "audio")]
+impl From<PlaybackArgs> for PlaybackConfig {
+    fn from(value: PlaybackArgs) -> Self {
+        let config = Self {
+            root: value.root.unwrap_or(Frequency::MIDDLE_C.0),
+            duration: value.duration,
+            volume: value.volume,
+            gap: value.gap,
+            waveform: match value.waveform {
+                WaveformArg::Sine => Waveform::Sine,
+                WaveformArg::Triangle => Waveform::Triangle,
+                WaveformArg::Saw => Waveform::Sawtooth,
+                WaveformArg::Square => Waveform::Square,
+                WaveformArg::Harmonic => Waveform::Harmonic {
+                    partials: value.partials,
+                    rolloff: value.rolloff,
+                },
+            },
+            envelope: Envelope::fade(value.attack, value.release),
+            difference_tones: value.difference_tones,
+            pan: value.pan,
+            spread: value.spread,
+            chord_style: match value.chord_style {
+                ChordStyleArg::Block => ChordStyle::Block,
+                ChordStyleArg::Ascending => ChordStyle::Ascending,
+                ChordStyleArg::Descending => ChordStyle::Descending,
+                ChordStyleArg::Strum => ChordStyle::Strum(value.strum_delay),
+            },
+            scale_direction: match value.scale_direction {
+                ScaleDirectionArg::Ascending => ScaleDirection::Ascending,
+                ScaleDirectionArg::Descending => ScaleDirection::Descending,
+                ScaleDirectionArg::UpDown => ScaleDirection::UpDown,
+            },
+        };
+        match value.bpm {
+            Some(bpm) => config.at_tempo(bpm),
+            None => config,
+        }
+    }
+}
+
+/// An interval given on the command line, as a JI ratio, a step of an EDO, or a size in
+/// cents.
+#[derive(Debug, Clone, Copy)]
+enum IntervalArg {
+    Ratio(Ratio<i32>),
+    Edo { edo: Edo, steps: i32 },
+    Cents(f64),
+}
+
+impl IntervalArg {
+    fn cents(&self) -> f64 {
+        match self {
+            Self::Ratio(ratio) => ratio.cents(),
+            Self::Edo { edo, steps } => *steps as f64 * edo.step_size(),
+            Self::Cents(cents) => *cents,
+        }
+    }
+}
+
+impl std::fmt::Display for IntervalArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ratio(ratio) => write!(f, "{}", ratio),
+            Self::Edo { edo, steps } => write!(f, "{}edo:{}", edo.divisions, steps),
+            Self::Cents(cents) => write!(f, "{}c", cents),
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Play for IntervalArg {
+    fn play(&self, player: &Player) {
+        let root = Frequency(player.config.root);
+        player.play_dyad(root.0, root.transpose(self.cents()).0);
+    }
+}
+
+/// The options choosing what a scale is built from, exactly one of which must be given
+/// in the `source` group of the subcommand they are flattened into.
+#[derive(Args, Debug, Clone)]
+#[group(skip)]
+struct ScaleSource {
+    /// The degrees of the scale, e.g. `9/8 5/4 3/2`
+    #[clap(
+        short = 'r',
+        long = "ratios",
+        num_args = 1..,
+        group = "source",
+        value_name = "N/D",
+        value_parser = ratio_arg,
+    )]
+    ratios: Vec<String>,
+    /// The limits of a tonality diamond
+    #[clap(
+        long = "diamond",
+        num_args = 1..,
+        group = "source",
+        value_parser = clap::value_parser!(u32).range(1..),
+    )]
+    diamond: Vec<u32>,
+    /// The first and last harmonics of a segment of the harmonic series
+    #[clap(
+        long = "harmonics",
+        num_args = 2,
+        value_names = ["FROM", "TO"],
+        group = "source",
+        value_parser = clap::value_parser!(i32).range(1..),
+    )]
+    harmonics: Vec<i32>,
+    /// The dimensions of a lattice, in the same form as for `lattice`
+    #[clap(
+        long = "lattice",
+        num_args = 1..,
+        group = "source",
+        requires = "region",
+        value_name = "N/D[@OFFSET][:BOUNDS]",
+        value_parser = dimension_arg,
+    )]
+    lattice: Vec<String>,
+    /// The inclusive range of indices, `a..b`, along each dimension of the lattice
+    #[clap(
+        long = "region",
+        num_args = 1..,
+        allow_hyphen_values = true,
+        value_name = "A..B",
+        value_parser = range_arg,
+    )]
+    region: Vec<String>,
+    /// The number of equal divisions of the octave
+    #[clap(long = "edo", group = "source", value_parser = clap::value_parser!(u32).range(1..))]
+    edo: Option<u32>,
+}
+
+impl ScaleSource {
+    fn build(self) -> Scale {
+        if let Some(edo) = self.edo {
+            Scale::from_edo(Edo::new(edo))
+        } else if let [from, to] = self.harmonics[..] {
+            Scale::harmonic_segment(from, to)
+        } else if !self.diamond.is_empty() {
+            Scale::from_diamond(&Diamond::<i32>::new(self.diamond))
+        } else if !self.lattice.is_empty() {
+            let lattice = Lattice::new(self.lattice.iter().map(|r| parse_dimension(r)).collect());
+            let region: Vec<RangeInclusive<i32>> =
+                self.region.iter().map(|r| parse_range(r)).collect();
+            Scale::from_lattice_region(&lattice, &region)
+        } else {
+            Scale::from_ratios(&parse_ratios(self.ratios))
+        }
+    }
+}
+
+/// The file formats a scale can be exported to.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ScaleExport {
+    /// A Scala `.scl` file
+    Scl,
+    /// A LilyPond `.ly` file
+    Lilypond,
+    /// A MusicXML `.musicxml` file
+    Musicxml,
+    /// A JSON file, with each degree's size in cents and 12 EDO approximation
+    Json,
+}
+
+/// The file formats a comma pump's progression can be exported to.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum PumpExport {
+    /// A MusicXML `.musicxml` file
+    Musicxml,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum SclMode {
+    /// Export a scale as a Scala `.scl` file.
+    ///
+    /// The scale is built from exactly one source, given in the same way
+    /// as for `scale`, and printed as a `.scl` file, or written to the path
+    /// given by `--output`.
+    ///
+    /// Ex. `rust-intonation scl export --harmonics 8 16 -o otonal.scl`
+    #[command(group(ArgGroup::new("source").required(true)))]
+    Export {
+        #[command(flatten)]
+        source: ScaleSource,
+        /// The path of the `.scl` file to write
+        #[clap(short = 'o', long = "output", value_hint = ValueHint::FilePath)]
+        output: Option<String>,
+    },
+    /// Show the degrees of a Scala `.scl` file.
+    ///
+    /// Prints the scale's description, followed by each degree in ascending
+    /// order with its size in cents and the step up to the next degree.
+    /// Passing `--play` also plays the scale, including the period, in the
+    /// order given by `--scale-direction`.
+    ///
+    /// Ex. `rust-intonation scl show diamond.scl --play`
+    Show {
+        /// The path of the `.scl` file to read
+        #[clap(value_hint = ValueHint::FilePath)]
+        path: String,
+        /// Also play the scale, including the period
+        #[cfg(feature = "audio")]
+        #[clap(long = "play")]
+        play: bool,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        playback: PlaybackArgs,
     },
 }
 
+#[derive(Subcommand, Debug, Clone)]
+enum EdoMode {
+    /// Report the consistency and accuracy of the EDO in an odd limit.
+    ///
+    /// Prints whether the EDO is consistent in the given odd limit, and
+    /// the largest error, in cents, among the intervals of that limit's
+    /// tonality diamond.
+    ///
+    /// Ex. `rust-intonation edo -e 31 analyze -l 9`
+    Analyze {
+        /// The odd limit to analyze the EDO in
+        #[clap(short = 'l', long = "limit", default_value = "9")]
+        limit: u32,
+    },
+    /// Compare the EDO to the JI intervals of an odd limit.
+    ///
+    /// Prints each interval of the odd limit's tonality diamond, in order
+    /// of size, with the nearest step of the EDO, and the error of that
+    /// step in cents.
+    ///
+    /// Ex. `rust-intonation edo -e 31 compare -l 7`
+    ///
+    /// Passing `--ratios` compares the EDO to the given JI ratios instead,
+    /// in the order they are given.
+    ///
+    /// Ex. `rust-intonation edo -e 22 compare -r 9/8 5/4 11/8 3/2`
+    ///
+    /// Passing `--json` also writes the comparison to a JSON file, in the
+    /// schema shared by `diamond`, `lattice` and `scale`.
+    ///
+    /// Ex. `rust-intonation edo -e 31 compare -l 9 --json 31edo.json`
+    Compare {
+        /// The odd limit whose intervals to compare the EDO to
+        #[clap(short = 'l', long = "limit", default_value = "7")]
+        limit: u32,
+        /// The ratios to compare the EDO to, e.g. `5/4 7/4 11/8`
+        #[clap(
+            short = 'r',
+            long = "ratios",
+            num_args = 1..,
+            conflicts_with = "limit",
+            value_name = "N/D",
+            value_parser = ratio_arg,
+        )]
+        ratios: Vec<String>,
+        /// The path of a JSON file to write the comparison to
+        #[clap(long = "json", value_name = "PATH", value_hint = ValueHint::FilePath)]
+        json: Option<String>,
+    },
+    #[cfg(feature = "audio")]
+    /// Play every step of the EDO in ascending order.
+    ///
+    /// Ex. `rust-intonation edo -e 31 play --duration 0.25`
+    Play {
+        #[command(flatten)]
+        playback: PlaybackArgs,
+    },
+}
+
+impl Cli {
+    /// Replaces the defaults of the options not given on the command line with those
+    /// from the configuration file.
+    fn apply_config(&mut self, config: Config, matches: &ArgMatches) {
+        let given = |id| given_on_command_line(matches, id);
+
+        if let (Some(format), false) = (config.format, given("format")) {
+            self.format = format;
+        }
+        if let SubCommand::Lattice { ratios, .. } | SubCommand::Pump { ratios, .. } = &mut self.cmd
+        {
+            if let (Some(config_ratios), false) = (config.lattice_ratios, given("ratios")) {
+                *ratios = config_ratios;
+            }
+        }
+        #[cfg(feature = "audio")]
+        if let Some(playback) = self.cmd.playback_mut() {
+            playback.root = playback.root.or(config.root);
+            if let (Some(duration), false) = (config.duration, given("duration")) {
+                playback.duration = duration;
+            }
+            if let (Some(volume), false) = (config.volume, given("volume")) {
+                playback.volume = volume;
+            }
+            if let (Some(gap), false) = (config.gap, given("gap")) {
+                playback.gap = gap;
+            }
+        }
+    }
+
+    /// Rejects a structured `--format` given on the command line to a subcommand that
+    /// prints no results, rather than ignoring it. A default from the configuration
+    /// file applies to every subcommand, so is left alone.
+    ///
+    /// Also rejects a structured `--format` for a lattice asked to print more than one
+    /// set of records, whose columns differ, into the same output.
+    fn check_format(&self, matches: &ArgMatches) {
+        let prints_results = match &self.cmd {
+            SubCommand::Midi { .. } => false,
+            #[cfg(feature = "mpe")]
+            SubCommand::Mpe { list, .. } => *list,
+            _ => true,
+        };
+        if !prints_results
+            && self.format != Format::Text
+            && given_on_command_line(matches, "format")
+        {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "--format has no effect on a command that prints no results",
+                )
+                .exit();
+        }
+        if let SubCommand::Lattice {
+            indices,
+            indices_file,
+            stats,
+            diamond,
+            ..
+        } = &self.cmd
+        {
+            let record_sets = [
+                !indices.is_empty() || indices_file.is_some(),
+                *stats,
+                !diamond.is_empty(),
+            ];
+            if self.format != Format::Text && record_sets.iter().filter(|s| **s).count() > 1 {
+                Cli::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        format!(
+                            "only one of --indices, --stats and --diamond can be printed as {:?}",
+                            self.format
+                        )
+                        .to_lowercase(),
+                    )
+                    .exit();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+impl SubCommand {
+    fn playback_mut(&mut self) -> Option<&mut PlaybackArgs> {
+        match self {
+            Self::Play { playback, .. }
+            | Self::Tone { playback, .. }
+            | Self::Drone { playback, .. }
+            | Self::Scale { playback, .. }
+            | Self::Nudge { playback, .. }
+            | Self::Compare { playback, .. }
+            | Self::Chord { playback, .. }
+            | Self::Diamond { playback, .. }
+            | Self::Lattice { playback, .. }
+            | Self::Wav { playback, .. }
+            | Self::Pump { playback, .. }
+            | Self::Repl { playback }
+            | Self::Scl {
+                mode: SclMode::Show { playback, .. },
+            }
+            | Self::Edo {
+                mode: Some(EdoMode::Play { playback }),
+                ..
+            } => Some(playback),
+            _ => None,
+        }
+    }
+}
+
 pub fn run() {
-    let args = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut args = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let config = Config::load().unwrap_or_else(|err| {
+        exit_with_error(format!("could not load the configuration file: {}", err))
+    });
+    args.apply_config(config, &matches);
+    args.check_format(&matches);
+    let format = args.format;
+    let verbosity = Verbosity::new(args.quiet, args.verbose);
+    #[cfg(feature = "audio")]
+    let no_audio = args.no_audio;
+    #[cfg(feature = "audio")]
+    let player = |playback: PlaybackArgs| {
+        let config = playback.into();
+        match (no_audio, verbosity) {
+            (true, Verbosity::Quiet) => Player::dry_run(config),
+            (true, _) => Player::dry_run(config).log_to(std::io::stdout()),
+            (false, Verbosity::Verbose) => Player::new(config).log_to(std::io::stderr()),
+            (false, _) => Player::new(config),
+        }
+    };
     match args.cmd {
-        SubCommand::Play { ratio } => {
+        #[cfg(feature = "audio")]
+        SubCommand::Play {
+            ratio,
+            beats: true,
+            playback,
+        } => {
             let ratio = parse_ratio(&ratio);
-            ratio.play();
+            let player = player(playback);
+            println!("{} Hz", ratio.beat_rate(player.config.root as f64));
+            ratio.play_beats(&player);
         }
-        SubCommand::Compare { ratio } => {
+        #[cfg(feature = "audio")]
+        SubCommand::Play {
+            ratio, playback, ..
+        } => {
             let ratio = parse_ratio(&ratio);
-            ratio.play();
-
-            std::thread::sleep(Duration::from_secs_f32(0.5));
+            ratio.play(&player(playback));
+        }
+        #[cfg(feature = "audio")]
+        SubCommand::Tone {
+            frequency,
+            note,
+            offset_cents,
+            a4,
+            playback,
+        } => {
+            let a4 = Frequency(a4);
+            let tone = match (frequency, note) {
+                (Some(frequency), _) => Frequency(frequency),
+                (None, note) => note.unwrap().parse::<Note>().unwrap().frequency(a4),
+            }
+            .transpose(offset_cents);
+            let name = Pitch::<i32>::from(tone).name(a4);
+            match format {
+                Format::Text => println!("{}\t{}", tone, name),
+                format => print_records(
+                    &[Record(vec![
+                        ("frequency", Value::Number(tone.0 as f64)),
+                        ("note", Value::Text(name)),
+                    ])],
+                    format,
+                ),
+            }
+            tone.play(&player(playback));
+        }
+        #[cfg(feature = "audio")]
+        SubCommand::Drone {
+            harmonics,
+            ratios,
+            amplitudes,
+            seconds,
+            playback,
+        } => {
+            let drone = if ratios.is_empty() {
+                let harmonics: Vec<u32> = std::iter::once(1)
+                    .chain(harmonics.into_iter().filter(|h| *h != 1))
+                    .collect();
+                Drone::from_harmonics(&harmonics)
+            } else {
+                let root = Ratio::new(1, 1);
+                let ratios: Vec<Ratio<i32>> = std::iter::once(root)
+                    .chain(parse_ratios(ratios).into_iter().filter(|r| *r != root))
+                    .collect();
+                Drone::from_ratios(&ratios)
+            }
+            .with_amplitudes(&amplitudes);
+            let drone = match seconds {
+                Some(seconds) => drone.sustained_for(seconds),
+                None => drone,
+            };
+            drone.play(&player(playback));
+        }
+        #[cfg(feature = "audio")]
+        SubCommand::Nudge {
+            start,
+            step,
+            tolerance,
+            max_denominator,
+            playback,
+        } => {
+            let comparison = PitchComparison::new(start);
+            let handle = if no_audio {
+                comparison.play(&player(playback));
+                None
+            } else {
+                Some(comparison.play_async(&playback.into()))
+            };
+            Nudge::new(comparison, step.into(), tolerance.into(), max_denominator)
+                .run(std::io::stdin().lock(), std::io::stdout())
+                .unwrap();
+            if let Some(handle) = handle {
+                handle.stop();
+                handle.wait();
+            }
+        }
+        SubCommand::Compare {
+            a,
+            b,
+            #[cfg(feature = "audio")]
+            play,
+            #[cfg(feature = "audio")]
+            stereo,
+            #[cfg(feature = "audio")]
+            repeats,
+            #[cfg(feature = "audio")]
+            playback,
+        } => {
+            let a = parse_interval(&a);
+            let b = match b {
+                Some(b) => parse_interval(&b),
+                None => IntervalArg::Edo {
+                    edo: Edo::new(12),
+                    steps: (a.cents() / 100.).round() as i32,
+                },
+            };
+            let difference = b.cents() - a.cents();
+            match format {
+                Format::Text => {
+                    println!("{}\t{}", a, a.cents());
+                    println!("{}\t{}", b, b.cents());
+                    println!("difference\t{}", difference);
+                }
+                format => {
+                    let record = Record(vec![
+                        ("a", Value::Text(a.to_string())),
+                        ("a_cents", Value::Number(a.cents())),
+                        ("b", Value::Text(b.to_string())),
+                        ("b_cents", Value::Number(b.cents())),
+                        ("difference", Value::Number(difference)),
+                    ]);
+                    print_records(&[record], format);
+                }
+            }
+            #[cfg(feature = "audio")]
+            if play {
+                let mut player = player(playback);
+                let gap = 2. * player.config.gap;
 
-            let (et, _) = ratio.to_approximate_12_edo_interval();
-            et.play()
+                compare(&mut player, &a, &b, repeats, gap, stereo);
+            }
+        }
+        SubCommand::Chord {
+            chord,
+            #[cfg(feature = "audio")]
+            play,
+            #[cfg(feature = "audio")]
+            playback,
+        } => {
+            let chord = parse_chord(chord);
+            match format {
+                Format::Text => {
+                    for ratio in &chord.ratios {
+                        let approximation = ratio.to_approximate_12_edo_interval();
+                        println!("{}\t{}\t{:?}", ratio, ratio.cents(), approximation);
+                    }
+                }
+                format => print_ratios(chord.ratios.clone(), format),
+            }
+            #[cfg(feature = "audio")]
+            if play {
+                chord.play(&player(playback));
+            }
+        }
+        #[cfg(feature = "audio")]
+        SubCommand::Wav {
+            ratio,
+            chord,
+            scale,
+            diamond,
+            edo,
+            output,
+            playback,
+        } => {
+            let config: PlaybackConfig = playback.into();
+            let result = if let Some(ratio) = ratio {
+                parse_ratio(&ratio).render_to_wav(&output, &config)
+            } else if !chord.is_empty() {
+                parse_chord(chord).render_to_wav(&output, &config)
+            } else if !scale.is_empty() {
+                Scale::from_ratios(&parse_ratios(scale)).render_to_wav(&output, &config)
+            } else if !diamond.is_empty() {
+                Diamond::<i32>::new(diamond).render_to_wav(&output, &config)
+            } else {
+                Scale::from_edo(Edo::new(edo.unwrap())).render_to_wav(&output, &config)
+            };
+            result.unwrap();
+            verbosity.info(format!("wrote {}", output));
+        }
+        SubCommand::Midi {
+            ratios,
+            scale,
+            lattice,
+            walk,
+            output,
+            chord,
+            bend_range,
+        } => {
+            let cents: Vec<f64> = if !scale.is_empty() {
+                let scale = Scale::from_ratios(&parse_ratios(scale));
+                (0..=scale.len() as i32)
+                    .map(|i| scale.cents_at(i))
+                    .collect()
+            } else if !lattice.is_empty() {
+                let lattice = Lattice::new(lattice.iter().map(|r| parse_dimension(r)).collect());
+                parse_indices(walk)
+                    .iter()
+                    .map(|i| lattice.at(i).cents())
+                    .collect()
+            } else {
+                parse_ratios(ratios).iter().map(|r| r.cents()).collect()
+            };
+            let mut export = MidiExport::default();
+            export.bend_range = bend_range;
+            if chord {
+                export.add_cents(&cents);
+            } else {
+                for c in cents {
+                    export.add_cents(&[c]);
+                }
+            }
+            export
+                .write(&output)
+                .unwrap_or_else(|err| exit_with_error(format!("{}: {}", output, err)));
+            verbosity.info(format!("wrote {}", output));
         }
-        SubCommand::Diamond { limits } => println!("{}", Diamond::<i32>::new(limits)),
-        SubCommand::Lattice { ratios, indices } => {
+        #[cfg(feature = "mpe")]
+        SubCommand::Mpe { list: true, .. } => {
+            let ports = MpeOutput::ports().unwrap_or_else(|err| exit_with_error(err));
+            match format {
+                Format::Text => ports.iter().for_each(|port| println!("{}", port)),
+                format => {
+                    let records: Vec<Record> = ports
+                        .into_iter()
+                        .map(|port| Record(vec![("port", Value::Text(port))]))
+                        .collect();
+                    print_records(&records, format);
+                }
+            }
+        }
+        #[cfg(feature = "mpe")]
+        SubCommand::Mpe {
+            ratios,
+            chord,
+            port,
+            bend_range,
+            duration,
+            ..
+        } => {
             let ratios = parse_ratios(ratios);
-            let indices = parse_indices(indices);
-
-            let lattice_dimensions: Vec<LatticeDimension<i32>> = ratios
-                .iter()
-                .map(|r| LatticeDimension::new(*r, Infinite))
-                .collect();
+            let mut output = MpeOutput::connect(port.as_deref(), bend_range)
+                .unwrap_or_else(|err| exit_with_error(err));
+            output.duration = duration;
+            if chord {
+                output.play_chord(&ratios);
+            } else {
+                output.play_sequence(&ratios);
+            }
+        }
+        SubCommand::Scale {
+            source,
+            export,
+            matrix,
+            compare_edo,
+            output,
+            #[cfg(feature = "audio")]
+            play,
+            #[cfg(feature = "audio")]
+            playback,
+        } => {
+            let scale = source.build();
+            if matrix {
+                print_interval_matrix(&scale, format);
+            } else if let Some(divisions) = compare_edo {
+                print_scale_comparison(&scale, Edo::new(divisions), format);
+            } else if let Some(export) = export {
+                let text = match export {
+                    ScaleExport::Scl => scale.to_scl(),
+                    ScaleExport::Lilypond => {
+                        lilypond::document(&lilypond::scale(&scale, Note::MIDDLE_C))
+                    }
+                    ScaleExport::Musicxml => musicxml::scale(&scale, Note::MIDDLE_C),
+                    ScaleExport::Json => json::scale(&scale, Edo::new(12)) + "\n",
+                };
+                match output {
+                    Some(path) => {
+                        std::fs::write(&path, text).unwrap();
+                        verbosity.info(format!("wrote {}", path));
+                    }
+                    None => print!("{}", text),
+                }
+            } else {
+                print_scale(&scale, format);
+            }
+            #[cfg(feature = "audio")]
+            if play {
+                scale.play(&player(playback));
+            }
+        }
+        SubCommand::Scl {
+            mode: SclMode::Export { source, output },
+        } => {
+            let scale = source.build();
+            match output {
+                Some(path) => {
+                    scale.write_scl(&path).unwrap();
+                    verbosity.info(format!("wrote {}", path));
+                }
+                None => print!("{}", scale.to_scl()),
+            }
+        }
+        SubCommand::Scl {
+            mode:
+                SclMode::Show {
+                    path,
+                    #[cfg(feature = "audio")]
+                    play,
+                    #[cfg(feature = "audio")]
+                    playback,
+                },
+        } => {
+            let scale = Scale::read_scl(&path).unwrap_or_else(|err| panic!("{}: {}", path, err));
+            if format == Format::Text {
+                println!("{}", scale.description);
+            }
+            print_scale(&scale, format);
+            #[cfg(feature = "audio")]
+            if play {
+                scale.play(&player(playback));
+            }
+        }
+        SubCommand::Diamond {
+            limits,
+            cents,
+            et,
+            html,
+            json,
+            max_width,
+            #[cfg(feature = "audio")]
+            play,
+            #[cfg(feature = "audio")]
+            playback,
+        } => {
+            let diamond = Diamond::<i32>::new(limits.clone());
+            match format {
+                Format::Text => {
+                    let cell = |ratio: &Ratio<i32>| {
+                        let mut lines = vec![ratio.to_string()];
+                        if cents {
+                            lines.push(format!("{:.1}", ratio.cents()));
+                        }
+                        if et {
+                            let (interval, error) = ratio.to_approximate_12_edo_interval();
+                            lines.push(format!("{} {:+.0}", interval.abbreviation(), error));
+                        }
+                        lines
+                    };
+                    match max_width.or_else(terminal_width) {
+                        Some(width) => println!("{}", diamond.display_within(width, cell)),
+                        None => println!("{}", diamond.display_with(cell)),
+                    }
+                }
+                format => {
+                    let records: Vec<Record> = diamond
+                        .ratios()
+                        .iter()
+                        .flatten()
+                        .zip(diamond.identities().into_iter().flatten())
+                        .map(|(ratio, identity)| {
+                            Record::ratio(*ratio)
+                                .prepend("identity", Value::Text(identity.to_string()))
+                                .prepend("denominator", Value::Integer(identity.utonal as i64))
+                                .prepend("numerator", Value::Integer(identity.otonal as i64))
+                        })
+                        .collect();
+                    print_records(&records, format);
+                }
+            }
+            if let Some(path) = html {
+                std::fs::write(&path, html::diamond(&diamond, Edo::new(12))).unwrap();
+                verbosity.info(format!("wrote {}", path));
+            }
+            if let Some(path) = json {
+                std::fs::write(&path, json::diamond(&diamond, Edo::new(12)) + "\n").unwrap();
+                verbosity.info(format!("wrote {}", path));
+            }
+            #[cfg(feature = "audio")]
+            if play {
+                diamond.play(&player(playback));
+            }
+        }
+        SubCommand::Lattice {
+            ratios,
+            file,
+            toml,
+            indices,
+            indices_file,
+            html,
+            json,
+            region,
+            stats,
+            diamond,
+            #[cfg(feature = "audio")]
+            play,
+            #[cfg(feature = "audio")]
+            playback,
+        } => {
+            let lattice = match file {
+                Some(path) => Lattice::read_toml(&path)
+                    .unwrap_or_else(|err| exit_with_error(format!("{}: {}", path, err))),
+                None => Lattice::new(ratios.iter().map(|r| parse_dimension(r)).collect()),
+            };
+            let indices = match indices_file {
+                Some(path) => read_indices(&path, lattice.dimensions.len())
+                    .unwrap_or_else(|err| exit_with_error(err)),
+                None => parse_indices(indices),
+            };
+            let region: Vec<RangeInclusive<i32>> = region.iter().map(|r| parse_range(r)).collect();
+            if let Some(path) = toml {
+                lattice.write_toml(&path).unwrap();
+                verbosity.info(format!("wrote {}", path));
+            }
+            if let Some(path) = html {
+                std::fs::write(&path, html::lattice(&lattice, &region, Edo::new(12))).unwrap();
+                verbosity.info(format!("wrote {}", path));
+            }
+            if let Some(path) = json {
+                let export = json::lattice(&lattice, &region, Edo::new(12)) + "\n";
+                std::fs::write(&path, export).unwrap();
+                verbosity.info(format!("wrote {}", path));
+            }
 
-            let lattice = Lattice::new(lattice_dimensions);
+            match format {
+                Format::Text => {
+                    for i in &indices {
+                        print_ratio(lattice.at(i));
+                    }
+                }
+                format => {
+                    let records: Vec<Record> = indices
+                        .iter()
+                        .map(|i| {
+                            Record::ratio(lattice.at(i))
+                                .prepend("indices", Value::Integers(i.clone()))
+                        })
+                        .collect();
+                    print_records(&records, format);
+                }
+            }
+            if let (true, Some(stats)) = (stats, lattice.region_stats(&region)) {
+                let smallest = stats.smallest_interval.map(|r| r.to_string());
+                match format {
+                    Format::Text => {
+                        println!("pitch classes\t{}", stats.pitch_classes);
+                        println!("span\t{}", stats.span);
+                        println!("min tenney height\t{}", stats.min_tenney_height);
+                        println!("max tenney height\t{}", stats.max_tenney_height);
+                        println!("smallest interval\t{}", smallest.unwrap_or_default());
+                    }
+                    format => {
+                        let record = Record(vec![
+                            ("pitch_classes", Value::Integer(stats.pitch_classes as i64)),
+                            ("span", Value::Number(stats.span)),
+                            ("min_tenney_height", Value::Number(stats.min_tenney_height)),
+                            ("max_tenney_height", Value::Number(stats.max_tenney_height)),
+                            (
+                                "smallest_interval",
+                                Value::Text(smallest.unwrap_or_default()),
+                            ),
+                        ]);
+                        print_records(&[record], format);
+                    }
+                }
+            }
+            if !diamond.is_empty() {
+                let diamond = Diamond::<i32>::new(diamond);
+                let placements = lattice.locate_diamond(&diamond, &region);
+                let contains_diamond = placements.iter().all(Placement::is_shared);
+                let contains_region = diamond.contains_region(&lattice, &region);
+                let columns = |placement: &Placement| {
+                    let identities: Vec<String> = placement
+                        .identities
+                        .iter()
+                        .map(Identity::to_string)
+                        .collect();
+                    let indices: Vec<String> = placement
+                        .indices
+                        .iter()
+                        .map(|i| i.iter().map(i32::to_string).collect::<Vec<_>>().join(","))
+                        .collect();
+                    (identities.join(", "), indices.join(" "))
+                };
+                match format {
+                    Format::Text => {
+                        for placement in &placements {
+                            let (identities, indices) = columns(placement);
+                            println!("{}\t{}\t{}", placement.ratio, identities, indices);
+                        }
+                        println!("region contains diamond\t{}", contains_diamond);
+                        println!("diamond contains region\t{}", contains_region);
+                    }
+                    format => {
+                        let records: Vec<Record> = placements
+                            .iter()
+                            .map(|placement| {
+                                let (identities, indices) = columns(placement);
+                                Record(vec![
+                                    ("ratio", Value::Text(placement.ratio.to_string())),
+                                    ("identities", Value::Text(identities)),
+                                    ("indices", Value::Text(indices)),
+                                    ("region_contains_diamond", Value::Bool(contains_diamond)),
+                                    ("diamond_contains_region", Value::Bool(contains_region)),
+                                ])
+                            })
+                            .collect();
+                        print_records(&records, format);
+                    }
+                }
+            }
+            #[cfg(feature = "audio")]
+            if play {
+                let player = player(playback);
+                lattice.play_walk(&indices, &player);
+                if !region.is_empty() {
+                    if !indices.is_empty() {
+                        player.rest();
+                    }
+                    lattice.play_region(&region, &player);
+                }
+            }
+        }
+        SubCommand::Pump {
+            comma,
+            ratios,
+            chord,
+            edo,
+            export,
+            output,
+            #[cfg(feature = "audio")]
+            play,
+            #[cfg(feature = "audio")]
+            playback,
+        } => {
+            let lattice = Lattice::new(
+                parse_ratios(ratios)
+                    .iter()
+                    .map(|r| LatticeDimension::new(*r, Infinite))
+                    .collect(),
+            );
+            let comma = parse_ratio(&comma);
+            let pump = CommaPump::new(&lattice, comma, &parse_chord(chord))
+                .unwrap_or_else(|| panic!("{} cannot be reached on the lattice", comma));
 
-            for i in indices {
-                print_ratio(lattice.at(&i));
+            let edo_drift = edo.map(|edo| (edo, pump.tempered_drift(&Edo::new(edo))));
+            match (export, format) {
+                (Some(export), _) => {
+                    let text = match export {
+                        PumpExport::Musicxml => musicxml::progression(&pump.chords, Note::MIDDLE_C),
+                    };
+                    match output {
+                        Some(path) => {
+                            std::fs::write(&path, text).unwrap();
+                            verbosity.info(format!("wrote {}", path));
+                        }
+                        None => print!("{}", text),
+                    }
+                }
+                (None, Format::Text) => {
+                    for (root, chord) in pump.roots.iter().zip(pump.chords.iter()) {
+                        println!("{}\t{}", root, chord);
+                    }
+                    println!("drift\t{}", pump.drift());
+                    if let Some((edo, drift)) = edo_drift {
+                        println!("{} EDO drift\t{}", edo, drift);
+                    }
+                }
+                (None, format) => {
+                    let records: Vec<Record> = pump
+                        .roots
+                        .iter()
+                        .zip(pump.chords.iter())
+                        .enumerate()
+                        .map(|(step, (root, chord))| {
+                            let mut fields = vec![
+                                ("step", Value::Integer(step as i64)),
+                                ("root", Value::Text(root.to_string())),
+                                ("chord", Value::Text(chord.to_string())),
+                                ("drift", Value::Number(pump.drift())),
+                            ];
+                            if let Some((edo, drift)) = edo_drift {
+                                fields.push(("edo", Value::Integer(edo as i64)));
+                                fields.push(("edo_drift", Value::Number(drift)));
+                            }
+                            Record(fields)
+                        })
+                        .collect();
+                    print_records(&records, format);
+                }
+            }
+            #[cfg(feature = "audio")]
+            if play {
+                pump.play(&player(playback));
+            }
+        }
+        SubCommand::Repl {
+            #[cfg(feature = "audio")]
+            playback,
+        } => {
+            #[cfg(feature = "audio")]
+            let mut repl = Repl::new(playback.into());
+            #[cfg(not(feature = "audio"))]
+            let mut repl = Repl::default();
+            repl.run(std::io::stdin().lock(), std::io::stdout())
+                .unwrap();
+        }
+        SubCommand::Completions { shell } => clap_complete::generate(
+            shell,
+            &mut Cli::command(),
+            "rust-intonation",
+            &mut std::io::stdout(),
+        ),
+        SubCommand::Ratios { ratios, root: None } => {
+            print_ratios(parse_ratios(args_or_stdin(ratios)), format)
+        }
+        SubCommand::Ratios {
+            ratios,
+            root: Some(root),
+        } => {
+            let root: Note = root.parse().unwrap();
+            let ratios = parse_ratios(args_or_stdin(ratios));
+            match format {
+                Format::Text => {
+                    for ratio in ratios {
+                        let approximation = ratio.to_approximate_12_edo_interval();
+                        println!("{}\t{:?}\t{}", ratio, approximation, root.name_above(ratio));
+                    }
+                }
+                format => {
+                    let records: Vec<Record> = ratios
+                        .into_iter()
+                        .map(|ratio| {
+                            let mut record = Record::ratio(ratio);
+                            record.0.push(("note", Value::Text(root.name_above(ratio))));
+                            record
+                        })
+                        .collect();
+                    print_records(&records, format);
+                }
+            }
+        }
+        SubCommand::Intervals { limit, name } => {
+            let intervals: Vec<(&str, Ratio<i32>)> = match name {
+                Some(name) => constants::find(&name).into_iter().copied().collect(),
+                None => constants::up_to_limit(limit).copied().collect(),
+            };
+            match format {
+                Format::Text => {
+                    for (name, ratio) in intervals {
+                        println!("{}\t{}\t{}", name, ratio, ratio.cents());
+                    }
+                }
+                format => {
+                    let records: Vec<Record> = intervals
+                        .into_iter()
+                        .map(|(_, ratio)| Record::ratio(ratio))
+                        .collect();
+                    print_records(&records, format);
+                }
             }
         }
-        SubCommand::Ratios { ratios } => {
-            for ratio in parse_ratios(ratios) {
-                print_ratio(ratio);
+        SubCommand::Approximate {
+            cents,
+            max_denominator,
+            tolerance,
+        } => {
+            let records: Vec<Record> = args_or_stdin(cents)
+                .iter()
+                .map(|c| c.parse::<f64>().unwrap())
+                .flat_map(|cents| {
+                    Ratio::approximations(cents, tolerance, max_denominator)
+                        .into_iter()
+                        .map(move |ratio| (cents, ratio))
+                })
+                .map(|(cents, ratio)| {
+                    let error = (ratio.cents() - cents + 600.).rem_euclid(1200.) - 600.;
+                    Record(vec![
+                        ("target", Value::Number(cents)),
+                        ("ratio", Value::Text(ratio.to_string())),
+                        ("cents", Value::Number(ratio.cents())),
+                        ("error", Value::Number(error)),
+                        ("tenney_height", Value::Number(ratio.tenney_height())),
+                    ])
+                })
+                .collect();
+            match format {
+                Format::Text => {
+                    for Record(fields) in records {
+                        let values: Vec<String> =
+                            fields.iter().map(|(_, v)| v.to_string()).collect();
+                        println!("{}", values.join("\t"));
+                    }
+                }
+                format => print_records(&records, format),
             }
         }
+        SubCommand::Series { limit } if format != Format::Text => {
+            let records: Vec<Record> = (1..=limit)
+                .rev()
+                .map(|i| {
+                    Record::ratio(Ratio::new(i, 1)).prepend("harmonic", Value::Integer(i as i64))
+                })
+                .collect();
+            print_records(&records, format);
+        }
         SubCommand::Series { limit } => {
             for i in (1..=limit).rev() {
                 let r = Ratio::new(i, 1);
@@ -155,23 +2237,172 @@ pub fn run() {
                 print_ratio(r);
             }
         }
-        SubCommand::Edo { edo } => {
+        SubCommand::Edo {
+            edo,
+            commas: Some(limit),
+            max_complexity,
+            ..
+        } => {
             let edo = Edo::new(edo);
-            for steps in 0..=edo.divisions {
-                let int = EdoInterval::new(&edo, steps);
-                println!(
-                    "{}/{}\t{:?}",
-                    steps,
-                    edo.divisions,
-                    int.to_approximate_12_edo_interval()
-                );
+            let commas = edo
+                .try_tempered_commas(limit, max_complexity)
+                .unwrap_or_else(|err| exit_with_error(format!("--max-complexity: {}", err)));
+            print_ratios(commas, format);
+        }
+        SubCommand::Edo {
+            edo,
+            mode: Some(EdoMode::Analyze { limit }),
+            ..
+        } => {
+            let edo = Edo::new(edo);
+            match format {
+                Format::Text => {
+                    println!("consistent\t{}", edo.consistency(limit));
+                    println!("max error\t{}", edo.max_error(limit));
+                }
+                format => {
+                    let record = Record(vec![
+                        ("consistent", Value::Bool(edo.consistency(limit))),
+                        ("max_error", Value::Number(edo.max_error(limit))),
+                    ]);
+                    print_records(&[record], format);
+                }
             }
         }
+        SubCommand::Edo {
+            edo,
+            mode:
+                Some(EdoMode::Compare {
+                    limit,
+                    ratios,
+                    json,
+                }),
+            ..
+        } => {
+            let edo = Edo::new(edo);
+            let table = if ratios.is_empty() {
+                edo.comparison_table(limit)
+            } else {
+                parse_ratios(ratios)
+                    .into_iter()
+                    .map(|ratio| (ratio, edo.approximate(ratio)))
+                    .collect()
+            };
+            if let Some(path) = json {
+                let ratios: Vec<Ratio<i32>> = table.iter().map(|(ratio, _)| *ratio).collect();
+                std::fs::write(&path, json::edo(edo, &ratios) + "\n").unwrap();
+                verbosity.info(format!("wrote {}", path));
+            }
+            match format {
+                Format::Text => {
+                    for (ratio, (steps, error)) in table {
+                        println!("{}\t{}/{}\t{}", ratio, steps, edo.divisions, error);
+                    }
+                }
+                format => {
+                    let records: Vec<Record> = table
+                        .into_iter()
+                        .map(|(ratio, (steps, error))| {
+                            Record(vec![
+                                ("ratio", Value::Text(ratio.to_string())),
+                                ("cents", Value::Number(ratio.cents())),
+                                ("steps", Value::Integer(steps as i64)),
+                                ("error", Value::Number(error)),
+                            ])
+                        })
+                        .collect();
+                    print_records(&records, format);
+                }
+            }
+        }
+        #[cfg(feature = "audio")]
+        SubCommand::Edo {
+            edo,
+            mode: Some(EdoMode::Play { playback }),
+            ..
+        } => {
+            let edo = Edo::new(edo);
+            edo.play_scale(&player(playback));
+        }
+        SubCommand::Edo {
+            edo, mode: None, ..
+        } => {
+            let edo = Edo::new(edo);
+            let intervals = (0..=edo.divisions).map(|steps| EdoInterval::new(edo, steps as i32));
+            match format {
+                Format::Text => {
+                    for int in intervals {
+                        println!("{}\t{}", int.compare_to_12_edo(), int.name());
+                    }
+                }
+                format => {
+                    let records: Vec<Record> = intervals
+                        .map(|int| {
+                            let comparison = int.compare_to_12_edo();
+                            Record(vec![
+                                ("steps", Value::Integer(comparison.steps as i64)),
+                                ("cents", Value::Number(comparison.cents)),
+                                ("name", Value::Text(int.name())),
+                                ("interval", Value::Text(format!("{:?}", comparison.nearest))),
+                                ("error", Value::Number(comparison.delta)),
+                            ])
+                        })
+                        .collect();
+                    print_records(&records, format);
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether the argument was given on the command line, to the innermost
+/// subcommand or before it.
+fn given_on_command_line(matches: &ArgMatches, id: &str) -> bool {
+    let mut matches = matches;
+    while let Some((_, subcommand)) = matches.subcommand() {
+        matches = subcommand;
+    }
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+/// Prints the error the way clap reports invalid arguments, and exits with its status.
+fn exit_with_error(message: impl std::fmt::Display) -> ! {
+    Cli::command().error(ErrorKind::Io, message).exit()
+}
+
+/// Returns the given arguments or, when they are just `-`, or there are none and input
+/// is piped in, the whitespace-separated words of stdin.
+fn args_or_stdin(args: Vec<String>) -> Vec<String> {
+    let stdin = std::io::stdin();
+    match args.as_slice() {
+        [dash] if dash == "-" => read_words(stdin.lock()),
+        [] if !stdin.is_terminal() => read_words(stdin.lock()),
+        _ => args,
     }
 }
 
+fn read_words<R: Read>(mut input: R) -> Vec<String> {
+    let mut text = String::new();
+    input.read_to_string(&mut text).unwrap();
+    text.split_whitespace().map(String::from).collect()
+}
+
 fn print_ratio(ratio: Ratio<i32>) {
-    println!("{}\t{:?}", ratio, ratio.to_approximate_12_edo_interval());
+    let approximation = ratio.to_approximate_12_edo_interval();
+    match name_of(ratio) {
+        Some(name) => println!("{}\t{:?}\t{}", ratio, approximation, name),
+        None => println!("{}\t{:?}", ratio, approximation),
+    }
+}
+
+fn print_ratios(ratios: Vec<Ratio<i32>>, format: Format) {
+    match format {
+        Format::Text => ratios.into_iter().for_each(print_ratio),
+        format => {
+            let records: Vec<Record> = ratios.into_iter().map(Record::ratio).collect();
+            print_records(&records, format);
+        }
+    }
 }
 
 fn parse_indices(indices: Vec<String>) -> Vec<Vec<i32>> {
@@ -182,6 +2413,328 @@ fn parse_index(s: &str) -> Vec<i32> {
     s.split(',').map(|n| n.parse().unwrap()).collect()
 }
 
+/// Reads lattice indices from a file, as [parse_indices_lines] does, returning an error
+/// prefixed with the path, and the line at fault if the file could be read.
+fn read_indices(path: &str, dimensions: usize) -> Result<Vec<Vec<i32>>, String> {
+    let text = std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    parse_indices_lines(&text, dimensions).map_err(|err| format!("{}:{}", path, err))
+}
+
+/// Parses lattice indices, one comma-separated set on each line, with an index for each
+/// of the given number of dimensions, skipping blank lines and anything after a `#`.
+/// Errors start with the number of the line at fault.
+fn parse_indices_lines(text: &str, dimensions: usize) -> Result<Vec<Vec<i32>>, String> {
+    let mut indices = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let index: Vec<i32> = line
+            .split(',')
+            .map(|n| n.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("{}: invalid indices `{}`", i + 1, line))?;
+        if index.len() != dimensions {
+            return Err(format!(
+                "{}: expected {} indices, one for each dimension, but found {} in `{}`",
+                i + 1,
+                dimensions,
+                index.len(),
+                line
+            ));
+        }
+        indices.push(index);
+    }
+    Ok(indices)
+}
+
+fn print_scale(scale: &Scale, format: Format) {
+    let rows = scale.degrees.iter().zip(scale.steps());
+    match format {
+        Format::Text => {
+            for (degree, step) in rows {
+                println!("{}\t{}\t{}", degree, degree.cents(), step);
+            }
+        }
+        format => {
+            let records: Vec<Record> = rows
+                .map(|(degree, step)| {
+                    Record(vec![
+                        ("degree", Value::Text(degree.to_string())),
+                        ("cents", Value::Number(degree.cents())),
+                        ("step", Value::Number(step)),
+                    ])
+                })
+                .collect();
+            print_records(&records, format);
+        }
+    }
+}
+
+/// Prints a row for each degree of the scale, with the interval from it up to each of
+/// the following degrees, ending with the period. As text, the columns are aligned.
+fn print_interval_matrix(scale: &Scale, format: Format) {
+    let rows: Vec<Vec<(String, f64)>> = scale
+        .ratio_matrix()
+        .iter()
+        .zip(scale.interval_matrix())
+        .map(|(degrees, cents)| {
+            let mut intervals: Vec<String> = degrees.iter().map(Degree::to_string).collect();
+            intervals.push(scale.period.to_string());
+            intervals.into_iter().zip(cents).collect()
+        })
+        .collect();
+    match format {
+        Format::Text => {
+            let width = rows
+                .iter()
+                .flatten()
+                .map(|(interval, _)| interval.len())
+                .max()
+                .unwrap_or(0);
+            for row in rows {
+                let cells: Vec<String> = row
+                    .iter()
+                    .map(|(interval, _)| format!("{:<width$}", interval))
+                    .collect();
+                println!("{}", cells.join("  ").trim_end());
+            }
+        }
+        format => {
+            let records: Vec<Record> = rows
+                .iter()
+                .enumerate()
+                .flat_map(|(from, row)| {
+                    row.iter()
+                        .enumerate()
+                        .map(move |(steps, (interval, cents))| {
+                            Record(vec![
+                                ("from", Value::Integer(from as i64)),
+                                ("steps", Value::Integer(steps as i64)),
+                                ("interval", Value::Text(interval.clone())),
+                                ("cents", Value::Number(*cents)),
+                            ])
+                        })
+                })
+                .collect();
+            print_records(&records, format);
+        }
+    }
+}
+
+/// Prints each degree of the scale with the nearest step of the EDO and the difference
+/// between them in cents, followed, as text, by summary statistics of the differences.
+fn print_scale_comparison(scale: &Scale, edo: Edo, format: Format) {
+    let comparison = scale.compare(&Scale::<i32>::from_edo(edo));
+    match format {
+        Format::Text => {
+            for d in &comparison.degrees {
+                println!(
+                    "{}\t{}\\{}\t{:+.3}",
+                    scale.degrees[d.degree], d.nearest, edo.divisions, d.difference
+                );
+            }
+            println!("max\t{:.3}", comparison.max_difference());
+            println!("mean\t{:.3}", comparison.mean_difference());
+            println!("rms\t{:.3}", comparison.rms_difference());
+        }
+        format => {
+            let records: Vec<Record> = comparison
+                .degrees
+                .iter()
+                .map(|d| {
+                    Record(vec![
+                        ("degree", Value::Text(scale.degrees[d.degree].to_string())),
+                        ("step", Value::Integer(d.nearest as i64)),
+                        ("difference", Value::Number(d.difference)),
+                    ])
+                })
+                .collect();
+            print_records(&records, format);
+        }
+    }
+}
+
+/// Returns the width of the terminal standard output is written to, from `$COLUMNS`, or
+/// 80 columns if it is not set, or `None` if standard output is not a terminal.
+fn terminal_width() -> Option<usize> {
+    std::io::stdout().is_terminal().then(|| {
+        std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .unwrap_or(80)
+    })
+}
+
+/// Parses an inclusive range of indices, `a..b`.
+fn parse_range(s: &str) -> RangeInclusive<i32> {
+    let (a, b) = s.split_once("..").unwrap();
+    a.parse().unwrap()..=b.parse().unwrap()
+}
+
+/// Parses a lattice dimension as a ratio, optionally followed by an `@` and the offset
+/// of its origin, and then by a colon and its bounds: `inf`, a length `n`, or an
+/// inclusive range `a..b`.
+fn parse_dimension(s: &str) -> LatticeDimension<i32> {
+    let (ratio, bounds) = s.split_once(':').unwrap_or((s, "inf"));
+    let (ratio, offset) = ratio.split_once('@').unwrap_or((ratio, "0"));
+    let bounds = match (bounds, bounds.split_once("..")) {
+        ("inf", _) => Infinite,
+        (_, Some(_)) => {
+            let range = parse_range(bounds);
+            RangeBounded(*range.start(), *range.end())
+        }
+        (n, None) => LengthBounded(n.parse().unwrap()),
+    };
+    LatticeDimension::new(parse_ratio(ratio), bounds).with_offset(offset.parse().unwrap())
+}
+
+fn parse_chord(chord: Vec<String>) -> Chord {
+    match chord.as_slice() {
+        [harmonics] if harmonics.contains(':') => {
+            let harmonics: Vec<i32> = harmonics.split(':').map(|h| h.parse().unwrap()).collect();
+            Chord::from_harmonics(&harmonics)
+        }
+        _ => Chord::new(parse_ratios(chord)),
+    }
+}
+
+fn parse_interval(s: &str) -> IntervalArg {
+    if s.contains('/') {
+        IntervalArg::Ratio(parse_ratio(s))
+    } else if let Some((edo, steps)) = s.split_once("edo:") {
+        IntervalArg::Edo {
+            edo: Edo::new(edo.parse().unwrap()),
+            steps: steps.parse().unwrap(),
+        }
+    } else {
+        IntervalArg::Cents(s.trim_end_matches('c').parse().unwrap())
+    }
+}
+
+/// Checks that a value is a ratio `n/d` of positive integers.
+fn ratio_arg(s: &str) -> Result<String, String> {
+    let valid = s
+        .split_once('/')
+        .is_some_and(|(n, d)| is_positive(n) && is_positive(d));
+    validated(s, valid, "a ratio of positive integers, e.g. 3/2")
+}
+
+/// Checks that a value is the name of an interval, as looked up by [constants::lookup].
+fn interval_name_arg(s: &str) -> Result<String, String> {
+    validated(
+        s,
+        constants::lookup(s).is_some(),
+        "the name of an interval, e.g. \"perfect fifth\"",
+    )
+}
+
+/// Checks that a value is a note name, e.g. `C#4`, as parsed by [Note].
+fn note_arg(s: &str) -> Result<String, String> {
+    validated(s, s.parse::<Note>().is_ok(), "a note name, e.g. C4 or Bb3")
+}
+
+/// Checks that a value is a lattice dimension, as parsed by [parse_dimension].
+fn dimension_arg(s: &str) -> Result<String, String> {
+    let (ratio, bounds) = s.split_once(':').unwrap_or((s, "inf"));
+    let (ratio, offset) = ratio.split_once('@').unwrap_or((ratio, "0"));
+    let valid = ratio_arg(ratio).is_ok()
+        && is_integer(offset)
+        && (bounds == "inf" || is_integer(bounds) || range_arg(bounds).is_ok());
+    validated(
+        s,
+        valid,
+        "a ratio with an optional offset and bounds, e.g. 3/2, 3/2:inf, 5/4:3, 5/4:-2..2 or 3/2@-2:-3..3",
+    )
+}
+
+/// Checks that a value is an inclusive range of integers, as parsed by [parse_range].
+fn range_arg(s: &str) -> Result<String, String> {
+    let valid = s
+        .split_once("..")
+        .is_some_and(|(a, b)| is_integer(a) && is_integer(b));
+    validated(s, valid, "an inclusive range of integers, e.g. -1..2")
+}
+
+/// Checks that a value is a lattice index, as parsed by [parse_index].
+fn index_arg(s: &str) -> Result<String, String> {
+    validated(
+        s,
+        s.split(',').all(is_integer),
+        "comma-separated integers, e.g. 1,0,-1",
+    )
+}
+
+/// Checks that a value is a member of a chord, as parsed by [parse_chord].
+fn chord_arg(s: &str) -> Result<String, String> {
+    let harmonics = s.contains(':') && s.split(':').all(is_positive);
+    validated(
+        s,
+        harmonics || ratio_arg(s).is_ok(),
+        "colon-separated harmonics, e.g. 4:5:6, or a ratio, e.g. 5/4",
+    )
+}
+
+/// Checks that a value is an interval, as parsed by [parse_interval].
+fn interval_arg(s: &str) -> Result<String, String> {
+    let edo_step = s
+        .split_once("edo:")
+        .is_some_and(|(edo, steps)| is_positive(edo) && is_integer(steps));
+    let cents = s
+        .trim_end_matches('c')
+        .parse::<f64>()
+        .is_ok_and(f64::is_finite);
+    validated(
+        s,
+        ratio_arg(s).is_ok() || edo_step || cents,
+        "a ratio, EDO step or size in cents, e.g. 5/4, 31edo:10 or 386c",
+    )
+}
+
+/// Checks that a value is a finite number no less than zero.
+#[cfg(feature = "audio")]
+fn non_negative_arg(s: &str) -> Result<f32, String> {
+    s.parse()
+        .ok()
+        .filter(|n: &f32| n.is_finite() && *n >= 0.)
+        .ok_or_else(|| "expected a number no less than 0".to_string())
+}
+
+/// Checks that a value is a finite number greater than zero.
+#[cfg(any(feature = "audio", feature = "mpe"))]
+fn positive_arg(s: &str) -> Result<f32, String> {
+    s.parse()
+        .ok()
+        .filter(|n: &f32| n.is_finite() && *n > 0.)
+        .ok_or_else(|| "expected a number greater than 0".to_string())
+}
+
+/// Checks that a value is a number from 0 to 1, inclusive.
+#[cfg(feature = "audio")]
+fn unit_arg(s: &str) -> Result<f32, String> {
+    s.parse()
+        .ok()
+        .filter(|n: &f32| (0. ..=1.).contains(n))
+        .ok_or_else(|| "expected a number from 0 to 1, e.g. 0.2".to_string())
+}
+
+fn validated(s: &str, valid: bool, expected: &str) -> Result<String, String> {
+    if valid {
+        Ok(s.to_string())
+    } else {
+        Err(format!("expected {}", expected))
+    }
+}
+
+fn is_positive(s: &str) -> bool {
+    s.parse::<i32>().is_ok_and(|n| n > 0)
+}
+
+fn is_integer(s: &str) -> bool {
+    s.parse::<i32>().is_ok()
+}
+
 fn parse_ratios(ratios: Vec<String>) -> Vec<Ratio<i32>> {
     ratios.iter().map(|r| parse_ratio(r)).collect()
 }
@@ -192,3 +2745,29 @@ fn parse_ratio(s: &str) -> Ratio<i32> {
     let denom: i32 = parts[1].parse().unwrap();
     Ratio::new(numer, denom)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn indices_lines() {
+        let text = "# a walk\n0,0\n\n1, -2 # up a fifth\n   \n-1,3\n";
+
+        assert_eq!(
+            parse_indices_lines(text, 2),
+            Ok(vec![vec![0, 0], vec![1, -2], vec![-1, 3]])
+        );
+        assert_eq!(
+            parse_indices_lines("0,0\n1,x\n", 2),
+            Err("2: invalid indices `1,x`".to_string())
+        );
+        assert_eq!(
+            parse_indices_lines("# 3 dimensions\n0,0,1\n", 2),
+            Err(
+                "2: expected 2 indices, one for each dimension, but found 3 in `0,0,1`".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Defaults for the CLI's options, read from an optional configuration file at
+//! `~/.config/rust-intonation/config.toml`, or under `$XDG_CONFIG_HOME` when it is set.
+//!
+//! The file is read with the [toml](https://docs.rs/toml) crate, and only the keys
+//! below can be given.
+//!
+//! ```toml
+//! format = "json"
+//!
+//! [playback]
+//! root = 415
+//! duration = 3
+//!
+//! [lattice]
+//! ratios = ["3/2", "5/4", "7/4"]
+//! ```
+use super::output::Format;
+use clap::ValueEnum;
+use std::{error::Error, fmt::Display, path::PathBuf};
+use toml::{Table, Value};
+
+/// Describes why the configuration file could not be read. Keys in a section are given
+/// as `section.key`.
+#[derive(Debug)]
+pub(super) enum ConfigError {
+    /// The file exists but could not be read.
+    Io(std::io::Error),
+    /// The file is not valid TOML.
+    Parse(toml::de::Error),
+    /// A key is not one of the options that can be configured.
+    UnknownKey { key: String },
+    /// A value is not of the type its key expects.
+    InvalidValue { key: String },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Parse(err) => write!(f, "{}", err),
+            Self::UnknownKey { key } => write!(f, "unknown key `{}`", key),
+            Self::InvalidValue { key } => write!(f, "invalid value for `{}`", key),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The defaults read from the configuration file, each left as `None` when not given.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct Config {
+    pub format: Option<Format>,
+    pub root: Option<f32>,
+    pub duration: Option<f32>,
+    pub volume: Option<f32>,
+    pub gap: Option<f32>,
+    pub lattice_ratios: Option<Vec<String>>,
+}
+
+impl Config {
+    /// Reads the configuration file, or returns an empty [Config] if there is none.
+    pub fn load() -> Result<Self, ConfigError> {
+        let Some(path) = path() else {
+            return Ok(Self::default());
+        };
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(ConfigError::Io(err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let table: Table = text.parse().map_err(ConfigError::Parse)?;
+        let mut config = Self::default();
+
+        for (key, value) in keys(&table, "") {
+            let invalid = || ConfigError::InvalidValue { key: key.clone() };
+            let number = |valid: fn(f32) -> bool| {
+                number(value)
+                    .filter(|n| n.is_finite() && valid(*n))
+                    .ok_or_else(invalid)
+            };
+            match key.as_str() {
+                "format" => {
+                    let format = value.as_str().ok_or_else(invalid)?;
+                    config.format = Some(Format::from_str(format, true).map_err(|_| invalid())?);
+                }
+                "playback.root" => config.root = Some(number(|n| n > 0.)?),
+                "playback.duration" => config.duration = Some(number(|n| n > 0.)?),
+                "playback.volume" => config.volume = Some(number(|n| (0. ..=1.).contains(&n))?),
+                "playback.gap" => config.gap = Some(number(|n| n >= 0.)?),
+                "lattice.ratios" => {
+                    config.lattice_ratios = Some(strings(value).ok_or_else(invalid)?)
+                }
+                _ => return Err(ConfigError::UnknownKey { key }),
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_home.join("rust-intonation").join("config.toml"))
+}
+
+/// Returns every value in the table that is not itself a table, with its key prefixed
+/// by those of the sections holding it, e.g. `playback.root`.
+fn keys<'a>(table: &'a Table, prefix: &str) -> Vec<(String, &'a Value)> {
+    table
+        .iter()
+        .flat_map(|(key, value)| {
+            let key = format!("{}{}", prefix, key);
+            match value {
+                Value::Table(section) => keys(section, &format!("{}.", key)),
+                value => vec![(key, value)],
+            }
+        })
+        .collect()
+}
+
+/// Returns an integer or floating point value as a number.
+fn number(value: &Value) -> Option<f32> {
+    match value {
+        Value::Integer(n) => Some(*n as f32),
+        Value::Float(n) => Some(*n as f32),
+        _ => None,
+    }
+}
+
+fn strings(value: &Value) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|item| item.as_str().map(String::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse() {
+        let text = "# defaults\nformat = \"json\"\n\n[playback]\nroot = 415 # baroque\nduration = 3\n\n[lattice]\nratios = [\"3/2\", \"7/4\",]\n";
+
+        assert_eq!(
+            Config::parse(text).unwrap(),
+            Config {
+                format: Some(Format::Json),
+                root: Some(415.),
+                duration: Some(3.),
+                lattice_ratios: Some(vec!["3/2".to_string(), "7/4".to_string()]),
+                ..Config::default()
+            }
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            Config::parse("format json"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert_eq!(
+            Config::parse("root = 415").unwrap_err().to_string(),
+            "unknown key `root`"
+        );
+        assert_eq!(
+            Config::parse("[playback]\n\nroot = \"A\"")
+                .unwrap_err()
+                .to_string(),
+            "invalid value for `playback.root`"
+        );
+        assert_eq!(
+            Config::parse("[playback]\ngap = -1")
+                .unwrap_err()
+                .to_string(),
+            "invalid value for `playback.gap`"
+        );
+        assert_eq!(
+            Config::parse("[lattice]\nratios = [\"3/2\", 5]")
+                .unwrap_err()
+                .to_string(),
+            "invalid value for `lattice.ratios`"
+        );
+        assert!(matches!(
+            Config::parse("format = \"xml\""),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+    }
+}
@@ -0,0 +1,159 @@
+//! An interactive prompt for nudging a tone up and down against a sustained reference
+//! tone, showing the ratio the interval between them approximates after each nudge.
+use super::repl::parse_ratio;
+use crate::interval::name_of;
+use crate::play::PitchComparison;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
++ or -          raise or lower the tone by a step, once for each sign, e.g. `+++`
++<c> or -<c>    raise or lower the tone by <c> cents
+<c>             retune the tone to <c> cents above the reference
+<n/d>           retune the tone to the ratio above the reference
+help            show this message
+quit            stop the tones";
+
+/// The tones being compared, and how the interval between them is nudged and shown.
+pub(super) struct Nudge {
+    comparison: PitchComparison,
+    step: f64,
+    tolerance: f64,
+    max_denominator: i32,
+}
+
+impl Nudge {
+    pub fn new(
+        comparison: PitchComparison,
+        step: f64,
+        tolerance: f64,
+        max_denominator: i32,
+    ) -> Self {
+        Self {
+            comparison,
+            step,
+            tolerance,
+            max_denominator,
+        }
+    }
+
+    /// Shows the starting interval, then reads lines from the input until it ends or
+    /// `quit` is entered, writing the interval after each nudge to the output.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> std::io::Result<()> {
+        write!(output, "{}\n> ", self.describe())?;
+        output.flush()?;
+        for line in input.lines() {
+            let line = line?;
+            match line.trim() {
+                "quit" | "exit" | "q" => break,
+                "" => {}
+                line => match self.eval(line) {
+                    Ok(result) => writeln!(output, "{}", result)?,
+                    Err(message) => writeln!(output, "error: {}", message)?,
+                },
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Runs a single line entered at the prompt, returning the interval between the
+    /// tones after it, or a message describing why it could not be run.
+    pub fn eval(&mut self, line: &str) -> Result<String, String> {
+        let invalid = || format!("unknown command `{}`, try `help`", line);
+        let signs = |sign: char| line.chars().all(|c| c == sign).then_some(line.len() as f64);
+        if line == "help" {
+            return Ok(HELP.to_string());
+        } else if let Some(steps) = signs('+') {
+            self.comparison.nudge(steps * self.step);
+        } else if let Some(steps) = signs('-') {
+            self.comparison.nudge(-steps * self.step);
+        } else if let Some(cents) = line.strip_prefix('+') {
+            self.comparison.nudge(cents.parse().map_err(|_| invalid())?);
+        } else if let Some(cents) = line.strip_prefix('-') {
+            let cents: f64 = cents.parse().map_err(|_| invalid())?;
+            self.comparison.nudge(-cents);
+        } else if line.contains('/') {
+            self.comparison.set_cents(parse_ratio(line)?.cents());
+        } else {
+            self.comparison
+                .set_cents(line.parse().map_err(|_| invalid())?);
+        }
+        Ok(self.describe())
+    }
+
+    /// Returns the cents between the tones, with the ratio they approximate, if any,
+    /// and its name, if it has one.
+    fn describe(&self) -> String {
+        let cents = format!("{:.2}", self.comparison.cents());
+        match self
+            .comparison
+            .approximation(self.tolerance, self.max_denominator)
+        {
+            Some(ratio) => match name_of(ratio) {
+                Some(name) => format!("{}\t{}\t{}", cents, ratio, name),
+                None => format!("{}\t{}", cents, ratio),
+            },
+            None => cents,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn nudge() -> Nudge {
+        Nudge::new(PitchComparison::new(0.), 0.5, 1., 128)
+    }
+
+    #[test]
+    fn nudging() {
+        let mut nudge = nudge();
+
+        assert_eq!(nudge.eval("+"), Ok("0.50\t1/1\tunison".to_string()));
+        assert_eq!(
+            nudge.eval("+21"),
+            Ok("21.50\t81/80\tsyntonic comma".to_string())
+        );
+        assert_eq!(
+            nudge.eval("---"),
+            Ok("20.00\t2048/2025\tdiaschisma".to_string())
+        );
+        assert_eq!(
+            nudge.eval("-5.5"),
+            Ok("14.50\t126/125\tseptimal semicomma".to_string())
+        );
+        assert_eq!(nudge.eval("750"), Ok("750.00\t37/24".to_string()));
+        assert_eq!(
+            nudge.eval("7/4"),
+            Ok("968.83\t7/4\tharmonic seventh".to_string())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut nudge = nudge();
+
+        assert_eq!(
+            nudge.eval("+x"),
+            Err("unknown command `+x`, try `help`".to_string())
+        );
+        assert_eq!(nudge.eval("3/0"), Err("invalid ratio `3/0`".to_string()));
+        assert_eq!(nudge.comparison.cents(), 0.);
+    }
+
+    #[test]
+    fn run_until_quit() {
+        let mut output = vec![];
+        Nudge::new(PitchComparison::new(702.), 1., 1., 128)
+            .run("-\n\nq\n+\n".as_bytes(), &mut output)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "702.00\t3/2\tperfect fifth\n> 701.00\t3/2\tperfect fifth\n> > "
+        );
+    }
+}
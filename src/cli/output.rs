@@ -0,0 +1,229 @@
+//! Structured output of the CLI's results, for piping into other tools.
+use crate::{interval::name_of, json::string as json_string, ratio::Ratio};
+use clap::ValueEnum;
+use std::fmt::Display;
+
+/// The format the results of a subcommand are printed in.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+pub(super) enum Format {
+    /// Tab-separated, for reading at the terminal
+    #[default]
+    Text,
+    /// A JSON array of objects, or a single object for a single result
+    Json,
+    /// Comma-separated values, with a header row
+    Csv,
+    /// Tab-separated values, with a header row
+    Tsv,
+}
+
+/// How much the CLI prints besides the results of a subcommand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Verbosity {
+    /// Only the results.
+    Quiet,
+    /// The results, and messages such as the paths of files written.
+    Normal,
+    /// Everything, including a description of each sound as it is played.
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn new(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Self::Quiet
+        } else if verbose {
+            Self::Verbose
+        } else {
+            Self::Normal
+        }
+    }
+
+    /// Prints a message to stderr, so that it is kept apart from the results, unless
+    /// quiet.
+    pub fn info(self, message: impl Display) {
+        if self != Self::Quiet {
+            eprintln!("{}", message);
+        }
+    }
+}
+
+/// A single field of a [Record].
+pub(super) enum Value {
+    Text(String),
+    Number(f64),
+    Integer(i64),
+    Bool(bool),
+    Integers(Vec<i32>),
+}
+
+/// One result of a subcommand, as named fields in a fixed order.
+pub(super) struct Record(pub Vec<(&'static str, Value)>);
+
+impl Record {
+    /// A record of a JI ratio, its size in cents, the nearest 12 EDO interval with the
+    /// ratio's error from it, and the name of the ratio, if it has one.
+    pub fn ratio(ratio: Ratio<i32>) -> Self {
+        let (interval, error) = ratio.to_approximate_12_edo_interval();
+        Self(vec![
+            ("ratio", Value::Text(ratio.to_string())),
+            ("cents", Value::Number(ratio.cents())),
+            ("interval", Value::Text(format!("{:?}", interval))),
+            ("error", Value::Number(error)),
+            (
+                "name",
+                Value::Text(name_of(ratio).unwrap_or_default().to_string()),
+            ),
+        ])
+    }
+
+    /// Returns the record with the given field added before the others.
+    pub fn prepend(mut self, name: &'static str, value: Value) -> Self {
+        self.0.insert(0, (name, value));
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        let fields: Vec<String> = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), value.to_json()))
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+impl Value {
+    fn to_json(&self) -> String {
+        match self {
+            Self::Text(text) => json_string(text),
+            Self::Number(n) if n.is_finite() => n.to_string(),
+            Self::Number(_) => "null".to_string(),
+            Self::Integer(n) => n.to_string(),
+            Self::Bool(b) => b.to_string(),
+            Self::Integers(ns) => {
+                let ns: Vec<String> = ns.iter().map(|n| n.to_string()).collect();
+                format!("[{}]", ns.join(","))
+            }
+        }
+    }
+}
+
+/// Prints the records in the given format. A single record is printed as a JSON object
+/// rather than an array.
+pub(super) fn print_records(records: &[Record], format: Format) {
+    match (format, records) {
+        (Format::Json, [record]) => println!("{}", record.to_json()),
+        (Format::Json, _) => println!("{}", json_array(records)),
+        (Format::Csv, _) => print!("{}", delimited(records, ',')),
+        (Format::Tsv | Format::Text, _) => print!("{}", delimited(records, '\t')),
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "{}", text),
+            Self::Number(n) => write!(f, "{}", n),
+            Self::Integer(n) => write!(f, "{}", n),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Integers(ns) => {
+                let ns: Vec<String> = ns.iter().map(|n| n.to_string()).collect();
+                write!(f, "{}", ns.join(","))
+            }
+        }
+    }
+}
+
+/// Returns the records as a JSON array.
+fn json_array(records: &[Record]) -> String {
+    let records: Vec<String> = records.iter().map(|r| r.to_json()).collect();
+    format!("[{}]", records.join(","))
+}
+
+/// Returns the records as rows of values separated by the delimiter, under a header row
+/// of the field names of the first record.
+fn delimited(records: &[Record], delimiter: char) -> String {
+    let Some(first) = records.first() else {
+        return String::new();
+    };
+    let header: Vec<String> = first
+        .0
+        .iter()
+        .map(|(name, _)| delimited_field(name, delimiter))
+        .collect();
+
+    let mut output = header.join(&delimiter.to_string()) + "\n";
+    for record in records {
+        let row: Vec<String> = record
+            .0
+            .iter()
+            .map(|(_, value)| delimited_field(&value.to_string(), delimiter))
+            .collect();
+        output.push_str(&row.join(&delimiter.to_string()));
+        output.push('\n');
+    }
+    output
+}
+
+/// Quotes a field containing the delimiter, a quote or a line break, doubling its quotes.
+fn delimited_field(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ratio_to_json() {
+        let record =
+            Record::ratio(Ratio::new(2, 1)).prepend("indices", Value::Integers(vec![0, -1]));
+
+        assert_eq!(
+            record.to_json(),
+            r#"{"indices":[0,-1],"ratio":"1/1","cents":0,"interval":"PerfectUnison","error":0,"name":"unison"}"#
+        );
+    }
+
+    #[test]
+    fn csv_and_tsv() {
+        let records = [
+            Record::ratio(Ratio::new(3, 2)).prepend("indices", Value::Integers(vec![1, 0])),
+            Record(vec![("indices", Value::Text("say \"hi\"".to_string()))]),
+        ];
+
+        assert_eq!(
+            delimited(&records, ',').lines().collect::<Vec<&str>>(),
+            vec![
+                "indices,ratio,cents,interval,error,name",
+                "\"1,0\",3/2,701.9550008653874,PerfectFifth,1.955000865387433,perfect fifth",
+                "\"say \"\"hi\"\"\"",
+            ]
+        );
+        assert_eq!(
+            delimited(&records[..1], '\t'),
+            "indices\tratio\tcents\tinterval\terror\tname\n1,0\t3/2\t701.9550008653874\tPerfectFifth\t1.955000865387433\tperfect fifth\n"
+        );
+    }
+
+    #[test]
+    fn values_to_json() {
+        let record = Record(vec![
+            ("name", Value::Text("a \"quoted\"\tname".to_string())),
+            ("consistent", Value::Bool(true)),
+            ("steps", Value::Integer(-3)),
+            ("error", Value::Number(f64::NAN)),
+        ]);
+
+        assert_eq!(
+            json_array(&[record]),
+            r#"[{"name":"a \"quoted\"\tname","consistent":true,"steps":-3,"error":null}]"#
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! An interactive prompt for querying ratios and lattices, and playing intervals,
+//! without relaunching the CLI, or reopening the audio device, for every query.
+use crate::lattice::{Lattice, LatticeDimension, LatticeDimensionBounds::Infinite};
+#[cfg(feature = "audio")]
+use crate::play::{Play, PlaybackConfig, Player};
+use crate::ratio::Ratio;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+<n/d> ...               show the nearest 12 EDO interval to each ratio
+compare <n/d> <n/d>     show the difference, in cents, from the first ratio to the second
+lattice <n/d> ...       build a lattice with a dimension for each ratio
+at <i,j,...> ...        show the ratio at each index of the lattice
+help                    show this message
+quit                    leave the prompt";
+
+#[cfg(feature = "audio")]
+const PLAYBACK_HELP: &str = "\
+play <n/d> ...          play each ratio above the root
+set <setting> <value>   set the root, duration, volume, gap or pan of playback
+settings                show the playback settings";
+
+/// The state kept between the lines entered at the prompt.
+#[cfg_attr(not(feature = "audio"), derive(Default))]
+pub(super) struct Repl {
+    lattice: Option<Lattice<i32>>,
+    #[cfg(feature = "audio")]
+    config: PlaybackConfig,
+    /// Opened on first use, so that the prompt can be used without an audio device.
+    #[cfg(feature = "audio")]
+    player: Option<Player>,
+}
+
+impl Repl {
+    #[cfg(feature = "audio")]
+    pub fn new(config: PlaybackConfig) -> Self {
+        Self {
+            lattice: None,
+            config,
+            player: None,
+        }
+    }
+
+    /// Reads lines from the input until it ends or `quit` is entered, writing the
+    /// result of each to the output.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> std::io::Result<()> {
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            let line = line?;
+            match line.trim() {
+                "quit" | "exit" => break,
+                "" => {}
+                line => match self.eval(line) {
+                    Ok(result) if result.is_empty() => {}
+                    Ok(result) => writeln!(output, "{}", result)?,
+                    Err(message) => writeln!(output, "error: {}", message)?,
+                },
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Runs a single line entered at the prompt, returning its result, or a message
+    /// describing why it could not be run.
+    pub fn eval(&mut self, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["help"] => Ok(self.help()),
+            ["compare", a, b] => {
+                let (a, b) = (parse_ratio(a)?, parse_ratio(b)?);
+                Ok(format!("{}\t{}\t{}", a, b, b.difference(&a)))
+            }
+            ["compare", ..] => Err("compare takes two ratios".to_string()),
+            ["lattice", ratios @ ..] if !ratios.is_empty() => {
+                let dimensions = ratios
+                    .iter()
+                    .map(|r| Ok(LatticeDimension::new(parse_ratio(r)?, Infinite)))
+                    .collect::<Result<Vec<_>, String>>()?;
+                self.lattice = Some(Lattice::new(dimensions));
+                Ok(String::new())
+            }
+            ["lattice"] => Err("lattice takes at least one ratio".to_string()),
+            ["at", indices @ ..] => {
+                let lattice = self
+                    .lattice
+                    .as_ref()
+                    .ok_or("no lattice has been built yet")?;
+                let ratios = indices
+                    .iter()
+                    .map(|i| {
+                        let ratio = lattice.try_at(&parse_index(i)?);
+                        Ok(describe(
+                            ratio.map_err(|err| format!("index `{}`: {}", i, err))?,
+                        ))
+                    })
+                    .collect::<Result<Vec<String>, String>>()?;
+                Ok(ratios.join("\n"))
+            }
+            #[cfg(feature = "audio")]
+            ["play", ratios @ ..] => {
+                let ratios = parse_ratios(ratios)?;
+                let player = self.player.get_or_insert_with(|| Player::new(self.config));
+                for ratio in ratios {
+                    ratio.play(player);
+                }
+                Ok(String::new())
+            }
+            #[cfg(feature = "audio")]
+            ["set", setting, value] => {
+                let value: f32 = value
+                    .parse()
+                    .map_err(|_| format!("invalid value `{}`", value))?;
+                match *setting {
+                    "root" => self.config.root = value,
+                    "duration" => self.config.duration = value,
+                    "volume" => self.config.volume = value,
+                    "gap" => self.config.gap = value,
+                    "pan" => self.config.pan = value,
+                    _ => return Err(format!("unknown setting `{}`", setting)),
+                }
+                if let Some(player) = self.player.as_mut() {
+                    player.config = self.config;
+                }
+                Ok(String::new())
+            }
+            #[cfg(feature = "audio")]
+            ["settings"] => Ok(format!(
+                "root\t{}\nduration\t{}\nvolume\t{}\ngap\t{}\npan\t{}",
+                self.config.root,
+                self.config.duration,
+                self.config.volume,
+                self.config.gap,
+                self.config.pan
+            )),
+            _ => {
+                let ratios = parse_ratios(&words)
+                    .map_err(|_| format!("unknown command `{}`, try `help`", line))?;
+                let ratios: Vec<String> = ratios.into_iter().map(describe).collect();
+                Ok(ratios.join("\n"))
+            }
+        }
+    }
+
+    fn help(&self) -> String {
+        #[cfg(feature = "audio")]
+        return format!("{}\n{}", HELP, PLAYBACK_HELP);
+        #[cfg(not(feature = "audio"))]
+        HELP.to_string()
+    }
+}
+
+fn describe(ratio: Ratio<i32>) -> String {
+    format!("{}\t{:?}", ratio, ratio.to_approximate_12_edo_interval())
+}
+
+fn parse_ratios(ratios: &[&str]) -> Result<Vec<Ratio<i32>>, String> {
+    ratios.iter().map(|r| parse_ratio(r)).collect()
+}
+
+pub(super) fn parse_ratio(text: &str) -> Result<Ratio<i32>, String> {
+    let invalid = || format!("invalid ratio `{}`", text);
+    let (n, d) = text.split_once('/').ok_or_else(invalid)?;
+    let (n, d): (i32, i32) = (
+        n.parse().map_err(|_| invalid())?,
+        d.parse().map_err(|_| invalid())?,
+    );
+    if n <= 0 || d <= 0 {
+        return Err(invalid());
+    }
+    Ok(Ratio::new(n, d))
+}
+
+fn parse_index(text: &str) -> Result<Vec<i32>, String> {
+    text.split(',')
+        .map(|i| i.parse().map_err(|_| format!("invalid index `{}`", text)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn repl() -> Repl {
+        #[cfg(feature = "audio")]
+        return Repl::new(PlaybackConfig::default());
+        #[cfg(not(feature = "audio"))]
+        Repl::default()
+    }
+
+    #[test]
+    fn ratios_and_lattices() {
+        let mut repl = repl();
+
+        assert_eq!(
+            repl.eval("3/2 5/4"),
+            Ok(
+                "3/2\t(PerfectFifth, 1.955000865387433)\n5/4\t(MajorThird, -13.686286135165176)"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            repl.eval("at 1,0"),
+            Err("no lattice has been built yet".to_string())
+        );
+        assert_eq!(repl.eval("lattice 3/2 5/4"), Ok(String::new()));
+        assert_eq!(
+            repl.eval("at 1,1 0,-1"),
+            Ok(
+                "15/8\t(MajorSeventh, -11.7312852697778)\n8/5\t(MinorSixth, 13.686286135165233)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn compare() {
+        let mut repl = repl();
+
+        assert_eq!(
+            repl.eval("compare 10/9 9/8"),
+            Ok("10/9\t9/8\t21.50628959671478".to_string())
+        );
+        assert_eq!(
+            repl.eval("compare 10/9"),
+            Err("compare takes two ratios".to_string())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut repl = repl();
+
+        assert_eq!(
+            repl.eval("3/0"),
+            Err("unknown command `3/0`, try `help`".to_string())
+        );
+        assert_eq!(
+            repl.eval("compare 3/2 x"),
+            Err("invalid ratio `x`".to_string())
+        );
+        assert_eq!(repl.eval("lattice 3/2"), Ok(String::new()));
+        assert_eq!(repl.eval("at 1,a"), Err("invalid index `1,a`".to_string()));
+        assert_eq!(
+            repl.eval("at 30"),
+            Err("index `30`: the result overflows its integer type".to_string())
+        );
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn settings() {
+        let mut repl = repl();
+        repl.player = Some(Player::recorder(PlaybackConfig::default()));
+
+        assert_eq!(repl.eval("set root 440"), Ok(String::new()));
+        assert_eq!(repl.eval("set duration 0.1"), Ok(String::new()));
+        assert_eq!(repl.player.as_ref().unwrap().config.root, 440.);
+        assert_eq!(
+            repl.eval("set tempo 1"),
+            Err("unknown setting `tempo`".to_string())
+        );
+
+        repl.eval("play 3/2").unwrap();
+        assert!(!repl.player.as_ref().unwrap().samples().is_empty());
+    }
+
+    #[test]
+    fn run_until_quit() {
+        let mut output = vec![];
+        repl()
+            .run("3/2\n\nquit\n4/3\n".as_bytes(), &mut output)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "> 3/2\t(PerfectFifth, 1.955000865387433)\n> > "
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Generates comma pumps: chord progressions around a JI lattice which, untempered,
+//! drift by a comma on returning to the pitch class they started from.
+#[cfg(feature = "audio")]
+use crate::play::{Play, Player};
+use crate::{chord::Chord, lattice::Lattice, monzo::Monzo, ratio::Ratio, temperaments::Edo};
+use num::traits::PrimInt;
+
+/// The furthest along any one dimension of the lattice that a comma is searched for.
+const MAX_STEPS: i32 = 8;
+
+/// Models a comma pump: the same chord built on each point of a path through a lattice,
+/// one step at a time, from the origin to the comma.
+///
+/// In JI, the last chord is a comma away from the first, while in a temperament that
+/// tempers the comma out it is the same chord.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Chord, Edo, Lattice, LatticeDimension, Ratio};
+/// # use rust_intonation::{LatticeDimensionBounds::Infinite, comma_pump::CommaPump};
+/// let lattice = Lattice::new(vec![
+///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+/// ]);
+/// let major = Chord::from_harmonics(&[4, 5, 6]);
+/// let pump = CommaPump::new(&lattice, Ratio::new(81, 80), &major).unwrap();
+///
+/// assert_eq!(pump.path.last(), Some(&vec![4, -1]));
+/// assert!((pump.drift() - 21.506).abs() < 0.001);
+/// assert_eq!(pump.tempered_drift(&Edo::new(12)), 0.);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommaPump<T: PrimInt = i32> {
+    pub comma: Ratio<T>,
+    /// The lattice coordinates of the root of each chord.
+    pub path: Vec<Vec<i32>>,
+    /// The root of each chord, relative to the first.
+    pub roots: Vec<Ratio<T>>,
+    pub chords: Vec<Chord<T>>,
+}
+
+impl<T: PrimInt> CommaPump<T> {
+    /// Construct the pump of the given chord around the lattice that drifts by the
+    /// given comma, or `None` if the comma cannot be reached by stepping along the
+    /// lattice's dimensions.
+    ///
+    /// Of the ways to reach the comma, the one with the fewest steps is taken, and the
+    /// steps along each dimension are interleaved as evenly as possible. The bounds of
+    /// the lattice's dimensions are ignored.
+    pub fn new(lattice: &Lattice<T>, comma: Ratio<T>, chord: &Chord<T>) -> Option<Self> {
+        let target = coordinates(lattice, comma)?;
+
+        let mut position = vec![0; target.len()];
+        let mut root = Ratio::new(T::one(), T::one());
+        let mut path = vec![position.clone()];
+        let mut roots = vec![root];
+        while position != target {
+            let remaining =
+                |d: usize| (target[d] - position[d]).abs() as f64 / target[d].abs() as f64;
+            let dimension = (0..target.len())
+                .filter(|&d| position[d] != target[d])
+                .fold(None, |best: Option<usize>, d| match best {
+                    Some(b) if remaining(b) >= remaining(d) => Some(b),
+                    _ => Some(d),
+                })
+                .unwrap();
+
+            let ratio = lattice.dimensions[dimension].ratio;
+            if target[dimension] > 0 {
+                position[dimension] += 1;
+                root = root * ratio;
+            } else {
+                position[dimension] -= 1;
+                root = root / ratio;
+            }
+            path.push(position.clone());
+            roots.push(root);
+        }
+
+        let chords = roots.iter().map(|root| chord.transpose(*root)).collect();
+        Some(Self {
+            comma,
+            path,
+            roots,
+            chords,
+        })
+    }
+
+    /// Returns the distance, in cents, between the roots of the first and last chords
+    /// in JI, that is, the size of the comma.
+    pub fn drift(&self) -> f64 {
+        let cents = self.comma.cents();
+        if cents > 600. {
+            cents - 1200.
+        } else {
+            cents
+        }
+    }
+
+    /// Returns the distance, in cents, between the roots of the first and last chords
+    /// when played in the given EDO, which is zero if the EDO tempers out the comma.
+    pub fn tempered_drift(&self, edo: &Edo) -> f64 {
+        edo.tempered_steps(self.comma) as f64 * edo.step_size()
+    }
+}
+
+#[cfg(feature = "audio")]
+impl<T: PrimInt> Play for CommaPump<T> {
+    fn play(&self, player: &Player) {
+        let root = player.config.root;
+        for chord in &self.chords {
+            player.play_chord(root, &chord.ratios);
+        }
+    }
+}
+
+/// Returns the number of steps along each dimension of the lattice, with the fewest
+/// steps in total, that reaches the comma, ignoring octaves.
+fn coordinates<T: PrimInt>(lattice: &Lattice<T>, comma: Ratio<T>) -> Option<Vec<i32>> {
+    let dimensions: Vec<Vec<i32>> = lattice
+        .dimensions
+        .iter()
+        .map(|d| Monzo::from(d.ratio).exponents)
+        .collect();
+    let comma = Monzo::from(comma).exponents;
+    let primes = dimensions
+        .iter()
+        .map(|d| d.len())
+        .fold(comma.len(), usize::max);
+
+    let mut best: Option<Vec<i32>> = None;
+    let mut steps = vec![-MAX_STEPS; dimensions.len()];
+    loop {
+        let reaches_comma = (1..primes).all(|p| {
+            let exponent: i32 = dimensions
+                .iter()
+                .zip(steps.iter())
+                .map(|(d, s)| s * d.get(p).unwrap_or(&0))
+                .sum();
+            exponent == *comma.get(p).unwrap_or(&0)
+        });
+        let total = |s: &[i32]| s.iter().map(|s| s.abs()).sum::<i32>();
+        if reaches_comma && best.as_ref().is_none_or(|b| total(&steps) < total(b)) {
+            best = Some(steps.clone());
+        }
+
+        match steps.iter().position(|s| *s < MAX_STEPS) {
+            Some(i) => {
+                steps[i] += 1;
+                steps[..i].iter_mut().for_each(|s| *s = -MAX_STEPS);
+            }
+            None => return best,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{LatticeDimension, LatticeDimensionBounds::Infinite};
+    use pretty_assertions::assert_eq;
+
+    fn five_limit() -> Lattice<i32> {
+        Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), Infinite),
+            LatticeDimension::new(Ratio::new(5, 4), Infinite),
+        ])
+    }
+
+    #[test]
+    fn syntonic_comma_pump() {
+        let pump = CommaPump::new(
+            &five_limit(),
+            Ratio::new(81, 80),
+            &Chord::from_harmonics(&[4, 5, 6]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            pump.path,
+            vec![
+                vec![0, 0],
+                vec![1, 0],
+                vec![1, -1],
+                vec![2, -1],
+                vec![3, -1],
+                vec![4, -1]
+            ]
+        );
+        assert_eq!(
+            pump.roots,
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(3, 2),
+                Ratio::new(6, 5),
+                Ratio::new(9, 5),
+                Ratio::new(27, 20),
+                Ratio::new(81, 80)
+            ]
+        );
+        assert_eq!(
+            pump.chords[2],
+            Chord::new(vec![Ratio::new(6, 5), Ratio::new(3, 2), Ratio::new(9, 5)])
+        );
+        assert!((pump.tempered_drift(&Edo::new(53)) - 1200. / 53.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn diesis_is_tempered_out_by_12_edo() {
+        let pump = CommaPump::new(
+            &five_limit(),
+            Ratio::new(128, 125),
+            &Chord::from_harmonics(&[4, 5, 6]),
+        )
+        .unwrap();
+
+        assert_eq!(pump.path.last(), Some(&vec![0, -3]));
+        assert!((pump.drift() - 41.059).abs() < 0.001);
+        assert_eq!(pump.tempered_drift(&Edo::new(12)), 0.);
+    }
+
+    #[test]
+    fn unreachable_comma() {
+        let pump = CommaPump::new(
+            &five_limit(),
+            Ratio::new(64, 63),
+            &Chord::from_harmonics(&[4, 5, 6]),
+        );
+
+        assert_eq!(pump, None);
+    }
+}
@@ -0,0 +1,179 @@
+//! Historical models of the consonance of JI intervals, behind a common
+//! [ConsonanceMetric] trait, so that ratios, lattice regions and scales can be ranked by
+//! whichever model is wanted.
+use crate::{
+    monzo::Monzo,
+    ratio::Ratio,
+    scale::{Degree, Scale},
+};
+use num::traits::PrimInt;
+
+/// A model of how dissonant a JI interval is. Lower values are more consonant.
+pub trait ConsonanceMetric {
+    /// Returns the dissonance of the ratio under this model.
+    fn dissonance<T: PrimInt>(&self, ratio: &Ratio<T>) -> f64;
+
+    /// Returns the ratios sorted from most to least consonant. Ratios of equal
+    /// dissonance keep their order.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, Ratio};
+    /// # use rust_intonation::LatticeDimensionBounds::Infinite;
+    /// # use rust_intonation::consonance::{ConsonanceMetric, EulerGradus};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let ranked = EulerGradus.rank(&lattice.region(&[-1..=1, 0..=1]));
+    /// assert_eq!(ranked[0], Ratio::new(1, 1));
+    /// assert_eq!(ranked[1], Ratio::new(3, 2));
+    /// ```
+    fn rank<T: PrimInt>(&self, ratios: &[Ratio<T>]) -> Vec<Ratio<T>> {
+        let mut ranked = ratios.to_vec();
+        ranked.sort_by(|a, b| self.dissonance(a).total_cmp(&self.dissonance(b)));
+        ranked
+    }
+
+    /// Returns the mean dissonance of the intervals between every pair of the scale's
+    /// degrees, ignoring degrees given in cents, or `0.` if there are no such intervals.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale, consonance::{ConsonanceMetric, TenneyHeight}};
+    /// let triad = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+    /// let cluster = Scale::from_ratios(&[Ratio::new(16, 15), Ratio::new(9, 8)]);
+    /// assert!(TenneyHeight.scale_dissonance(&triad) < TenneyHeight.scale_dissonance(&cluster));
+    /// ```
+    fn scale_dissonance<T: PrimInt>(&self, scale: &Scale<T>) -> f64 {
+        let ratios: Vec<Ratio<T>> = scale
+            .degrees
+            .iter()
+            .filter_map(|degree| match degree {
+                Degree::Ratio(ratio) => Some(*ratio),
+                Degree::Cents(_) => None,
+            })
+            .collect();
+
+        let dissonances: Vec<f64> = ratios
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &low)| {
+                ratios[i + 1..]
+                    .iter()
+                    .map(move |&high| self.dissonance(&(high / low)))
+            })
+            .collect();
+        match dissonances.len() {
+            0 => 0.,
+            len => dissonances.iter().sum::<f64>() / len as f64,
+        }
+    }
+}
+
+/// Euler's gradus suavitatis. For a ratio `n/d` with `n * d = p1^e1 * p2^e2 * ...`, the
+/// gradus is `1 + e1 * (p1 - 1) + e2 * (p2 - 1) + ...`.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, consonance::{ConsonanceMetric, EulerGradus}};
+/// assert_eq!(EulerGradus.dissonance(&Ratio::new(3, 2)), 4.);
+/// assert_eq!(EulerGradus.dissonance(&Ratio::new(5, 4)), 7.);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EulerGradus;
+
+impl ConsonanceMetric for EulerGradus {
+    fn dissonance<T: PrimInt>(&self, ratio: &Ratio<T>) -> f64 {
+        let monzo = Monzo::from(*ratio);
+        let gradus: u32 = monzo
+            .primes()
+            .iter()
+            .zip(monzo.exponents.iter())
+            .map(|(p, e)| e.unsigned_abs() * (p - 1))
+            .sum();
+        (gradus + 1) as f64
+    }
+}
+
+/// James Tenney's harmonic distance, `log2(n * d)`, as given by [Ratio::tenney_height].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TenneyHeight;
+
+impl ConsonanceMetric for TenneyHeight {
+    fn dissonance<T: PrimInt>(&self, ratio: &Ratio<T>) -> f64 {
+        ratio.tenney_height()
+    }
+}
+
+/// Martin Vogel's degree of relationship: the number of steps by the consonant primes
+/// `3`, `5` and `7`, and any higher primes, needed to reach the ratio, with octaves
+/// ignored.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, consonance::{ConsonanceMetric, Vogel}};
+/// assert_eq!(Vogel.dissonance(&Ratio::new(3, 2)), 1.);
+/// assert_eq!(Vogel.dissonance(&Ratio::new(9, 8)), 2.);
+/// assert_eq!(Vogel.dissonance(&Ratio::new(81, 80)), 5.);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vogel;
+
+impl ConsonanceMetric for Vogel {
+    fn dissonance<T: PrimInt>(&self, ratio: &Ratio<T>) -> f64 {
+        let monzo = Monzo::from(*ratio);
+        monzo
+            .exponents
+            .iter()
+            .skip(1)
+            .map(|e| e.unsigned_abs())
+            .sum::<u32>() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn euler_gradus() {
+        let gradus: Vec<f64> = [(1, 1), (3, 2), (4, 3), (5, 3), (6, 5), (9, 8), (16, 15)]
+            .iter()
+            .map(|&(n, d)| EulerGradus.dissonance(&Ratio::new(n, d)))
+            .collect();
+
+        assert_eq!(gradus, vec![1., 4., 5., 7., 8., 8., 11.]);
+    }
+
+    #[test]
+    fn metrics_disagree() {
+        let ratios = [Ratio::new(7, 4), Ratio::new(9, 8)];
+
+        assert_eq!(
+            Vogel.rank(&ratios),
+            vec![Ratio::new(7, 4), Ratio::new(9, 8)]
+        );
+        assert_eq!(
+            EulerGradus.rank(&ratios),
+            vec![Ratio::new(9, 8), Ratio::new(7, 4)]
+        );
+    }
+
+    #[test]
+    fn scale_dissonance_ignores_cents() {
+        let scale: Scale = Scale::new(
+            "mixed",
+            vec![Degree::Ratio(Ratio::new(3, 2)), Degree::Cents(350.)],
+            crate::scale::Period::default(),
+        );
+
+        assert_eq!(Vogel.scale_dissonance(&scale), 1.);
+        assert_eq!(Vogel.scale_dissonance(&Scale::<i32>::from_ratios(&[])), 0.);
+    }
+}
@@ -0,0 +1,177 @@
+//! Tools for constructing Erv Wilson's combination product sets (CPS), such as the
+//! hexany, from a set of harmonic factors.
+use crate::{ratio::Ratio, scale::Scale};
+use num::traits::PrimInt;
+use std::fmt::Display;
+
+/// Models a combination product set: the products of every combination of `k` of the
+/// given factors, written `k)n` for `n` factors.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::cps::Cps;
+/// let hexany = Cps::hexany([1, 3, 5, 7]);
+/// assert_eq!(hexany.to_string(), "2)4 1.3.5.7");
+/// assert_eq!(hexany.products(), vec![3, 5, 7, 15, 21, 35]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cps<T: PrimInt = i32> {
+    pub factors: Vec<T>,
+    pub k: usize,
+}
+
+impl<T: PrimInt> Cps<T> {
+    /// Construct a new [Cps] from the products of every combination of `k` of the given
+    /// factors.
+    ///
+    /// Panics if `k` is zero, or greater than the number of factors.
+    pub fn new(factors: Vec<T>, k: usize) -> Self {
+        assert!(
+            k > 0 && k <= factors.len(),
+            "k must be between 1 and the number of factors"
+        );
+        Self { factors, k }
+    }
+
+    /// Construct the 2)4 hexany of the given factors.
+    pub fn hexany(factors: [T; 4]) -> Self {
+        Self::new(factors.to_vec(), 2)
+    }
+
+    /// Construct the 2)5 dekany of the given factors.
+    pub fn dekany(factors: [T; 5]) -> Self {
+        Self::new(factors.to_vec(), 2)
+    }
+
+    /// Construct the 3)6 eikosany of the given factors.
+    pub fn eikosany(factors: [T; 6]) -> Self {
+        Self::new(factors.to_vec(), 3)
+    }
+
+    /// Returns every combination of `k` of the factors, in the order the factors are
+    /// given.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::cps::Cps;
+    /// let cps = Cps::new(vec![1, 3, 5], 2);
+    /// assert_eq!(cps.combinations(), vec![vec![1, 3], vec![1, 5], vec![3, 5]]);
+    /// ```
+    pub fn combinations(&self) -> Vec<Vec<T>> {
+        combinations(&self.factors, self.k)
+    }
+
+    /// Returns the product of each of the [combinations][Cps::combinations].
+    pub fn products(&self) -> Vec<T> {
+        self.combinations()
+            .iter()
+            .map(|c| c.iter().fold(T::one(), |product, f| product * *f))
+            .collect()
+    }
+
+    /// Returns the ratio of each of the [products][Cps::products] to the first, so that
+    /// the product of the first `k` factors is the `1/1`.
+    pub fn ratios(&self) -> Vec<Ratio<T>> {
+        let products = self.products();
+        products
+            .iter()
+            .map(|p| Ratio::new(*p, products[0]))
+            .collect()
+    }
+}
+
+impl<T: PrimInt + Display> Cps<T> {
+    /// Returns the [ratios][Cps::ratios] of the set as an octave-repeating [Scale].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::cps::Cps;
+    /// let hexany = Cps::hexany([1, 3, 5, 7]).scale();
+    /// assert_eq!(hexany.description, "2)4 1.3.5.7 hexany");
+    /// assert_eq!(hexany.to_string(), "1/1 7/6 5/4 35/24 5/3 7/4");
+    /// ```
+    pub fn scale(&self) -> Scale<T> {
+        let mut scale = Scale::from_ratios(&self.ratios());
+        scale.description = match (self.k, self.factors.len()) {
+            (2, 4) => format!("{} hexany", self),
+            (2, 5) | (3, 5) => format!("{} dekany", self),
+            (3, 6) => format!("{} eikosany", self),
+            _ => format!("{} combination product set", self),
+        };
+        scale
+    }
+}
+
+impl<T: PrimInt + Display> Display for Cps<T> {
+    /// Uses Wilson's notation, e.g. `2)4 1.3.5.7` for the hexany.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let factors: Vec<String> = self.factors.iter().map(|f| f.to_string()).collect();
+        write!(f, "{}){} {}", self.k, self.factors.len(), factors.join("."))
+    }
+}
+
+fn combinations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    (0..items.len())
+        .flat_map(|i| {
+            combinations(&items[i + 1..], k - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, items[i]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hexany() {
+        let hexany = Cps::hexany([1, 3, 5, 7]);
+
+        assert_eq!(
+            hexany.combinations(),
+            vec![
+                vec![1, 3],
+                vec![1, 5],
+                vec![1, 7],
+                vec![3, 5],
+                vec![3, 7],
+                vec![5, 7]
+            ]
+        );
+        assert_eq!(
+            hexany.ratios(),
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(5, 3),
+                Ratio::new(7, 6),
+                Ratio::new(5, 4),
+                Ratio::new(7, 4),
+                Ratio::new(35, 24)
+            ]
+        );
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(Cps::dekany([1, 3, 5, 7, 9]).products().len(), 10);
+        assert_eq!(Cps::eikosany([1, 3, 5, 7, 9, 11]).products().len(), 20);
+        assert_eq!(Cps::eikosany([1, 3, 5, 7, 9, 11]).scale().len(), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "k must be between 1 and the number of factors")]
+    fn too_many_factors_per_combination() {
+        Cps::new(vec![1, 3], 3);
+    }
+}
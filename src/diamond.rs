@@ -1,67 +1,352 @@
 //! Tools for constructing and displaying a tonality diamond from a set of prime limits.
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use num::PrimInt;
 
-use crate::ratio::Ratio;
-use std::{fmt::Display, marker::PhantomData};
+use crate::{error::IntonationError, ratio::Ratio};
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::Frequency,
+    play::{Play, Player},
+};
+use core::{fmt::Display, marker::PhantomData, ops::Range};
 
 /// Models a tonality diamond with the given prime limits
+///
+/// Its ratios are generated once, when it is constructed, so displaying or exporting it
+/// repeatedly does not rebuild them.
 pub struct Diamond<T: PrimInt = i32> {
-    pub limits: Vec<u32>,
+    limits: Vec<u32>,
+    ratios: Vec<Vec<Ratio<i32>>>,
     phantom: PhantomData<T>,
 }
 
 impl<T: PrimInt> Display for Diamond<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ratios = self.generate();
-        let output = self
-            .index_coordinates()
-            .iter()
-            .map(|row| self.construct_diamond_row(row, &ratios))
-            .collect::<Vec<String>>()
-            .join("\n\n");
-        write!(f, "{}", output)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.display_with(|ratio| vec![ratio.to_string()]))
+    }
+}
+
+#[cfg(feature = "audio")]
+impl<T: PrimInt> Play for Diamond<T> {
+    /// Arpeggiates each otonality of the diamond, i.e. each row of
+    /// [ratios][Diamond::ratios], in turn.
+    fn play(&self, player: &Player) {
+        let root = player.config.root;
+        for row in self.ratios() {
+            let freqs: Vec<f32> = row.iter().map(|r| (Frequency(root) * *r).0).collect();
+            player.play_sequence(&freqs);
+            player.rest();
+        }
+    }
+}
+
+/// The pair of identities a cell of a diamond is built from: the limit of its otonality,
+/// as the numerator, over the limit of its utonality, as the denominator, as Partch
+/// named them, e.g. "5 over 3" for 5/3.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::diamond::Identity;
+/// let identity = Identity::new(5, 3);
+/// assert_eq!(identity.to_string(), "5 over 3");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Identity {
+    /// The limit of the cell's otonality, the numerator of its ratio.
+    pub otonal: u32,
+    /// The limit of the cell's utonality, the denominator of its ratio.
+    pub utonal: u32,
+}
+
+impl Identity {
+    /// Construct a new [Identity] from an otonal and a utonal limit.
+    pub fn new(otonal: u32, utonal: u32) -> Self {
+        Self { otonal, utonal }
+    }
+}
+
+impl Display for Identity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} over {}", self.otonal, self.utonal)
     }
 }
 
 type Coordinate = (usize, usize);
 type Coordinates = Vec<Coordinate>;
+type Cells = Vec<Vec<Vec<String>>>;
+
+/// The width, in characters, of a tab stop when the diamond is laid out with tabs.
+const TAB_WIDTH: usize = 8;
 
 impl<T: PrimInt> Diamond<T> {
+    /// Construct a new [Diamond] from the given limits.
+    ///
+    /// Panics if no limits are given, or if a limit is 0 or does not fit in an `i32`.
     pub fn new(limits: Vec<u32>) -> Self {
-        Self {
+        Self::try_new(limits).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Construct a new [Diamond], as [Diamond::new], but returning an [IntonationError]
+    /// rather than panicking if the limits cannot make a diamond.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, diamond::Diamond};
+    /// assert!(Diamond::<i32>::try_new(vec![1, 3, 5]).is_ok());
+    /// assert_eq!(Diamond::<i32>::try_new(vec![]).err(), Some(IntonationError::NoLimits));
+    /// assert_eq!(Diamond::<i32>::try_new(vec![0, 3]).err(), Some(IntonationError::ZeroLimit));
+    /// ```
+    pub fn try_new(limits: Vec<u32>) -> Result<Self, IntonationError> {
+        if limits.is_empty() {
+            return Err(IntonationError::NoLimits);
+        }
+        if limits.contains(&0) {
+            return Err(IntonationError::ZeroLimit);
+        }
+        let harmonics = limits
+            .iter()
+            .map(|limit| i32::try_from(*limit).map_err(|_| IntonationError::Overflow))
+            .collect::<Result<Vec<i32>, _>>()?;
+        let ratios = harmonics
+            .iter()
+            .map(|d| construct_ratios_with_denominator(&harmonics, *d))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
             limits,
+            ratios,
             phantom: PhantomData::<T>,
-        }
+        })
     }
 
-    pub fn generate(&self) -> Vec<Vec<Ratio<i32>>> {
+    /// Returns the limits the diamond was constructed from, in the order they were given.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::diamond::Diamond;
+    /// let diamond: Diamond = Diamond::new(vec![1, 5, 3]);
+    /// assert_eq!(diamond.limits(), &[1, 5, 3]);
+    /// ```
+    pub fn limits(&self) -> &[u32] {
+        &self.limits
+    }
+
+    /// Returns the ratios of the diamond, in a row for each limit as the denominator,
+    /// with a ratio for each limit as the numerator, in the order the limits were given.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{diamond::Diamond, Ratio};
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+    /// assert_eq!(diamond.ratios()[1][2], Ratio::new(5, 3));
+    /// ```
+    pub fn ratios(&self) -> &[Vec<Ratio<i32>>] {
+        &self.ratios
+    }
+
+    /// Returns the [Identity] of each cell of the diamond, in the rows of
+    /// [Diamond::ratios].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::diamond::{Diamond, Identity};
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+    /// assert_eq!(diamond.identities()[1][2], Identity::new(5, 3));
+    /// ```
+    pub fn identities(&self) -> Vec<Vec<Identity>> {
         self.limits
             .iter()
-            .map(|d| self.construct_ratios_with_denominator(*d as i32))
+            .map(|utonal| {
+                self.limits
+                    .iter()
+                    .map(|otonal| Identity::new(*otonal, *utonal))
+                    .collect()
+            })
             .collect()
     }
 
-    fn construct_ratios_with_denominator(&self, denominator: i32) -> Vec<Ratio<i32>> {
-        self.limits
+    /// Returns the identities of every cell of the diamond holding the given ratio, in
+    /// the order of [Diamond::identities], or none if it is not in the diamond.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{diamond::{Diamond, Identity}, Ratio};
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 9]);
+    /// assert_eq!(
+    ///     diamond.identities_of(Ratio::new(3, 2)),
+    ///     vec![Identity::new(3, 1), Identity::new(9, 3)]
+    /// );
+    /// assert_eq!(diamond.identities_of(Ratio::new(5, 4)), vec![]);
+    /// ```
+    pub fn identities_of(&self, ratio: Ratio<i32>) -> Vec<Identity> {
+        self.ratios
+            .iter()
+            .flatten()
+            .zip(self.identities().into_iter().flatten())
+            .filter(|(r, _)| **r == ratio)
+            .map(|(_, identity)| identity)
+            .collect()
+    }
+
+    /// Returns a copy of the ratios of the diamond, in the rows of [Diamond::ratios].
+    pub fn generate(&self) -> Vec<Vec<Ratio<i32>>> {
+        self.ratios.clone()
+    }
+
+    /// Lays the diamond out as its [Display] implementation does, with each cell
+    /// given by the lines returned for its ratio, so that cells can be annotated.
+    /// Lines should be shorter than a tab stop to keep the cells aligned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::diamond::Diamond;
+    /// let diamond: Diamond = Diamond::new(vec![1, 3]);
+    /// let annotated = diamond.display_with(|ratio| {
+    ///     vec![ratio.to_string(), format!("{:.0}", ratio.cents())]
+    /// });
+    /// assert_eq!(annotated, "\t3/2\n\t702\n\n1/1\t\t1/1\n0\t\t0\n\n\t4/3\n\t498");
+    /// ```
+    pub fn display_with<F: Fn(&Ratio<i32>) -> Vec<String>>(&self, cell: F) -> String {
+        self.construct_diamond(&self.cells(cell))
+    }
+
+    /// Lays the diamond out as [display_with][Diamond::display_with] does, but fitted
+    /// to the given width, in characters, so that large diamonds stay legible in a
+    /// terminal.
+    ///
+    /// If the layout with tabs is too wide, or a line of a cell is too long for a tab
+    /// stop, the cells are aligned with spaces instead, as closely as the longest line
+    /// allows. If that is still too wide, the diamond is split into pages of as even a
+    /// number of columns as possible, one after another, separated by a line of dashes.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::diamond::Diamond;
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+    /// let cell = |ratio: &rust_intonation::Ratio<i32>| vec![ratio.to_string()];
+    /// assert_eq!(diamond.display_within(80, cell), diamond.to_string());
+    /// assert_eq!(
+    ///     diamond.display_within(20, cell),
+    ///     "    5/4\n\n  3/2 5/3\n\n1/1 1/1 1/1\n\n  4/3 6/5\n\n    8/5"
+    /// );
+    /// ```
+    pub fn display_within<F: Fn(&Ratio<i32>) -> Vec<String>>(
+        &self,
+        max_width: usize,
+        cell: F,
+    ) -> String {
+        let cells = self.cells(cell);
+        let longest = cells
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let slots = 2 * self.limits.len() - 1;
+        if longest < TAB_WIDTH && (slots - 1) * TAB_WIDTH + longest <= max_width {
+            return self.construct_diamond(&cells);
+        }
+
+        // Adjacent cells in a row are two slots apart, so need a slot of at least half
+        // the longest line, and a space, to be kept apart.
+        let slot = (longest + 2) / 2;
+        let pages = slots.div_ceil(max_width.saturating_sub(longest) / slot + 1);
+        let per_page = slots.div_ceil(pages);
+        let separator = format!(
+            "\n\n{}\n\n",
+            "-".repeat(max_width.min((per_page - 1) * slot + longest))
+        );
+        (0..slots)
+            .step_by(per_page)
+            .map(|start| {
+                let page = self.construct_page(&cells, start..(start + per_page), slot);
+                page.trim_matches('\n').to_string()
+            })
+            .collect::<Vec<String>>()
+            .join(&separator)
+    }
+
+    fn cells<F: Fn(&Ratio<i32>) -> Vec<String>>(&self, cell: F) -> Cells {
+        self.ratios()
             .iter()
-            .map(|n| Ratio::new(*n as i32, denominator))
+            .map(|row| row.iter().map(&cell).collect())
             .collect()
     }
 
-    fn construct_diamond_row(&self, row: &[Coordinate], ratios: &[Vec<Ratio<i32>>]) -> String {
+    fn construct_diamond(&self, cells: &Cells) -> String {
+        self.index_coordinates()
+            .iter()
+            .map(|row| self.construct_diamond_row(row, cells))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    fn construct_diamond_row(&self, row: &[Coordinate], cells: &Cells) -> String {
         let prefix_len = self.limits.len() - row.len();
         let prefix = "\t".repeat(prefix_len);
-        format!(
-            "{}{}",
-            prefix,
-            row.iter()
-                .map(|(a, b)| format!("{}", &ratios[*a][*b]))
-                .collect::<Vec<String>>()
-                .join("\t\t")
-        )
+        let height = row.iter().map(|(a, b)| cells[*a][*b].len()).max();
+        (0..height.unwrap_or(0))
+            .map(|line| {
+                format!(
+                    "{}{}",
+                    prefix,
+                    row.iter()
+                        .map(|(a, b)| cells[*a][*b].get(line).map_or("", String::as_str))
+                        .collect::<Vec<&str>>()
+                        .join("\t\t")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 
-    fn index_coordinates(&self) -> Vec<Coordinates> {
+    /// Lays out the cells of the diamond whose slots, the tab stops of [Display], fall in
+    /// the given range, aligned with spaces to slots of the given width.
+    fn construct_page(&self, cells: &Cells, slots: Range<usize>, slot_width: usize) -> String {
+        self.index_coordinates()
+            .iter()
+            .map(|row| {
+                let first = self.limits.len() - row.len();
+                let height = row.iter().map(|(a, b)| cells[*a][*b].len()).max();
+                (0..height.unwrap_or(0))
+                    .map(|line| {
+                        let mut text = String::new();
+                        for (i, (a, b)) in row.iter().enumerate() {
+                            let slot = first + 2 * i;
+                            let Some(cell) = cells[*a][*b].get(line) else {
+                                continue;
+                            };
+                            if slots.contains(&slot) {
+                                let column = (slot - slots.start) * slot_width;
+                                text.push_str(&" ".repeat(column - text.chars().count()));
+                                text.push_str(cell);
+                            }
+                        }
+                        text
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    /// Returns the rows of the diamond as laid out by [Display], from top to bottom, as
+    /// the coordinates of each cell in [ratios][Diamond::ratios].
+    pub(crate) fn index_coordinates(&self) -> Vec<Coordinates> {
         let max = self.limits.len() - 1;
         let mut coordinate_rows = vec![];
         for i in (0..=max).rev() {
@@ -77,6 +362,16 @@ impl<T: PrimInt> Diamond<T> {
     }
 }
 
+fn construct_ratios_with_denominator(
+    limits: &[i32],
+    denominator: i32,
+) -> Result<Vec<Ratio<i32>>, IntonationError> {
+    limits
+        .iter()
+        .map(|n| Ratio::try_new(*n, denominator))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,7 +380,7 @@ mod tests {
     #[test]
     fn five_limit() {
         let d: Diamond = Diamond::new(vec![1, 3, 5]);
-        let g = d.generate();
+        let g = d.ratios();
 
         assert_eq!(g[0][0], Ratio::new(1, 1));
         assert_eq!(g[0][1], Ratio::new(3, 2));
@@ -99,4 +394,76 @@ mod tests {
         assert_eq!(g[2][1], Ratio::new(6, 5));
         assert_eq!(g[2][2], Ratio::new(1, 1));
     }
+
+    #[test]
+    fn identities() {
+        let d: Diamond = Diamond::new(vec![1, 5, 3]);
+        let identities = d.identities();
+
+        assert_eq!(identities[2][1], Identity::new(5, 3));
+        for (ratios, identities) in d.ratios().iter().zip(&identities) {
+            for (ratio, identity) in ratios.iter().zip(identities) {
+                let expected = Ratio::new(identity.otonal as i32, identity.utonal as i32);
+                assert_eq!(*ratio, expected);
+            }
+        }
+        assert_eq!(
+            d.identities_of(Ratio::new(1, 1)),
+            vec![
+                Identity::new(1, 1),
+                Identity::new(5, 5),
+                Identity::new(3, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let d: Diamond = Diamond::new(vec![1, 5, 3]);
+
+        assert_eq!(
+            d.to_string(),
+            "\t\t3/2\n\n\t5/4\t\t6/5\n\n1/1\t\t1/1\t\t1/1\n\n\t8/5\t\t5/3\n\n\t\t4/3"
+        );
+    }
+
+    #[test]
+    fn display_within_pages() {
+        let d: Diamond = Diamond::new(vec![1, 3, 5]);
+        let display = d.display_within(7, |ratio| vec![ratio.to_string()]);
+
+        assert_eq!(
+            display,
+            [
+                "    5/4\n\n  3/2\n\n1/1 1/1\n\n  4/3\n\n    8/5",
+                "5/3\n\n  1/1\n\n6/5",
+            ]
+            .join("\n\n-------\n\n")
+        );
+    }
+
+    #[test]
+    fn display_within_long_lines() {
+        let d: Diamond = Diamond::new(vec![1, 3]);
+        let display = d.display_within(80, |ratio| match ratio.numer {
+            1 => vec![ratio.to_string()],
+            _ => vec![ratio.to_string(), "x".repeat(9)],
+        });
+
+        assert_eq!(
+            display,
+            "     3/2\n     xxxxxxxxx\n\n1/1       1/1\n\n     4/3\n     xxxxxxxxx"
+        );
+    }
+
+    #[test]
+    fn display_with_uneven_cells() {
+        let d: Diamond = Diamond::new(vec![1, 3]);
+        let display = d.display_with(|ratio| match ratio.numer {
+            1 => vec![ratio.to_string()],
+            _ => vec![ratio.to_string(), "x".to_string()],
+        });
+
+        assert_eq!(display, "\t3/2\n\tx\n\n1/1\t\t1/1\n\n\t4/3\n\tx");
+    }
 }
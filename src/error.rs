@@ -0,0 +1,35 @@
+//! The error type returned by the fallible, `try_*`, variants of the crate's
+//! operations, for callers that cannot afford a panic.
+use alloc::string::String;
+use thiserror::Error;
+
+/// Describes why an operation on ratios or intervals could not be carried out.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum IntonationError {
+    /// A ratio was given a denominator of zero.
+    #[error("a ratio cannot have a zero denominator")]
+    ZeroDenominator,
+    /// A ratio was given a zero or negative numerator or denominator, so does not
+    /// describe an interval.
+    #[error("a ratio must have a positive numerator and denominator")]
+    NonPositiveRatio,
+    /// The numerator or denominator of a result does not fit in the integer type used.
+    #[error("the result overflows its integer type")]
+    Overflow,
+    /// A size in cents is not a whole number of 12 EDO semitones.
+    #[error("{0} cents is not a whole number of 12 EDO semitones")]
+    NotA12EdoInterval(f64),
+    /// A note name is not a letter, optionally followed by sharps or flats, and an
+    /// octave, e.g. `C#4`.
+    #[error("invalid note name `{0}`")]
+    InvalidNoteName(String),
+    /// A tonality diamond was given no limits to build its ratios from.
+    #[error("a tonality diamond needs at least one limit")]
+    NoLimits,
+    /// A tonality diamond was given a limit of 0, which is not a harmonic.
+    #[error("the limits of a tonality diamond must be at least 1")]
+    ZeroLimit,
+    /// An equal division was given no divisions of its period.
+    #[error("an equal division needs at least one division")]
+    NoDivisions,
+}
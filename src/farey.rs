@@ -0,0 +1,170 @@
+//! Enumerates the ratios that lie within a range of sizes, as a slice of the Farey
+//! sequence, and finds the simplest of them by descending the Stern–Brocot tree.
+//!
+//! Ranges are given in cents, and taken within the octave, `[0, 1200)`, since every
+//! [Ratio] is reduced to within the octave.
+use crate::{numtheory::gcd, ratio::Ratio};
+
+/// Tolerance, in cents, with which a ratio at either end of a range is counted as
+/// within it.
+const CENTS_TOLERANCE: f64 = 1e-9;
+
+/// The bound on the complexity of the ratios enumerated by [ratios_between].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FareyBound {
+    /// Every ratio with a denominator no greater than the bound, i.e. a slice of the
+    /// Farey sequence of that order.
+    Denominator(i32),
+    /// Every ratio with a [Tenney height][Ratio::tenney_height] no greater than the
+    /// bound.
+    TenneyHeight(f64),
+}
+
+/// Returns every ratio between the given sizes, in cents, inclusive, within the given
+/// bound, in ascending order.
+///
+/// ## Example
+///
+/// What lives between `5/4` and `9/7`?
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, farey::{ratios_between, FareyBound}};
+/// let ratios = ratios_between(
+///     Ratio::new(5, 4).cents(),
+///     Ratio::new(9, 7).cents(),
+///     FareyBound::Denominator(12),
+/// );
+/// assert_eq!(
+///     ratios,
+///     vec![
+///         Ratio::new(5, 4),
+///         Ratio::new(14, 11),
+///         Ratio::new(9, 7),
+///     ]
+/// );
+/// ```
+pub fn ratios_between(low_cents: f64, high_cents: f64, bound: FareyBound) -> Vec<Ratio<i32>> {
+    let max_denominator = match bound {
+        FareyBound::Denominator(d) => d,
+        // As n >= d, d * d <= n * d <= 2^height
+        FareyBound::TenneyHeight(height) => 2f64.powf(height / 2.).floor() as i32,
+    };
+    let (low, high) = (
+        2f64.powf(low_cents.max(0.) / 1200.),
+        2f64.powf(high_cents.min(1200.) / 1200.),
+    );
+
+    let mut ratios = vec![];
+    for d in 1..=max_denominator {
+        let first = ((low * d as f64).floor() as i32).max(d);
+        let last = ((high * d as f64).ceil() as i32).min(2 * d - 1);
+        for n in (first..=last).filter(|n| gcd(*n, d) == 1) {
+            let ratio = Ratio::new(n, d);
+            let cents = ratio.cents();
+            let within_bound = match bound {
+                FareyBound::Denominator(_) => true,
+                FareyBound::TenneyHeight(height) => ratio.tenney_height() <= height,
+            };
+            if within_bound
+                && cents >= low_cents - CENTS_TOLERANCE
+                && cents <= high_cents + CENTS_TOLERANCE
+            {
+                ratios.push(ratio);
+            }
+        }
+    }
+    ratios.sort_by(|a, b| a.cents().total_cmp(&b.cents()));
+    ratios
+}
+
+/// Returns the simplest ratio between the given sizes, in cents, inclusive: the first
+/// reached on descending the Stern–Brocot tree, which has the smallest numerator and
+/// denominator of any ratio in the range.
+///
+/// Returns `None` if the range is empty, reaches the octave, or is so narrow that the
+/// simplest ratio within it overflows `i32`.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, farey::simplest_between};
+/// assert_eq!(simplest_between(380., 400.), Some(Ratio::new(5, 4)));
+/// assert_eq!(simplest_between(690., 710.), Some(Ratio::new(3, 2)));
+/// assert_eq!(simplest_between(1150., 1300.), None);
+/// ```
+pub fn simplest_between(low_cents: f64, high_cents: f64) -> Option<Ratio<i32>> {
+    if !(low_cents <= high_cents && (0. ..1200.).contains(&high_cents)) {
+        return None;
+    }
+    let cents = |n: i32, d: i32| 1200. * (n as f64 / d as f64).log2();
+
+    let ((mut a, mut b), (mut c, mut d)) = ((0i32, 1i32), (1, 0));
+    loop {
+        let (n, m) = (a.checked_add(c)?, b.checked_add(d)?);
+        let size = cents(n, m);
+        if size < low_cents - CENTS_TOLERANCE {
+            (a, b) = (n, m);
+        } else if size > high_cents + CENTS_TOLERANCE || size >= 1200. {
+            (c, d) = (n, m);
+        } else {
+            return Some(Ratio::new(n, m));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn farey_sequence_of_order_five() {
+        let ratios = ratios_between(0., 1200., FareyBound::Denominator(5));
+
+        assert_eq!(
+            ratios,
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(6, 5),
+                Ratio::new(5, 4),
+                Ratio::new(4, 3),
+                Ratio::new(7, 5),
+                Ratio::new(3, 2),
+                Ratio::new(8, 5),
+                Ratio::new(5, 3),
+                Ratio::new(7, 4),
+                Ratio::new(9, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn tenney_height_bound() {
+        let ratios = ratios_between(300., 500., FareyBound::TenneyHeight(30f64.log2()));
+
+        assert_eq!(
+            ratios,
+            vec![Ratio::new(6, 5), Ratio::new(5, 4), Ratio::new(4, 3)]
+        );
+    }
+
+    #[test]
+    fn simplest_between_narrow_ranges() {
+        assert_eq!(simplest_between(0., 10.), Some(Ratio::new(1, 1)));
+        assert_eq!(simplest_between(960., 980.), Some(Ratio::new(7, 4)));
+        assert_eq!(simplest_between(21.5, 21.6), Some(Ratio::new(81, 80)));
+        assert_eq!(
+            simplest_between(1199.9, 1199.99),
+            Some(Ratio::new(17313, 8657))
+        );
+        let semitone = simplest_between(100., 100.).unwrap();
+        assert!((semitone.cents() - 100.).abs() <= CENTS_TOLERANCE);
+    }
+
+    #[test]
+    fn simplest_between_invalid_ranges() {
+        assert_eq!(simplest_between(700., 600.), None);
+        assert_eq!(simplest_between(1150., 1300.), None);
+        assert_eq!(simplest_between(-20., -10.), None);
+    }
+}
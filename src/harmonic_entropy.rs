@@ -0,0 +1,209 @@
+//! Paul Erlich's harmonic entropy, a measure of how ambiguous an interval is to the ear,
+//! and so of how concordant it sounds.
+//!
+//! A heard interval is modeled as a Gaussian distribution, in cents, around its true
+//! size. The probability that it is heard as each of a series of simple JI ratios is
+//! found from that distribution, and the harmonic entropy is the entropy, in nats, of
+//! those probabilities. Intervals close to a single simple ratio, such as `3/2`, have
+//! low entropy, while those between several ratios have high entropy.
+use crate::{numtheory::gcd, ratio::Ratio};
+use num::traits::PrimInt;
+
+/// How the series of JI ratios an interval can be heard as is chosen and weighted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Weighting {
+    /// Every ratio `n/d` with `n * d` no greater than the limit, each weighted by
+    /// `1 / sqrt(n * d)`, so that simpler ratios are favored.
+    Tenney,
+    /// Every ratio `n/d` with `d` no greater than the limit, each weighted by the width
+    /// of the region, bounded by the mediants with its neighbours, in which it is the
+    /// nearest ratio of the series.
+    Farey,
+}
+
+/// Settings for calculating [harmonic_entropy].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HarmonicEntropyParams {
+    /// The standard deviation, in cents, of the Gaussian an interval is heard as.
+    pub spread: f64,
+    /// The limit on the series of ratios, as described for each [Weighting].
+    pub limit: u32,
+    pub weighting: Weighting,
+}
+
+impl Default for HarmonicEntropyParams {
+    /// Uses the Tenney series of ratios with `n * d` up to 10000, widened by 17 cents.
+    fn default() -> Self {
+        Self {
+            spread: 17.,
+            limit: 10000,
+            weighting: Weighting::Tenney,
+        }
+    }
+}
+
+/// The number of standard deviations beyond which ratios are too unlikely to affect the
+/// entropy, and are left out.
+const SPREAD_CUTOFF: f64 = 6.;
+
+/// Returns the harmonic entropy, in nats, of an interval of the given size in cents.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::harmonic_entropy::{harmonic_entropy, HarmonicEntropyParams};
+/// let params = HarmonicEntropyParams::default();
+/// let fifth = harmonic_entropy(701.955, &params);
+/// let wolf_fifth = harmonic_entropy(678., &params);
+/// assert!(fifth < wolf_fifth);
+/// ```
+pub fn harmonic_entropy(cents: f64, params: &HarmonicEntropyParams) -> f64 {
+    let probabilities: Vec<f64> = match params.weighting {
+        Weighting::Tenney => tenney_probabilities(cents, params),
+        Weighting::Farey => farey_probabilities(cents, params),
+    };
+
+    let total: f64 = probabilities.iter().sum();
+    probabilities
+        .iter()
+        .map(|p| p / total)
+        .filter(|p| *p > 0.)
+        .map(|p| -p * p.ln())
+        .sum()
+}
+
+impl<T: PrimInt> Ratio<T> {
+    /// Returns the [harmonic entropy](crate::harmonic_entropy), in nats, of the ratio,
+    /// using the [default parameters](HarmonicEntropyParams::default).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Ratio;
+    /// assert!(Ratio::new(3, 2).harmonic_entropy() < Ratio::new(45, 32).harmonic_entropy());
+    /// ```
+    pub fn harmonic_entropy(&self) -> f64 {
+        harmonic_entropy(self.cents(), &HarmonicEntropyParams::default())
+    }
+}
+
+/// Returns the unnormalized probability of hearing the interval as each ratio of the
+/// Tenney series within reach of it.
+fn tenney_probabilities(cents: f64, params: &HarmonicEntropyParams) -> Vec<f64> {
+    let limit = params.limit as u64;
+    let reach = SPREAD_CUTOFF * params.spread;
+
+    let mut probabilities = vec![];
+    for d in 1..=(limit as f64).sqrt() as u64 {
+        for n in d..=limit / d {
+            let ratio_cents = ratio_cents(n, d);
+            if ratio_cents > cents + reach {
+                break;
+            }
+            if ratio_cents < cents - reach || gcd(n, d) != 1 {
+                continue;
+            }
+            let distance = (cents - ratio_cents) / params.spread;
+            probabilities.push((-distance * distance / 2.).exp() / ((n * d) as f64).sqrt());
+        }
+    }
+    probabilities
+}
+
+/// Returns the probability of hearing the interval as each ratio of the Farey series
+/// within reach of it.
+fn farey_probabilities(cents: f64, params: &HarmonicEntropyParams) -> Vec<f64> {
+    let reach = SPREAD_CUTOFF * params.spread;
+    let (low, high) = (cents - reach, cents + reach);
+
+    let mut ratios: Vec<(u64, u64)> = vec![];
+    for d in 1..=params.limit as u64 {
+        let (min, max) = (2f64.powf(low / 1200.), 2f64.powf(high / 1200.));
+        let first = ((min * d as f64).floor() as u64).max(d);
+        let last = (max * d as f64).ceil() as u64;
+        ratios.extend((first..=last).filter(|n| gcd(*n, d) == 1).map(|n| (n, d)));
+    }
+    ratios.sort_by(|(a, b), (c, d)| (a * d).cmp(&(c * b)));
+
+    ratios
+        .iter()
+        .enumerate()
+        .map(|(i, &(n, d))| {
+            let lower = match i {
+                0 => f64::NEG_INFINITY,
+                _ => ratio_cents(n + ratios[i - 1].0, d + ratios[i - 1].1),
+            };
+            let upper = match ratios.get(i + 1) {
+                Some((next_n, next_d)) => ratio_cents(n + next_n, d + next_d),
+                None => f64::INFINITY,
+            };
+            normal_cdf((upper - cents) / params.spread)
+                - normal_cdf((lower - cents) / params.spread)
+        })
+        .collect()
+}
+
+fn ratio_cents(n: u64, d: u64) -> f64 {
+    1200. * (n as f64 / d as f64).log2()
+}
+
+/// The cumulative distribution function of the standard normal distribution.
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1. + erf(x / std::f64::consts::SQRT_2))
+}
+
+/// Approximates the error function to within 1.5e-7, following Abramowitz and Stegun,
+/// formula 7.1.26.
+fn erf(x: f64) -> f64 {
+    let t = 1. / (1. + 0.327_591_1 * x.abs());
+    let polynomial = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let value = 1. - polynomial * (-x * x).exp();
+    value.copysign(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simple_ratios_are_local_minima() {
+        let params = HarmonicEntropyParams::default();
+
+        for cents in [701.955, 386.314, 1200.] {
+            let entropy = harmonic_entropy(cents, &params);
+            assert!(entropy < harmonic_entropy(cents - 20., &params));
+            assert!(entropy < harmonic_entropy(cents + 20., &params));
+        }
+    }
+
+    #[test]
+    fn farey_weighting() {
+        let params = HarmonicEntropyParams {
+            limit: 80,
+            weighting: Weighting::Farey,
+            ..HarmonicEntropyParams::default()
+        };
+
+        let fifth = harmonic_entropy(701.955, &params);
+        assert!(fifth > 0.);
+        assert!(fifth < harmonic_entropy(650., &params));
+    }
+
+    #[test]
+    fn narrower_spread_lowers_entropy() {
+        let wide = HarmonicEntropyParams::default();
+        let narrow = HarmonicEntropyParams { spread: 5., ..wide };
+
+        assert!(harmonic_entropy(500., &narrow) < harmonic_entropy(500., &wide));
+    }
+
+    #[test]
+    fn normal_cdf_values() {
+        assert!((normal_cdf(0.) - 0.5).abs() < 1e-6);
+        assert!((normal_cdf(1.) - 0.841_345).abs() < 1e-6);
+        assert!((normal_cdf(-1.96) - 0.024_998).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,206 @@
+//! Standalone HTML charts of [Lattices][Lattice] and [Diamonds][Diamond], laid out as
+//! grids of ratios that reveal each ratio's size in cents, its [Monzo], and its
+//! approximation in an EDO on hover.
+//!
+//! The charts need no scripts or external stylesheets, so can be shared as single files.
+use crate::{
+    diamond::Diamond,
+    lattice::{region_indices, Lattice},
+    monzo::Monzo,
+    ratio::Ratio,
+    temperaments::Edo,
+};
+use num::traits::PrimInt;
+use std::{fmt::Display, ops::RangeInclusive};
+
+const STYLE: &str = "body { font-family: sans-serif; }
+.grid { display: grid; gap: 0.25em; margin-bottom: 2em; }
+.cell { position: relative; padding: 0.5em; border: 1px solid #ccc; text-align: center; }
+.cell:hover { background: #eef; }
+.details { display: none; position: absolute; z-index: 1; top: 100%; left: 0; margin: 0; padding: 0.5em; background: #fff; border: 1px solid #888; text-align: left; white-space: nowrap; }
+.cell:hover .details { display: block; }
+.details dt { font-weight: bold; }
+.details dd { margin: 0 0 0.25em 0; }";
+
+/// Returns an HTML chart of the diamond, laid out as its [Display][std::fmt::Display]
+/// implementation is, with otonalities on top and utonalities on the bottom.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Edo, diamond::Diamond, html};
+/// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+/// let chart = html::diamond(&diamond, Edo::new(12));
+/// assert!(chart.contains("<span class=\"ratio\">5/3</span>"));
+/// assert!(chart.contains("<dd>[0 -1 1&gt;</dd>"));
+/// ```
+pub fn diamond<T: PrimInt>(diamond: &Diamond<T>, edo: Edo) -> String {
+    let ratios = diamond.ratios();
+    let width = 2 * diamond.limits().len() - 1;
+    let cells: Vec<String> = diamond
+        .index_coordinates()
+        .iter()
+        .enumerate()
+        .flat_map(|(row, coordinates)| {
+            let offset = diamond.limits().len() - coordinates.len();
+            coordinates
+                .iter()
+                .enumerate()
+                .map(move |(i, (a, b))| (row, offset + 2 * i, ratios[*a][*b]))
+        })
+        .map(|(row, column, ratio)| cell(ratio, row, column, edo))
+        .collect();
+    let limits: Vec<String> = diamond.limits().iter().map(u32::to_string).collect();
+    document(
+        &format!("Tonality diamond {}", limits.join(" ")),
+        &grid(width, &cells),
+    )
+}
+
+/// Returns an HTML chart of the region of the lattice spanned by the given range of
+/// indices along each dimension.
+///
+/// The first dimension runs from left to right and the second from bottom to top. A
+/// separate grid is drawn for each set of indices along any further dimensions.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Edo, Lattice, LatticeDimension, Ratio, html};
+/// # use rust_intonation::LatticeDimensionBounds::Infinite;
+/// let lattice = Lattice::new(vec![
+///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+/// ]);
+/// let chart = html::lattice(&lattice, &[-1..=1, 0..=1], Edo::new(12));
+/// assert_eq!(chart.matches("class=\"cell\"").count(), 6);
+/// assert!(chart.contains("<dd>11\\12, +11.73¢</dd>"));
+/// ```
+pub fn lattice<T: PrimInt + Display>(
+    lattice: &Lattice<T>,
+    ranges: &[RangeInclusive<i32>],
+    edo: Edo,
+) -> String {
+    let (plane, rest) = ranges.split_at(ranges.len().min(2));
+    let columns = plane.first().cloned().unwrap_or(0..=0);
+    let rows = plane.get(1).cloned().unwrap_or(0..=0);
+    let width = columns.clone().count();
+
+    let grids: Vec<String> = region_indices(rest)
+        .iter()
+        .map(|outer| {
+            let cells: Vec<String> = rows
+                .clone()
+                .rev()
+                .enumerate()
+                .flat_map(|(row, j)| {
+                    columns.clone().enumerate().map(move |(column, i)| {
+                        let mut indices = vec![i, j];
+                        indices.truncate(plane.len());
+                        indices.extend(outer);
+                        (row, column, indices)
+                    })
+                })
+                .map(|(row, column, indices)| cell(lattice.at(&indices), row, column, edo))
+                .collect();
+            let heading = if outer.is_empty() {
+                String::new()
+            } else {
+                let outer: Vec<String> = outer.iter().map(i32::to_string).collect();
+                format!("<h2>{}</h2>\n", outer.join(", "))
+            };
+            format!("{}{}", heading, grid(width, &cells))
+        })
+        .collect();
+    let dimensions: Vec<String> = lattice
+        .dimensions
+        .iter()
+        .map(|d| d.ratio.to_string())
+        .collect();
+    document(
+        &format!("Lattice {}", dimensions.join(" ")),
+        &grids.join("\n"),
+    )
+}
+
+/// Returns the cell showing the ratio, at the given 0-indexed row and column of a grid.
+fn cell<T: PrimInt + Display>(ratio: Ratio<T>, row: usize, column: usize, edo: Edo) -> String {
+    let (steps, error) = edo.approximate(ratio);
+    format!(
+        "<div class=\"cell\" style=\"grid-row: {}; grid-column: {}\"><span class=\"ratio\">{}</span><dl class=\"details\"><dt>cents</dt><dd>{:.2}</dd><dt>monzo</dt><dd>{}</dd><dt>{} EDO</dt><dd>{}\\{}, {:+.2}¢</dd></dl></div>",
+        row + 1,
+        column + 1,
+        ratio,
+        ratio.cents(),
+        escape(&Monzo::from(ratio).to_string()),
+        edo.divisions,
+        steps,
+        edo.divisions,
+        error
+    )
+}
+
+fn grid(width: usize, cells: &[String]) -> String {
+    format!(
+        "<div class=\"grid\" style=\"grid-template-columns: repeat({}, 6em)\">\n{}\n</div>",
+        width,
+        cells.join("\n")
+    )
+}
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}\n</body>\n</html>\n"
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{LatticeDimension, LatticeDimensionBounds::Infinite};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn diamond_layout() {
+        let chart = diamond(&Diamond::<i32>::new(vec![1, 3]), Edo::new(12));
+
+        assert!(chart.contains("<title>Tonality diamond 1 3</title>"));
+        assert!(chart
+            .contains("style=\"grid-row: 1; grid-column: 2\"><span class=\"ratio\">3/2</span>"));
+        assert!(chart
+            .contains("style=\"grid-row: 2; grid-column: 1\"><span class=\"ratio\">1/1</span>"));
+        assert!(chart
+            .contains("style=\"grid-row: 2; grid-column: 3\"><span class=\"ratio\">1/1</span>"));
+        assert!(chart
+            .contains("style=\"grid-row: 3; grid-column: 2\"><span class=\"ratio\">4/3</span>"));
+    }
+
+    #[test]
+    fn lattice_layout() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), Infinite),
+            LatticeDimension::new(Ratio::new(5, 4), Infinite),
+            LatticeDimension::new(Ratio::new(7, 4), Infinite),
+        ]);
+        let chart = super::lattice(&lattice, &[0..=1, 0..=1, 0..=1], Edo::new(12));
+
+        assert_eq!(chart.matches("<div class=\"grid\"").count(), 2);
+        assert!(chart.contains("<h2>1</h2>"));
+        // The second dimension ascends from the bottom row
+        assert!(chart
+            .contains("style=\"grid-row: 1; grid-column: 2\"><span class=\"ratio\">15/8</span>"));
+        assert!(chart
+            .contains("style=\"grid-row: 2; grid-column: 1\"><span class=\"ratio\">1/1</span>"));
+    }
+
+    #[test]
+    fn escapes_monzos() {
+        assert_eq!(escape("[-1 1>"), "[-1 1&gt;");
+    }
+}
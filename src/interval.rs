@@ -1,14 +1,34 @@
 //! Operations for converting between JI ratios and approximations of ET (cent-based) intervals
 
-use crate::play::{play_dyad, Play};
+use crate::error::IntonationError;
 use crate::ratio::Ratio;
-use crate::temperaments::edo::EdoInterval;
-use num::traits::PrimInt;
+#[cfg(feature = "std")]
+use crate::temperaments::{
+    edo::{Edo, EdoInterval},
+    meantone::WellTemperamentInterval,
+};
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::Frequency,
+    play::{Play, Player},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+#[cfg(feature = "std")]
+use core::fmt::Display;
+// The test harness links std, whose inherent float methods take precedence
+#[cfg(not(any(feature = "std", test)))]
+use num::traits::Float;
+use num::traits::{Euclid, PrimInt};
+
+pub mod constants;
 
 macro_rules! ji_interval {
     ($name:ident $n:tt/$d:tt) => {
         #[doc = concat!("A just intonation interval with a ratio of `", stringify!($n), "/", stringify!($d), "`.")]
-        pub const $name: (i32, i32) = ($n, $d);
+        pub const $name: Ratio<i32> = Ratio::<i32>::const_new($n, $d);
     };
 }
 
@@ -19,10 +39,9 @@ ji_interval! { PERFECT_FOURTH 4/3 }
 ji_interval! { PERFECT_FIFTH 3/2 }
 ji_interval! { MAJOR_SIXTH 5/3 }
 ji_interval! { MAJOR_SEVEN 15/8 }
-ji_interval! { OCTAVE 2/1 }
 ji_interval! { SYNTONIC_COMMA 81/80 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TwelveEDOInterval {
     PerfectUnison,
     MinorSecond,
@@ -38,14 +57,80 @@ pub enum TwelveEDOInterval {
     MajorSeventh,
 }
 
+impl TwelveEDOInterval {
+    /// Returns the interval's short name, e.g. `P5` for a perfect fifth, or `m7` for a
+    /// minor seventh.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::interval::TwelveEDOInterval;
+    /// assert_eq!(TwelveEDOInterval::AugmentedFourth.abbreviation(), "A4");
+    /// ```
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::PerfectUnison => "P1",
+            Self::MinorSecond => "m2",
+            Self::MajorSecond => "M2",
+            Self::MinorThird => "m3",
+            Self::MajorThird => "M3",
+            Self::PerfectFourth => "P4",
+            Self::AugmentedFourth => "A4",
+            Self::PerfectFifth => "P5",
+            Self::MinorSixth => "m6",
+            Self::MajorSixth => "M6",
+            Self::MinorSeventh => "m7",
+            Self::MajorSeventh => "M7",
+        }
+    }
+
+    /// Returns the interval, reduced to within the octave, that is the given whole
+    /// number of 12 EDO semitones, in cents, or an [IntonationError] if the size falls
+    /// between semitones.
+    ///
+    /// Converting from an `f64` with `into` does the same, but panics instead.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, interval::TwelveEDOInterval};
+    /// assert_eq!(
+    ///     TwelveEDOInterval::try_from_cents(1900.),
+    ///     Ok(TwelveEDOInterval::PerfectFifth)
+    /// );
+    /// assert_eq!(
+    ///     TwelveEDOInterval::try_from_cents(386.3),
+    ///     Err(IntonationError::NotA12EdoInterval(386.3))
+    /// );
+    /// ```
+    #[allow(clippy::redundant_guards)]
+    pub fn try_from_cents(cents: f64) -> Result<Self, IntonationError> {
+        Ok(match Euclid::rem_euclid(&(cents / 100.), &12.) {
+            n if n == 0. => Self::PerfectUnison,
+            n if n == 1. => Self::MinorSecond,
+            n if n == 2. => Self::MajorSecond,
+            n if n == 3. => Self::MinorThird,
+            n if n == 4. => Self::MajorThird,
+            n if n == 5. => Self::PerfectFourth,
+            n if n == 6. => Self::AugmentedFourth,
+            n if n == 7. => Self::PerfectFifth,
+            n if n == 8. => Self::MinorSixth,
+            n if n == 9. => Self::MajorSixth,
+            n if n == 10. => Self::MinorSeventh,
+            n if n == 11. => Self::MajorSeventh,
+            _ => return Err(IntonationError::NotA12EdoInterval(cents)),
+        })
+    }
+}
+
+#[cfg(feature = "audio")]
 impl Play for TwelveEDOInterval {
-    fn play(&self) {
-        let middle_c = 440. * 2.0_f32.powf(-9. / 12.);
+    fn play(&self, player: &Player) {
+        let root = player.config.root;
         let et_steps: usize = self.into();
-        let et_steps = et_steps as f32;
-        let et_freq = middle_c * 2f32.powf(et_steps / 12.);
+        let et_freq = Frequency(root).transpose(100. * et_steps as f64).0;
 
-        play_dyad(middle_c, et_freq);
+        player.play_dyad(root, et_freq);
     }
 }
 
@@ -70,32 +155,106 @@ impl From<&TwelveEDOInterval> for usize {
 
 impl From<f64> for TwelveEDOInterval {
     fn from(value: f64) -> Self {
-        match (value / 100.) % 12. {
-            n if n == 0. => Self::PerfectUnison,
-            n if n == 1. => Self::MinorSecond,
-            n if n == 2. => Self::MajorSecond,
-            n if n == 3. => Self::MinorThird,
-            n if n == 4. => Self::MajorThird,
-            n if n == 5. => Self::PerfectFourth,
-            n if n == 6. => Self::AugmentedFourth,
-            n if n == 7. => Self::PerfectFifth,
-            n if n == 8. => Self::MinorSixth,
-            n if n == 9. => Self::MajorSixth,
-            n if n == 10. => Self::MinorSeventh,
-            n if n == 11. => Self::MajorSeventh,
-            _ => panic!(),
-        }
+        Self::try_from_cents(value).unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+/// Upper bounds, in cents, of the ranges used to name intervals by size.
+const INTERVAL_NAMES: [(f64, &str); 29] = [
+    (35., "unison"),
+    (80., "quarter tone"),
+    (125., "minor second"),
+    (170., "neutral second"),
+    (215., "major second"),
+    (250., "supermajor second"),
+    (290., "subminor third"),
+    (330., "minor third"),
+    (370., "neutral third"),
+    (410., "major third"),
+    (450., "supermajor third"),
+    (480., "narrow fourth"),
+    (520., "perfect fourth"),
+    (560., "wide fourth"),
+    (640., "tritone"),
+    (680., "narrow fifth"),
+    (720., "perfect fifth"),
+    (750., "wide fifth"),
+    (790., "subminor sixth"),
+    (830., "minor sixth"),
+    (870., "neutral sixth"),
+    (910., "major sixth"),
+    (950., "supermajor sixth"),
+    (990., "subminor seventh"),
+    (1030., "minor seventh"),
+    (1070., "neutral seventh"),
+    (1110., "major seventh"),
+    (1165., "supermajor seventh"),
+    (1200., "octave"),
+];
+
+/// Returns a human-readable name for an interval of the given size in cents, based on
+/// the range of sizes it falls into.
+///
+/// Intervals larger than an octave are named by their octave-reduced size, and
+/// descending intervals are named as such.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::interval::interval_name;
+/// assert_eq!(interval_name(701.955), "perfect fifth");
+/// assert_eq!(interval_name(347.408), "neutral third");
+/// assert_eq!(interval_name(-386.314), "descending major third");
+/// ```
+pub fn interval_name(cents: f64) -> String {
+    let size = cents.abs();
+    let reduced = size % 1200.;
+    let reduced = if size >= 1200. && reduced < INTERVAL_NAMES[0].0 {
+        reduced + 1200.
+    } else {
+        reduced
+    };
+
+    let name = INTERVAL_NAMES
+        .iter()
+        .find(|(bound, _)| reduced < *bound)
+        .map_or("octave", |(_, name)| name);
+
+    if cents < 0. {
+        format!("descending {}", name)
+    } else {
+        name.to_string()
     }
 }
 
+/// Returns the name of the given ratio if it is one of the named intervals or commas
+/// in [constants], e.g. `"septimal minor third"` for `7/6`.
+///
+/// Unlike [interval_name], which names any size by the range it falls into, only
+/// exact ratios are recognized.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{interval::name_of, Ratio};
+/// assert_eq!(name_of(Ratio::new(3, 2)), Some("perfect fifth"));
+/// assert_eq!(name_of(Ratio::new(81, 80)), Some("syntonic comma"));
+/// assert_eq!(name_of(Ratio::new(17, 16)), None);
+/// ```
+pub fn name_of<T: PrimInt>(ratio: Ratio<T>) -> Option<&'static str> {
+    let (numer, denom): (i32, i32) = (num::cast(ratio.numer)?, num::cast(ratio.denom)?);
+    constants::up_to_limit(i32::MAX)
+        .find(|(_, r)| r.numer == numer && r.denom == denom)
+        .map(|(name, _)| *name)
+}
+
 /// Describes the approximation of an equal tempered interval as a tuple
 /// pair of the named ET interval and a difference from ET, given in cents.
 pub type Approximate12EDOInterval = (TwelveEDOInterval, f64);
 
 impl<T: PrimInt> From<Ratio<T>> for Approximate12EDOInterval {
     fn from(value: Ratio<T>) -> Self {
-        let f: f64 = (&value).into();
-        let ji_cents: f64 = 1200. * f.log2();
+        let ji_cents: f64 = value.cents();
 
         let et_cents = (ji_cents / 100.).round() * 100.;
 
@@ -103,8 +262,55 @@ impl<T: PrimInt> From<Ratio<T>> for Approximate12EDOInterval {
     }
 }
 
-impl<'a> From<EdoInterval<'a>> for Approximate12EDOInterval {
-    fn from(value: EdoInterval<'a>) -> Self {
+#[cfg(feature = "std")]
+impl From<EdoInterval> for Approximate12EDOInterval {
+    fn from(value: EdoInterval) -> Self {
+        let non_12_cents: f64 = value.cents as f64;
+        let et_12_cents: f64 = (non_12_cents / 100.).round() * 100.;
+        (et_12_cents.into(), non_12_cents - et_12_cents)
+    }
+}
+
+/// Describes the comparison of an interval in some EDO to its nearest 12 EDO interval,
+/// retaining the source EDO and step alongside the approximation.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TwelveEDOComparison {
+    pub edo: Edo,
+    pub steps: i32,
+    pub cents: f64,
+    pub nearest: TwelveEDOInterval,
+    pub delta: f64,
+}
+
+#[cfg(feature = "std")]
+impl From<EdoInterval> for TwelveEDOComparison {
+    fn from(value: EdoInterval) -> Self {
+        let (nearest, delta) = value.into();
+        Self {
+            edo: value.edo(),
+            steps: value.steps(),
+            cents: value.cents as f64,
+            nearest,
+            delta,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for TwelveEDOComparison {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}/{}\t{}\t{:?}\t{}",
+            self.steps, self.edo.divisions, self.cents, self.nearest, self.delta
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<WellTemperamentInterval> for Approximate12EDOInterval {
+    fn from(value: WellTemperamentInterval) -> Self {
         let non_12_cents: f64 = value.cents as f64;
         let et_12_cents: f64 = (non_12_cents / 100.).round() * 100.;
         (et_12_cents.into(), non_12_cents - et_12_cents)
@@ -125,6 +331,55 @@ mod tests {
         assert_eq!(i1, (PerfectUnison, 0.));
     }
 
+    #[test]
+    fn ji_interval_constants() {
+        assert_eq!(MAJOR_SEVEN, Ratio::new(15, 8));
+        assert_eq!(SYNTONIC_COMMA, PERFECT_FIFTH.pow(4) / MAJOR_THIRD);
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(interval_name(0.), "unison");
+        assert_eq!(interval_name(21.506), "unison");
+        assert_eq!(interval_name(231.174), "supermajor second");
+        assert_eq!(interval_name(968.826), "subminor seventh");
+        assert_eq!(interval_name(1200.), "octave");
+        assert_eq!(interval_name(1901.955), "perfect fifth");
+        assert_eq!(interval_name(2400.), "octave");
+        assert_eq!(interval_name(-700.), "descending perfect fifth");
+    }
+
+    #[test]
+    fn name_of_named_intervals() {
+        assert_eq!(name_of(Ratio::new(7, 6)), Some("septimal minor third"));
+        assert_eq!(name_of(Ratio::new(2, 1)), Some("unison"));
+        assert_eq!(
+            name_of(Ratio::<i64>::new(531441, 524288)),
+            Some("pythagorean comma")
+        );
+        assert_eq!(name_of(Ratio::<i64>::new(1 << 40, (1 << 40) - 1)), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn twelve_edo_comparison() {
+        let fifth = Edo::new(53).interval(31);
+        let comparison: TwelveEDOComparison = fifth.into();
+
+        assert_eq!(comparison.edo, Edo::new(53));
+        assert_eq!(comparison.steps, 31);
+        assert_eq!(comparison.nearest, PerfectFifth);
+        assert!((comparison.cents - 701.887).abs() < 0.001);
+        assert!((comparison.delta - 1.887).abs() < 0.001);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn twelve_edo_comparison_display() {
+        let comparison: TwelveEDOComparison = Edo::new(24).interval(7).into();
+        assert_eq!(comparison.to_string(), "7/24\t350\tMajorThird\t-50");
+    }
+
     #[test]
     fn perfect_fifth() {
         let r = Ratio::new(3, 2);
@@ -0,0 +1,234 @@
+//! Named JI intervals, commas included, grouped by prime limit, for enumerating
+//! intervals and looking them up by name.
+//!
+//! Each group holds the intervals whose [limit][Ratio::limit] is exactly its prime, in
+//! ascending order of size. As every [Ratio] is normalized into the octave, the octave
+//! itself is left out, as it is equal to the unison.
+use crate::ratio::Ratio;
+
+/// A JI interval with its name, e.g. `("perfect fifth", 3/2)`.
+pub type NamedInterval = (&'static str, Ratio<i32>);
+
+const fn named(name: &'static str, numer: i32, denom: i32) -> NamedInterval {
+    (name, Ratio::<i32>::const_new(numer, denom))
+}
+
+static THREE_LIMIT: [NamedInterval; 14] = [
+    named("unison", 1, 1),
+    named("pythagorean comma", 531441, 524288),
+    named("pythagorean limma", 256, 243),
+    named("pythagorean apotome", 2187, 2048),
+    named("major whole tone", 9, 8),
+    named("pythagorean minor third", 32, 27),
+    named("pythagorean major third", 81, 64),
+    named("perfect fourth", 4, 3),
+    named("pythagorean augmented fourth", 729, 512),
+    named("perfect fifth", 3, 2),
+    named("pythagorean minor sixth", 128, 81),
+    named("pythagorean major sixth", 27, 16),
+    named("pythagorean minor seventh", 16, 9),
+    named("pythagorean major seventh", 243, 128),
+];
+
+static FIVE_LIMIT: [NamedInterval; 16] = [
+    named("schisma", 32805, 32768),
+    named("diaschisma", 2048, 2025),
+    named("syntonic comma", 81, 80),
+    named("lesser diesis", 128, 125),
+    named("greater diesis", 648, 625),
+    named("just chromatic semitone", 25, 24),
+    named("just diatonic semitone", 16, 15),
+    named("minor whole tone", 10, 9),
+    named("just minor third", 6, 5),
+    named("just major third", 5, 4),
+    named("just augmented fourth", 45, 32),
+    named("just diminished fifth", 64, 45),
+    named("just minor sixth", 8, 5),
+    named("just major sixth", 5, 3),
+    named("just minor seventh", 9, 5),
+    named("just major seventh", 15, 8),
+];
+
+static SEVEN_LIMIT: [NamedInterval; 17] = [
+    named("septimal kleisma", 225, 224),
+    named("septimal semicomma", 126, 125),
+    named("septimal comma", 64, 63),
+    named("slendro diesis", 49, 48),
+    named("septimal quarter tone", 36, 35),
+    named("septimal third tone", 28, 27),
+    named("septimal chromatic semitone", 21, 20),
+    named("septimal diatonic semitone", 15, 14),
+    named("septimal major second", 8, 7),
+    named("septimal minor third", 7, 6),
+    named("septimal major third", 9, 7),
+    named("septimal narrow fourth", 21, 16),
+    named("septimal tritone", 7, 5),
+    named("greater septimal tritone", 10, 7),
+    named("septimal minor sixth", 14, 9),
+    named("septimal major sixth", 12, 7),
+    named("harmonic seventh", 7, 4),
+];
+
+static ELEVEN_LIMIT: [NamedInterval; 10] = [
+    named("neutral third comma", 243, 242),
+    named("undecimal quarter tone", 33, 32),
+    named("undecimal neutral second", 12, 11),
+    named("greater undecimal neutral second", 11, 10),
+    named("undecimal neutral third", 11, 9),
+    named("undecimal superfourth", 11, 8),
+    named("undecimal subfifth", 16, 11),
+    named("undecimal neutral sixth", 18, 11),
+    named("undecimal neutral seventh", 11, 6),
+    named("undecimal supermajor seventh", 21, 11),
+];
+
+static THIRTEEN_LIMIT: [NamedInterval; 8] = [
+    named("tridecimal comma", 27, 26),
+    named("lesser tridecimal two-third tone", 14, 13),
+    named("greater tridecimal two-third tone", 13, 12),
+    named("tridecimal minor third", 13, 11),
+    named("tridecimal neutral third", 16, 13),
+    named("tridecimal semi-diminished fourth", 13, 10),
+    named("tridecimal neutral sixth", 13, 8),
+    named("tridecimal neutral seventh", 13, 7),
+];
+
+/// Each prime limit with its group of intervals.
+static LIMITS: [(i32, &[NamedInterval]); 5] = [
+    (3, &THREE_LIMIT),
+    (5, &FIVE_LIMIT),
+    (7, &SEVEN_LIMIT),
+    (11, &ELEVEN_LIMIT),
+    (13, &THIRTEEN_LIMIT),
+];
+
+/// Returns the named 3-limit, or Pythagorean, intervals, including the unison.
+pub fn three_limit() -> &'static [NamedInterval] {
+    &THREE_LIMIT
+}
+
+/// Returns the named intervals whose limit is 5.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{interval::constants, Ratio};
+/// let third = constants::five_limit().iter().find(|(name, _)| *name == "just major third");
+/// assert_eq!(third, Some(&("just major third", Ratio::new(5, 4))));
+/// ```
+pub fn five_limit() -> &'static [NamedInterval] {
+    &FIVE_LIMIT
+}
+
+/// Returns the named intervals whose limit is 7.
+pub fn seven_limit() -> &'static [NamedInterval] {
+    &SEVEN_LIMIT
+}
+
+/// Returns the named intervals whose limit is 11.
+pub fn eleven_limit() -> &'static [NamedInterval] {
+    &ELEVEN_LIMIT
+}
+
+/// Returns the named intervals whose limit is 13.
+pub fn thirteen_limit() -> &'static [NamedInterval] {
+    &THIRTEEN_LIMIT
+}
+
+/// Returns every named interval whose limit is no greater than the given limit, group
+/// by group.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::interval::constants;
+/// assert_eq!(constants::up_to_limit(5).count(), 30);
+/// assert!(constants::up_to_limit(7).any(|(name, _)| *name == "harmonic seventh"));
+/// ```
+pub fn up_to_limit(limit: i32) -> impl Iterator<Item = &'static NamedInterval> {
+    LIMITS
+        .iter()
+        .filter(move |(prime, _)| *prime <= limit)
+        .flat_map(|(_, intervals)| intervals.iter())
+}
+
+/// Looks up a named interval by name, ignoring case, and treating hyphens and
+/// underscores as spaces.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{interval::constants, Ratio};
+/// assert_eq!(constants::lookup("Septimal minor third"), Some(Ratio::new(7, 6)));
+/// assert_eq!(constants::lookup("syntonic-comma"), Some(Ratio::new(81, 80)));
+/// assert_eq!(constants::lookup("wolf fifth"), None);
+/// ```
+pub fn lookup(name: &str) -> Option<Ratio<i32>> {
+    find(name).map(|(_, ratio)| *ratio)
+}
+
+/// Looks up a named interval by name, as [lookup] does, returning it with its name as
+/// it is listed.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{interval::constants, Ratio};
+/// assert_eq!(constants::find("Unison"), Some(&("unison", Ratio::new(1, 1))));
+/// ```
+pub fn find(name: &str) -> Option<&'static NamedInterval> {
+    up_to_limit(i32::MAX).find(|(interval, _)| names_match(interval, name))
+}
+
+fn names_match(name: &str, query: &str) -> bool {
+    let normalize = |c: u8| match c {
+        b'-' | b'_' => b' ',
+        c => c.to_ascii_lowercase(),
+    };
+    name.len() == query.len()
+        && name
+            .bytes()
+            .zip(query.bytes())
+            .all(|(a, b)| normalize(a) == normalize(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn groups_hold_their_limit_in_ascending_order() {
+        for (prime, intervals) in LIMITS {
+            // Strictly ascending within each group, which, with each group's distinct
+            // limit, keeps every ratio unique
+            for pair in intervals.windows(2) {
+                assert!(pair[0].1.cents() < pair[1].1.cents(), "{:?}", pair);
+            }
+            // Bar the unison, which is 2-limit
+            for (name, ratio) in intervals.iter().filter(|(name, _)| *name != "unison") {
+                assert_eq!(ratio.limit(), prime, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut names: Vec<&str> = up_to_limit(13).map(|(name, _)| *name).collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn lookup_ignores_case_and_separators() {
+        assert_eq!(lookup("PERFECT_FIFTH"), Some(Ratio::new(3, 2)));
+        assert_eq!(
+            lookup("tridecimal semi diminished fourth"),
+            Some(Ratio::new(13, 10))
+        );
+        assert_eq!(lookup("perfect fifths"), None);
+    }
+}
@@ -0,0 +1,398 @@
+//! A single, self-describing JSON schema for exporting the pitches of [Diamonds][Diamond],
+//! [Lattice] regions, [Scales][Scale] and [EDO comparisons](Edo::comparison_table), so
+//! that visualization tools can read them all in one format.
+//!
+//! Every export is a JSON object of the same shape, e.g. for a diamond
+//!
+//! ```json
+//! {
+//!   "schema": "rust-intonation/pitches/v1",
+//!   "kind": "diamond",
+//!   "description": "1 3 tonality diamond",
+//!   "source": {"limits": [1, 3]},
+//!   "edo": {"divisions": 12, "period": "2/1"},
+//!   "pitches": [
+//!     {
+//!       "position": [1, 0],
+//!       "ratio": "3/2",
+//!       "cents": 701.9550008653874,
+//!       "monzo": [-1, 1],
+//!       "name": "perfect fifth",
+//!       "approximation": {"steps": 7, "cents": 700, "error": -1.955000865387433},
+//!       "identity": {"otonal": 3, "utonal": 1}
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! - `kind` is one of `diamond`, `lattice`, `scale` or `edo`, and `source` holds what
+//!   the pitches were built from: the `limits` of a diamond; the `dimensions` of a
+//!   lattice, each with its `ratio`, `bounds` (`null` if infinite, or a `length` or
+//!   inclusive `range`), `offset` and `label` (`null` if it has none), and the `region` of indices, as `[low, high]` along each
+//!   dimension; the `period` of a scale; or the `divisions` and `period` of an EDO.
+//! - `position` locates each pitch in its source: the indices of its numerator and
+//!   denominator among the limits of a diamond, its indices in a lattice, its degree in a
+//!   scale, or its index among the ratios an EDO is compared to.
+//! - `ratio`, `monzo` and `name` are `null` for pitches given only in cents, and `name`
+//!   is `null` for ratios without a [name](crate::interval::name_of).
+//! - `approximation` is the nearest step of the EDO given in `edo`, with the error, in
+//!   cents, from the pitch to the step, as in [Edo::approximate].
+//! - `identity` is given only for the pitches of a diamond: the limits of the otonality
+//!   and utonality of its cell, as in [Diamond::identities].
+//!
+//! Exports are written without whitespace between values, and numbers that are not
+//! finite are written as `null`.
+use crate::{
+    diamond::Diamond,
+    interval::name_of,
+    lattice::{region_indices, Lattice, LatticeDimensionBounds},
+    monzo::Monzo,
+    ratio::Ratio,
+    scale::{Degree, Period, Scale},
+    temperaments::Edo,
+};
+use num::traits::PrimInt;
+use std::{fmt::Display, ops::RangeInclusive};
+
+/// The identifier of the schema, and its version, given in every export.
+pub const SCHEMA: &str = "rust-intonation/pitches/v1";
+
+/// Returns the ratios of the diamond, in the rows of [Diamond::ratios], with their
+/// [identities](Diamond::identities) and approximations in the given EDO.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Edo, diamond::Diamond, json};
+/// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+/// let export = json::diamond(&diamond, Edo::new(12));
+/// assert!(export.starts_with(r#"{"schema":"rust-intonation/pitches/v1","kind":"diamond""#));
+/// assert!(export.contains(r#"{"position":[2,1],"ratio":"5/3","#));
+/// assert!(export.contains(r#""identity":{"otonal":5,"utonal":3}}"#));
+/// ```
+pub fn diamond<T: PrimInt + Display>(diamond: &Diamond<T>, edo: Edo) -> String {
+    let identities = diamond.identities();
+    let pitches: Vec<String> = diamond
+        .ratios()
+        .iter()
+        .zip(&identities)
+        .enumerate()
+        .flat_map(|(denom, (row, identities))| {
+            row.iter()
+                .zip(identities)
+                .enumerate()
+                .map(move |(numer, (ratio, identity))| {
+                    let position = [numer as i32, denom as i32];
+                    let identity = object(&[
+                        ("otonal", identity.otonal.to_string()),
+                        ("utonal", identity.utonal.to_string()),
+                    ]);
+                    ratio_pitch(&position, *ratio, edo, &[("identity", identity)])
+                })
+        })
+        .collect();
+    let limits: Vec<String> = diamond.limits().iter().map(u32::to_string).collect();
+    document(
+        "diamond",
+        &format!("{} tonality diamond", limits.join(" ")),
+        &object(&[("limits", format!("[{}]", limits.join(",")))]),
+        edo,
+        &pitches,
+    )
+}
+
+/// Returns the ratios in the region of the lattice spanned by the given range of
+/// indices along each dimension, in the order of [Lattice::region], with their
+/// approximations in the given EDO.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Edo, Lattice, LatticeDimension, Ratio, json};
+/// # use rust_intonation::LatticeDimensionBounds::Infinite;
+/// let lattice = Lattice::new(vec![
+///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+/// ]);
+/// let export = json::lattice(&lattice, &[-1..=1, 0..=1], Edo::new(12));
+/// assert!(export.contains(r#""region":[[-1,1],[0,1]]"#));
+/// assert!(export.contains(r#"{"position":[1,1],"ratio":"15/8","#));
+/// ```
+pub fn lattice<T: PrimInt + Display>(
+    lattice: &Lattice<T>,
+    ranges: &[RangeInclusive<i32>],
+    edo: Edo,
+) -> String {
+    let pitches: Vec<String> = region_indices(ranges)
+        .iter()
+        .map(|indices| ratio_pitch(indices, lattice.at(indices), edo, &[]))
+        .collect();
+    let dimensions: Vec<String> = lattice
+        .dimensions
+        .iter()
+        .map(|dimension| {
+            let bounds = match dimension.bounds {
+                LatticeDimensionBounds::Infinite => "null".to_string(),
+                LatticeDimensionBounds::LengthBounded(n) => object(&[("length", n.to_string())]),
+                LatticeDimensionBounds::RangeBounded(a, b) => {
+                    object(&[("range", format!("[{},{}]", a, b))])
+                }
+            };
+            object(&[
+                ("ratio", string(&dimension.ratio.to_string())),
+                ("bounds", bounds),
+                ("offset", dimension.offset.to_string()),
+                (
+                    "label",
+                    dimension
+                        .label
+                        .as_deref()
+                        .map_or("null".to_string(), string),
+                ),
+            ])
+        })
+        .collect();
+    let region: Vec<String> = ranges
+        .iter()
+        .map(|range| format!("[{},{}]", range.start(), range.end()))
+        .collect();
+    let ratios: Vec<String> = lattice
+        .dimensions
+        .iter()
+        .map(|dimension| dimension.ratio.to_string())
+        .collect();
+    document(
+        "lattice",
+        &format!("{} lattice", ratios.join(" ")),
+        &object(&[
+            ("dimensions", format!("[{}]", dimensions.join(","))),
+            ("region", format!("[{}]", region.join(","))),
+        ]),
+        edo,
+        &pitches,
+    )
+}
+
+/// Returns the degrees of the scale, starting with the root, with their approximations
+/// in the given EDO.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Edo, Scale, json};
+/// let export = json::scale(&Scale::from_edo(Edo::new(5)), Edo::new(12));
+/// assert!(export.contains(r#""source":{"period":"2/1"}"#));
+/// assert!(export.contains(r#"{"position":[1],"ratio":null,"cents":240,"monzo":null,"#));
+/// ```
+pub fn scale<T: PrimInt + Display>(scale: &Scale<T>, edo: Edo) -> String {
+    let pitches: Vec<String> = scale
+        .degrees
+        .iter()
+        .enumerate()
+        .map(|(degree, pitch)| match pitch {
+            Degree::Ratio(ratio) => ratio_pitch(&[degree as i32], *ratio, edo, &[]),
+            Degree::Cents(cents) => cents_pitch(&[degree as i32], *cents, edo),
+        })
+        .collect();
+    let period = match scale.period {
+        Period::Ratio(..) => string(&scale.period.to_string()),
+        Period::Cents(cents) => number(cents),
+    };
+    document(
+        "scale",
+        &scale.description,
+        &object(&[("period", period)]),
+        edo,
+        &pitches,
+    )
+}
+
+/// Returns the given ratios with their approximations in the EDO, such as those of its
+/// [comparison table](Edo::comparison_table).
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Edo, Ratio, json};
+/// let export = json::edo(Edo::new(31), &[Ratio::new(5, 4)]);
+/// assert!(export.contains(r#""source":{"divisions":31,"period":"2/1"}"#));
+/// assert!(export.contains(r#""approximation":{"steps":10,"#));
+/// ```
+pub fn edo<T: PrimInt + Display>(edo: Edo, ratios: &[Ratio<T>]) -> String {
+    let pitches: Vec<String> = ratios
+        .iter()
+        .enumerate()
+        .map(|(i, ratio)| ratio_pitch(&[i as i32], *ratio, edo, &[]))
+        .collect();
+    document(
+        "edo",
+        &format!("{} EDO", edo.divisions),
+        &edo_object(edo),
+        edo,
+        &pitches,
+    )
+}
+
+fn document(kind: &str, description: &str, source: &str, edo: Edo, pitches: &[String]) -> String {
+    object(&[
+        ("schema", string(SCHEMA)),
+        ("kind", string(kind)),
+        ("description", string(description)),
+        ("source", source.to_string()),
+        ("edo", edo_object(edo)),
+        ("pitches", format!("[{}]", pitches.join(","))),
+    ])
+}
+
+fn edo_object(edo: Edo) -> String {
+    let (n, d) = edo.period;
+    object(&[
+        ("divisions", edo.divisions.to_string()),
+        ("period", string(&format!("{}/{}", n, d))),
+    ])
+}
+
+/// Returns the pitch of the ratio, with the given extra fields, whose values are already
+/// JSON, after the others.
+fn ratio_pitch<T: PrimInt + Display>(
+    position: &[i32],
+    ratio: Ratio<T>,
+    edo: Edo,
+    extra: &[(&str, String)],
+) -> String {
+    let monzo: Vec<String> = Monzo::from(ratio)
+        .exponents
+        .iter()
+        .map(i32::to_string)
+        .collect();
+    pitch(
+        position,
+        string(&ratio.to_string()),
+        ratio.cents(),
+        format!("[{}]", monzo.join(",")),
+        name_of(ratio).map_or("null".to_string(), string),
+        edo,
+        extra,
+    )
+}
+
+fn cents_pitch(position: &[i32], cents: f64, edo: Edo) -> String {
+    let null = || "null".to_string();
+    pitch(position, null(), cents, null(), null(), edo, &[])
+}
+
+fn pitch(
+    position: &[i32],
+    ratio: String,
+    cents: f64,
+    monzo: String,
+    name: String,
+    edo: Edo,
+    extra: &[(&str, String)],
+) -> String {
+    let position: Vec<String> = position.iter().map(i32::to_string).collect();
+    let steps = (cents / edo.step_size()).round();
+    let step_cents = steps * edo.step_size();
+    let mut fields = vec![
+        ("position", format!("[{}]", position.join(","))),
+        ("ratio", ratio),
+        ("cents", number(cents)),
+        ("monzo", monzo),
+        ("name", name),
+        (
+            "approximation",
+            object(&[
+                ("steps", (steps as i32).to_string()),
+                ("cents", number(step_cents)),
+                ("error", number(step_cents - cents)),
+            ]),
+        ),
+    ];
+    fields.extend_from_slice(extra);
+    object(&fields)
+}
+
+/// Returns a JSON object of the given fields, whose values are already JSON.
+fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}:{}", string(name), value))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn number(n: f64) -> String {
+    if n.is_finite() {
+        n.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Returns the text as a JSON string, escaping quotes, backslashes and control
+/// characters.
+pub(crate) fn string(text: &str) -> String {
+    let mut json = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::LatticeDimension;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn diamond_document() {
+        let export = diamond(&Diamond::<i32>::new(vec![1, 3]), Edo::new(12));
+
+        assert_eq!(
+            export,
+            [
+                r#"{"schema":"rust-intonation/pitches/v1","kind":"diamond","description":"1 3 tonality diamond","#,
+                r#""source":{"limits":[1,3]},"edo":{"divisions":12,"period":"2/1"},"pitches":["#,
+                r#"{"position":[0,0],"ratio":"1/1","cents":0,"monzo":[0],"name":"unison","approximation":{"steps":0,"cents":0,"error":0},"identity":{"otonal":1,"utonal":1}},"#,
+                r#"{"position":[1,0],"ratio":"3/2","cents":701.9550008653874,"monzo":[-1,1],"name":"perfect fifth","approximation":{"steps":7,"cents":700,"error":-1.955000865387433},"identity":{"otonal":3,"utonal":1}},"#,
+                r#"{"position":[0,1],"ratio":"4/3","cents":498.0449991346125,"monzo":[2,-1],"name":"perfect fourth","approximation":{"steps":5,"cents":500,"error":1.9550008653874897},"identity":{"otonal":1,"utonal":3}},"#,
+                r#"{"position":[1,1],"ratio":"1/1","cents":0,"monzo":[0],"name":"unison","approximation":{"steps":0,"cents":0,"error":0},"identity":{"otonal":3,"utonal":3}}]}"#,
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn lattice_source() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), LatticeDimensionBounds::Infinite)
+                .with_offset(-1)
+                .with_label("fifths"),
+            LatticeDimension::new(Ratio::new(5, 4), LatticeDimensionBounds::LengthBounded(2)),
+            LatticeDimension::new(
+                Ratio::new(7, 4),
+                LatticeDimensionBounds::RangeBounded(-1, 1),
+            ),
+        ]);
+        let export = super::lattice(&lattice, &[0..=1, 0..=0, 0..=0], Edo::new(12));
+
+        assert!(export.contains(
+            r#""source":{"dimensions":[{"ratio":"3/2","bounds":null,"offset":-1,"label":"fifths"},{"ratio":"5/4","bounds":{"length":2},"offset":0,"label":null},{"ratio":"7/4","bounds":{"range":[-1,1]},"offset":0,"label":null}],"region":[[0,1],[0,0],[0,0]]}"#
+        ));
+        assert_eq!(export.matches(r#"{"position":"#).count(), 2);
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(string("a \"b\"\\\tc\u{1}"), r#""a \"b\"\\\tc\u0001""#);
+    }
+}
@@ -1,6 +1,7 @@
 use super::dimension_bounds::LatticeDimensionBounds;
-use crate::ratio::Ratio;
+use crate::{error::IntonationError, ratio::Ratio};
 
+use alloc::string::String;
 use num::traits::PrimInt;
 
 /// Models one dimension of a lattice, defining the [Ratio] by which to extend the dimension,
@@ -8,18 +9,100 @@ use num::traits::PrimInt;
 pub struct LatticeDimension<T: PrimInt> {
     pub ratio: Ratio<T>,
     pub bounds: LatticeDimensionBounds,
+    /// An optional name for the dimension, e.g. `fifths`, kept with its
+    /// [TOML description](crate::lattice::toml).
+    pub label: Option<String>,
+    /// The power of the ratio at index `0`, shifting the origin of the dimension, e.g.
+    /// `-2` to center it on the pitch two of its ratios below `1/1`. The bounds apply
+    /// to the indices around the shifted origin.
+    pub offset: i32,
 }
 
 impl<T: PrimInt> LatticeDimension<T> {
     pub fn new(ratio: Ratio<T>, bounds: LatticeDimensionBounds) -> Self {
-        Self { ratio, bounds }
+        Self {
+            ratio,
+            bounds,
+            label: None,
+            offset: 0,
+        }
+    }
+
+    /// Returns the dimension with the given label.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Returns the dimension with its origin shifted to the given power of its ratio.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// // Seven fifths, centered on the tonic two fifths down
+    /// let dim = LatticeDimension::new(Ratio::new(3, 2), RangeBounded(-3, 3)).with_offset(-2);
+    /// assert_eq!(dim.at(0), Ratio::new(16, 9));
+    /// assert_eq!(dim.at(2), Ratio::new(1, 1));
+    /// assert_eq!(dim.at(4), Ratio::new(256, 243));
+    /// ```
+    pub fn with_offset(mut self, offset: i32) -> Self {
+        self.offset = offset;
+        self
     }
 
     /// Indexes into the dimension, based on the [bounding rules][LatticeDimensionBounds] defined
-    /// for the dimension.
+    /// for the dimension, and then shifted by its [offset][LatticeDimension::offset].
     pub fn at(&self, index: i32) -> Ratio<T> {
-        let index = self.bounds.resolve_index(index);
-        self.ratio.pow(index)
+        self.ratio.pow(self.power(index))
+    }
+
+    /// Indexes into the dimension as [at][LatticeDimension::at] does, but returning an
+    /// [IntonationError] rather than panicking if the result overflows.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let dim = LatticeDimension::new(Ratio::new(3, 2), Infinite);
+    /// assert_eq!(dim.try_at(2), Ok(Ratio::new(9, 8)));
+    /// assert_eq!(dim.try_at(30), Err(IntonationError::Overflow));
+    /// ```
+    pub fn try_at(&self, index: i32) -> Result<Ratio<T>, IntonationError> {
+        self.ratio.try_pow(self.power(index))
+    }
+
+    /// Indexes into the dimension as [at][LatticeDimension::at] does, but without
+    /// reducing the result to within the octave, returning its numerator and denominator,
+    /// so that `3/2` at index 2 is `9/4`, rather than `9/8`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let dim = LatticeDimension::new(Ratio::new(3, 2), Infinite);
+    /// assert_eq!(dim.at_raw(2), (9, 4));
+    /// assert_eq!(dim.at_raw(-1), (2, 3));
+    /// ```
+    ///
+    /// Panics if the numerator or denominator overflows.
+    pub fn at_raw(&self, index: i32) -> (T, T) {
+        let index = self.power(index);
+        let (numer, denom) = if index < 0 {
+            (self.ratio.denom, self.ratio.numer)
+        } else {
+            (self.ratio.numer, self.ratio.denom)
+        };
+        let pow = |n: T| {
+            num::checked_pow(n, index.unsigned_abs() as usize)
+                .unwrap_or_else(|| panic!("{}", IntonationError::Overflow))
+        };
+        (pow(numer), pow(denom))
+    }
+
+    /// Returns the power the ratio is raised to at the given index.
+    fn power(&self, index: i32) -> i32 {
+        self.bounds.resolve_index(index) + self.offset
     }
 }
 
@@ -40,6 +123,26 @@ mod tests {
         assert_eq!(dim.at(-1), Ratio::new(4, 3));
     }
 
+    #[test]
+    fn at_raw_for_bounded_dimension() {
+        let dim = LatticeDimension::new(Ratio::new(5, 4), RangeBounded(-1, 2));
+
+        assert_eq!(dim.at_raw(0), (1, 1));
+        assert_eq!(dim.at_raw(2), (25, 16));
+        assert_eq!(dim.at_raw(3), (4, 5));
+        assert_eq!(dim.at(3), Ratio::new(8, 5));
+    }
+
+    #[test]
+    fn at_for_offset_dimension() {
+        let dim = LatticeDimension::new(Ratio::new(3, 2), LengthBounded(3)).with_offset(-1);
+
+        assert_eq!(dim.at(0), Ratio::new(4, 3));
+        assert_eq!(dim.at(2), Ratio::new(3, 2));
+        assert_eq!(dim.at(3), Ratio::new(4, 3));
+        assert_eq!(dim.at_raw(-1), (3, 2));
+    }
+
     #[test]
     fn at_for_length_bounded_dimension() {
         let dim = LatticeDimension::new(Ratio::new(3, 2), LengthBounded(2));
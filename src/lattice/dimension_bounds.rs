@@ -1,4 +1,4 @@
-use crate::math::sign_preserving_mod;
+use crate::numtheory::sign_preserving_mod;
 
 /// Models the different possibilities for a lattice dimension's bounding
 #[derive(Clone, Copy, Debug)]
@@ -0,0 +1,201 @@
+//! Coordinate adapters between the rectangular indices of a two-dimensional [Lattice]
+//! and the hexagonal and triangular grids of isomorphic keyboard layouts, such as the
+//! Lumatone's.
+//!
+//! The indices of the lattice are treated as _axial_ coordinates of a grid of hexagons
+//! with pointed tops: the first index runs along each row, and the second from one row
+//! to the next, half a cell further right with each row. Each of the six neighbors of a
+//! cell is then a single step along one dimension, or a step up one and down the other:
+//!
+//! ```text
+//!      [0,-1]  [1,-1]
+//! [-1,0]  [0,0]   [1,0]
+//!     [-1,1]   [0,1]
+//! ```
+//!
+//! [HexCoordinate] addresses the same cells by column and row, with alternate rows
+//! shifted, as keyboards number their keys, and [TriangularCoordinate] addresses them
+//! along three axes, as the points of a triangular grid such as the Tonnetz.
+//!
+//! [Lattice]: super::Lattice
+
+/// Which rows of a [HexCoordinate] grid are shifted half a cell to the right of the
+/// rows above and below them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowOffset {
+    /// Odd rows are shifted right.
+    Odd,
+    /// Even rows, including row 0, are shifted right.
+    Even,
+}
+
+/// A cell of a hexagonal grid with pointed tops, addressed by column and row, where
+/// alternate rows are shifted half a cell to the right, as given by a [RowOffset].
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+/// # use rust_intonation::lattice::geometry::{HexCoordinate, RowOffset};
+/// let lattice = Lattice::new(vec![
+///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+/// ]);
+/// // The key down and to the right of the origin, on an odd row, is a major third up
+/// let key = HexCoordinate::new(0, 1);
+/// assert_eq!(key.to_indices(RowOffset::Odd), [0, 1]);
+/// assert_eq!(lattice.at(&key.to_indices(RowOffset::Odd)), Ratio::new(5, 4));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HexCoordinate {
+    pub column: i32,
+    pub row: i32,
+}
+
+impl HexCoordinate {
+    /// Construct a new [HexCoordinate] from a column and a row.
+    pub fn new(column: i32, row: i32) -> Self {
+        Self { column, row }
+    }
+
+    /// Returns the lattice indices of the cell.
+    pub fn to_indices(self, offset: RowOffset) -> [i32; 2] {
+        [self.column - self.shift(offset), self.row]
+    }
+
+    /// Returns the cell at the given lattice indices.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::lattice::geometry::{HexCoordinate, RowOffset};
+    /// let cell = HexCoordinate::from_indices([-1, 3], RowOffset::Even);
+    /// assert_eq!(cell, HexCoordinate::new(1, 3));
+    /// assert_eq!(cell.to_indices(RowOffset::Even), [-1, 3]);
+    /// ```
+    pub fn from_indices(indices: [i32; 2], offset: RowOffset) -> Self {
+        let [q, row] = indices;
+        let cell = Self::new(0, row);
+        Self::new(q + cell.shift(offset), row)
+    }
+
+    /// Returns how many columns the cell's row is shifted from the axial coordinates:
+    /// half the number of rows from row 0, rounded toward the shifted rows.
+    fn shift(self, offset: RowOffset) -> i32 {
+        let parity = self.row & 1;
+        match offset {
+            RowOffset::Odd => (self.row - parity) / 2,
+            RowOffset::Even => (self.row + parity) / 2,
+        }
+    }
+}
+
+/// A point of a triangular grid, such as a Tonnetz, addressed by its position along
+/// three axes at 120° to one another, summing to zero. These are also the _cube_
+/// coordinates of the cells of a hexagonal grid.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::lattice::geometry::TriangularCoordinate;
+/// let point = TriangularCoordinate::new(2, -1, -1).unwrap();
+/// assert_eq!(point.to_indices(), [2, -1]);
+/// assert_eq!(TriangularCoordinate::from_indices([2, -1]), point);
+/// assert_eq!(TriangularCoordinate::new(1, 1, 1), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TriangularCoordinate {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl TriangularCoordinate {
+    /// Construct a new [TriangularCoordinate] from its position along each axis, or
+    /// `None` if they do not sum to zero.
+    pub fn new(x: i32, y: i32, z: i32) -> Option<Self> {
+        (x + y + z == 0).then_some(Self { x, y, z })
+    }
+
+    /// Returns the lattice indices of the point: its position along the first two axes.
+    pub fn to_indices(self) -> [i32; 2] {
+        [self.x, self.y]
+    }
+
+    /// Returns the point at the given lattice indices.
+    pub fn from_indices(indices: [i32; 2]) -> Self {
+        let [x, y] = indices;
+        Self { x, y, z: -x - y }
+    }
+
+    /// Returns the number of steps between two points, along the lines of the grid.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::lattice::geometry::TriangularCoordinate;
+    /// let origin = TriangularCoordinate::from_indices([0, 0]);
+    /// assert_eq!(origin.distance(TriangularCoordinate::from_indices([1, -1])), 1);
+    /// assert_eq!(origin.distance(TriangularCoordinate::from_indices([1, 1])), 2);
+    /// ```
+    pub fn distance(self, other: Self) -> u32 {
+        let steps = [self.x - other.x, self.y - other.y, self.z - other.z];
+        steps.iter().map(|s| s.unsigned_abs()).sum::<u32>() / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hex_round_trip() {
+        for offset in [RowOffset::Odd, RowOffset::Even] {
+            for row in -5..=5 {
+                for column in -5..=5 {
+                    let cell = HexCoordinate::new(column, row);
+                    assert_eq!(
+                        HexCoordinate::from_indices(cell.to_indices(offset), offset),
+                        cell
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hex_neighbors_are_one_step_apart() {
+        let neighbors = |cell: HexCoordinate, offset| {
+            // A shifted row's neighbors above and below are in its column and the next,
+            // and any other row's in the column before and its own
+            let shift = match (offset, cell.row & 1) {
+                (RowOffset::Odd, 1) | (RowOffset::Even, 0) => 0,
+                _ => -1,
+            };
+            [
+                (-1, 0),
+                (1, 0),
+                (shift, -1),
+                (shift + 1, -1),
+                (shift, 1),
+                (shift + 1, 1),
+            ]
+            .map(|(c, r)| HexCoordinate::new(cell.column + c, cell.row + r))
+        };
+        for offset in [RowOffset::Odd, RowOffset::Even] {
+            for cell in [HexCoordinate::new(2, -3), HexCoordinate::new(-1, 4)] {
+                let [q, r] = cell.to_indices(offset);
+                let origin = TriangularCoordinate::from_indices([q, r]);
+                let distances: Vec<u32> = neighbors(cell, offset)
+                    .iter()
+                    .map(|n| {
+                        origin.distance(TriangularCoordinate::from_indices(n.to_indices(offset)))
+                    })
+                    .collect();
+                assert_eq!(distances, [1; 6]);
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Queries of how a tonality [Diamond] embeds in a region of a [Lattice], and which
+//! ratios of the region lie in the diamond, to place a diamond within a larger gamut.
+use super::{region_indices, Lattice};
+use crate::{
+    diamond::{Diamond, Identity},
+    ratio::Ratio,
+};
+
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+use num::traits::PrimInt;
+
+/// Where a ratio lies in both a [Diamond] and a region of a [Lattice], as returned by
+/// [Lattice::locate_diamond] and [Diamond::locate_region].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placement {
+    /// The ratio.
+    pub ratio: Ratio<i32>,
+    /// The [identities][Diamond::identities] of every cell of the diamond holding the
+    /// ratio, in the order of [Diamond::identities].
+    pub identities: Vec<Identity>,
+    /// The indices of every point in the region holding the ratio, in the order of
+    /// [Lattice::region].
+    pub indices: Vec<Vec<i32>>,
+}
+
+impl Placement {
+    /// Returns whether the ratio lies in both the diamond and the region.
+    pub fn is_shared(&self) -> bool {
+        !self.identities.is_empty() && !self.indices.is_empty()
+    }
+}
+
+impl Lattice<i32> {
+    /// Returns where each distinct ratio of the diamond lies in the region of the
+    /// lattice spanned by the given range of indices along each dimension, as in
+    /// [Lattice::region], in the order the ratios first appear in [Diamond::ratios].
+    /// Ratios of the diamond outside the region have no indices.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// # use rust_intonation::diamond::{Diamond, Identity};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+    /// let placements = lattice.locate_diamond(&diamond, &[-1..=1, 0..=1]);
+    ///
+    /// let five_thirds = placements.iter().find(|p| p.ratio == Ratio::new(5, 3)).unwrap();
+    /// assert_eq!(five_thirds.identities, vec![Identity::new(5, 3)]);
+    /// assert_eq!(five_thirds.indices, vec![vec![-1, 1]]);
+    /// // 8/5 lies a major third below the region
+    /// let eight_fifths = placements.iter().find(|p| p.ratio == Ratio::new(8, 5)).unwrap();
+    /// assert!(!eight_fifths.is_shared());
+    /// ```
+    pub fn locate_diamond<T: PrimInt>(
+        &self,
+        diamond: &Diamond<T>,
+        ranges: &[RangeInclusive<i32>],
+    ) -> Vec<Placement> {
+        let cells = diamond_cells(diamond);
+        let points = self.region_points(ranges);
+        distinct(cells.iter().map(|(ratio, _)| *ratio))
+            .into_iter()
+            .map(|ratio| placement(ratio, &cells, &points))
+            .collect()
+    }
+
+    /// Returns whether every ratio of the diamond lies in the region of the lattice
+    /// spanned by the given range of indices along each dimension.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// # use rust_intonation::diamond::Diamond;
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+    /// assert!(!lattice.contains_diamond(&diamond, &[-1..=1, 0..=1]));
+    /// assert!(lattice.contains_diamond(&diamond, &[-1..=1, -1..=1]));
+    /// ```
+    pub fn contains_diamond<T: PrimInt>(
+        &self,
+        diamond: &Diamond<T>,
+        ranges: &[RangeInclusive<i32>],
+    ) -> bool {
+        self.locate_diamond(diamond, ranges)
+            .iter()
+            .all(Placement::is_shared)
+    }
+
+    /// Returns the indices of every point in the region, with its ratio.
+    fn region_points(&self, ranges: &[RangeInclusive<i32>]) -> Vec<(Vec<i32>, Ratio<i32>)> {
+        let indices = region_indices(ranges);
+        let ratios = self.walk(&indices);
+        indices.into_iter().zip(ratios).collect()
+    }
+}
+
+impl<T: PrimInt> Diamond<T> {
+    /// Returns where each distinct ratio in the region of the lattice spanned by the
+    /// given range of indices along each dimension lies in the diamond, in the order the
+    /// ratios first appear in [Lattice::region]. Ratios of the region outside the
+    /// diamond have no identities.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// # use rust_intonation::diamond::{Diamond, Identity};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+    /// let placements = diamond.locate_region(&lattice, &[0..=1, 1..=1]);
+    ///
+    /// assert_eq!(placements[0].ratio, Ratio::new(5, 4));
+    /// assert_eq!(placements[0].identities, vec![Identity::new(5, 1)]);
+    /// // 15/8 is not in the 5-limit diamond
+    /// assert_eq!(placements[1].ratio, Ratio::new(15, 8));
+    /// assert!(placements[1].identities.is_empty());
+    /// ```
+    pub fn locate_region(
+        &self,
+        lattice: &Lattice<i32>,
+        ranges: &[RangeInclusive<i32>],
+    ) -> Vec<Placement> {
+        let cells = diamond_cells(self);
+        let points = lattice.region_points(ranges);
+        distinct(points.iter().map(|(_, ratio)| *ratio))
+            .into_iter()
+            .map(|ratio| placement(ratio, &cells, &points))
+            .collect()
+    }
+
+    /// Returns whether every ratio in the region of the lattice spanned by the given
+    /// range of indices along each dimension lies in the diamond.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// # use rust_intonation::diamond::Diamond;
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let diamond: Diamond = Diamond::new(vec![1, 3, 5]);
+    /// assert!(diamond.contains_region(&lattice, &[-1..=1, 0..=0]));
+    /// assert!(!diamond.contains_region(&lattice, &[-1..=1, 0..=1]));
+    /// ```
+    pub fn contains_region(&self, lattice: &Lattice<i32>, ranges: &[RangeInclusive<i32>]) -> bool {
+        self.locate_region(lattice, ranges)
+            .iter()
+            .all(Placement::is_shared)
+    }
+}
+
+/// Returns the ratio of every cell of the diamond, with its identity.
+fn diamond_cells<T: PrimInt>(diamond: &Diamond<T>) -> Vec<(Ratio<i32>, Identity)> {
+    diamond
+        .ratios()
+        .iter()
+        .flatten()
+        .copied()
+        .zip(diamond.identities().into_iter().flatten())
+        .collect()
+}
+
+/// Returns the ratios in the order they first appear, without repeats.
+fn distinct(ratios: impl Iterator<Item = Ratio<i32>>) -> Vec<Ratio<i32>> {
+    let mut distinct = Vec::new();
+    for ratio in ratios {
+        if !distinct.contains(&ratio) {
+            distinct.push(ratio);
+        }
+    }
+    distinct
+}
+
+fn placement(
+    ratio: Ratio<i32>,
+    cells: &[(Ratio<i32>, Identity)],
+    points: &[(Vec<i32>, Ratio<i32>)],
+) -> Placement {
+    Placement {
+        ratio,
+        identities: cells
+            .iter()
+            .filter(|(r, _)| *r == ratio)
+            .map(|(_, identity)| *identity)
+            .collect(),
+        indices: points
+            .iter()
+            .filter(|(_, r)| *r == ratio)
+            .map(|(indices, _)| indices.clone())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{LatticeDimension, LatticeDimensionBounds::*};
+    use alloc::vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn repeated_ratios() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), LengthBounded(3)),
+            LatticeDimension::new(Ratio::new(5, 4), Infinite),
+        ]);
+        let diamond: Diamond = Diamond::new(vec![1, 3, 9]);
+        let placements = lattice.locate_diamond(&diamond, &[0..=3, 0..=0]);
+
+        assert_eq!(
+            placements[1],
+            Placement {
+                ratio: Ratio::new(3, 2),
+                identities: vec![Identity::new(3, 1), Identity::new(9, 3)],
+                indices: vec![vec![1, 0]],
+            }
+        );
+        // The dimension of fifths wraps around after three steps
+        assert_eq!(placements[0].indices, vec![vec![0, 0], vec![3, 0]]);
+        assert_eq!(placements[0].identities.len(), 3);
+    }
+
+    #[test]
+    fn symmetric() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), Infinite),
+            LatticeDimension::new(Ratio::new(7, 4), Infinite),
+        ]);
+        let diamond: Diamond = Diamond::new(vec![1, 3, 5, 7]);
+        let ranges = [-2..=2, -1..=1];
+        let shared = |placements: Vec<Placement>| {
+            let mut shared: Vec<Placement> = placements
+                .into_iter()
+                .filter(Placement::is_shared)
+                .collect();
+            shared.sort_by(|a, b| a.ratio.cents().total_cmp(&b.ratio.cents()));
+            shared
+        };
+
+        assert_eq!(
+            shared(lattice.locate_diamond(&diamond, &ranges)),
+            shared(diamond.locate_region(&lattice, &ranges))
+        );
+        assert!(!lattice.contains_diamond(&diamond, &ranges));
+        assert!(!diamond.contains_region(&lattice, &ranges));
+    }
+}
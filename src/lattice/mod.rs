@@ -2,11 +2,26 @@
 
 pub mod dimension;
 pub mod dimension_bounds;
+pub mod geometry;
+pub mod intersection;
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod toml;
+pub mod walk;
 
-use crate::ratio::Ratio;
+#[cfg(feature = "std")]
+pub use self::toml::LatticeTomlError;
+use crate::{error::IntonationError, numtheory::reduce, ratio::Ratio};
+#[cfg(feature = "audio")]
+use crate::{pitch::Frequency, play::Player};
 pub use dimension::LatticeDimension;
 pub use dimension_bounds::LatticeDimensionBounds;
+pub use intersection::Placement;
+pub use stats::RegionStats;
+pub use walk::{RandomWalk, WalkConstraints};
 
+use alloc::{vec, vec::Vec};
+use core::ops::RangeInclusive;
 use num::traits::PrimInt;
 
 /// Models an n-dimensional just intonation ratio lattice, constructed from a vector
@@ -21,15 +36,176 @@ impl<T: PrimInt> Lattice<T> {
         Self { dimensions }
     }
 
+    /// Returns the ratio at the given indices, one for each dimension in order.
+    ///
+    /// The ratio of each dimension is [raised to its index][LatticeDimension::at], and
+    /// the results are multiplied together in the order of the dimensions, starting from
+    /// `1/1`, with the product reduced to within the octave after each multiplication.
+    /// Reducing along the way gives the same ratio as reducing once at the end, so the
+    /// order only matters to whether an intermediate product overflows. Dimensions
+    /// without an index are left at `0`, and indices beyond the last dimension are
+    /// ignored.
+    ///
+    /// See [Lattice::at_raw] for the product without reducing it to within the octave.
     pub fn at(&self, indices: &[i32]) -> Ratio<T> {
+        self.try_at(indices).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns the ratio at the given indices, as [Lattice::at], but returning an
+    /// [IntonationError] rather than panicking if the result overflows.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// assert_eq!(lattice.try_at(&[1, 1]), Ok(Ratio::new(15, 8)));
+    /// assert_eq!(lattice.try_at(&[30, 0]), Err(IntonationError::Overflow));
+    /// ```
+    pub fn try_at(&self, indices: &[i32]) -> Result<Ratio<T>, IntonationError> {
+        self.dimensions.iter().zip(indices.iter()).try_fold(
+            Ratio::new(num::one(), num::one()),
+            |product, (dim, &index)| product.try_mul(dim.try_at(index)?),
+        )
+    }
+
+    /// Returns the numerator and denominator, in lowest terms, of the product of each
+    /// dimension [raised to its index][LatticeDimension::at_raw], without ever reducing
+    /// it to within the octave, so that it keeps the octave the product lies in.
+    ///
+    /// The dimensions are multiplied in the same order as in [Lattice::at], and the
+    /// result, reduced to within the octave, is the ratio [at][Lattice::at] the same
+    /// indices.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// // Two fifths and a third up, more than an octave and a half
+    /// assert_eq!(lattice.at_raw(&[2, 1]), (45, 16));
+    /// assert_eq!(lattice.at(&[2, 1]), Ratio::new(45, 32));
+    /// ```
+    ///
+    /// Panics if the numerator or denominator overflows.
+    pub fn at_raw(&self, indices: &[i32]) -> (T, T) {
         self.dimensions
             .iter()
             .zip(indices.iter())
-            .map(|(dim, &index)| dim.at(index))
-            .fold(Ratio::new(num::one(), num::one()), |r, acc| acc * r)
+            .map(|(dim, &index)| dim.at_raw(index))
+            .fold((num::one(), num::one()), |(numer, denom), (n, d)| {
+                let ((numer, d), (n, denom)) = (reduce(numer, d), reduce(n, denom));
+                let mul = |a: T, b: T| {
+                    a.checked_mul(&b)
+                        .unwrap_or_else(|| panic!("{}", IntonationError::Overflow))
+                };
+                (mul(numer, n), mul(denom, d))
+            })
+    }
+
+    /// Returns the ratio at every point in the region of the lattice spanned by the given
+    /// range of indices along each dimension, with the last dimension varying fastest.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// assert_eq!(
+    ///     lattice.region(&[0..=1, 0..=1]),
+    ///     vec![
+    ///         Ratio::new(1, 1),
+    ///         Ratio::new(5, 4),
+    ///         Ratio::new(3, 2),
+    ///         Ratio::new(15, 8)
+    ///     ]
+    /// );
+    /// ```
+    pub fn region(&self, ranges: &[RangeInclusive<i32>]) -> Vec<Ratio<T>> {
+        self.walk(&region_indices(ranges))
+    }
+
+    /// Returns the ratio at each set of indices along the given path through the
+    /// lattice, in order.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// assert_eq!(
+    ///     lattice.walk(&[vec![0, 0], vec![1, 0], vec![1, 1]]),
+    ///     vec![Ratio::new(1, 1), Ratio::new(3, 2), Ratio::new(15, 8)]
+    /// );
+    /// ```
+    pub fn walk(&self, path: &[Vec<i32>]) -> Vec<Ratio<T>> {
+        path.iter().map(|indices| self.at(indices)).collect()
+    }
+
+    /// Plays the ratio at each set of indices along the given path through the lattice,
+    /// one after another, above the root given in the player's
+    /// [PlaybackConfig](crate::play::PlaybackConfig).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// # use rust_intonation::play::Player;
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// // Up a fifth, a major third, and back down the fifth
+    /// lattice.play_walk(&[vec![0, 0], vec![1, 0], vec![1, 1], vec![0, 1]], &Player::default());
+    /// ```
+    #[cfg(feature = "audio")]
+    pub fn play_walk(&self, path: &[Vec<i32>], player: &Player) {
+        let root = Frequency(player.config.root);
+        let freqs: Vec<f32> = self
+            .walk(path)
+            .into_iter()
+            .map(|ratio| (root * ratio).0)
+            .collect();
+        player.play_sequence(&freqs);
+    }
+
+    /// Plays every ratio in the region of the lattice spanned by the given range of
+    /// indices along each dimension, one after another, in the order of
+    /// [region][Lattice::region].
+    #[cfg(feature = "audio")]
+    pub fn play_region(&self, ranges: &[RangeInclusive<i32>], player: &Player) {
+        self.play_walk(&region_indices(ranges), player);
     }
 }
 
+/// Returns every set of indices in the given ranges, with the last varying fastest.
+pub(crate) fn region_indices(ranges: &[RangeInclusive<i32>]) -> Vec<Vec<i32>> {
+    ranges.iter().fold(vec![vec![]], |indices, range| {
+        indices
+            .iter()
+            .flat_map(|index| {
+                range.clone().map(move |i| {
+                    let mut index = index.clone();
+                    index.push(i);
+                    index
+                })
+            })
+            .collect()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::dimension_bounds::LatticeDimensionBounds::*;
@@ -59,6 +235,21 @@ mod tests {
         assert_eq!(l.at(&[1, 1]), Ratio::new(15, 8));
     }
 
+    #[test]
+    fn at_raw_keeps_octaves() {
+        let l = Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), Infinite),
+            LatticeDimension::new(Ratio::new(5, 4), LengthBounded(3)),
+            LatticeDimension::new(Ratio::new(7, 4), Infinite),
+        ]);
+
+        assert_eq!(l.at_raw(&[]), (1, 1));
+        assert_eq!(l.at_raw(&[-2, 0, 0]), (4, 9));
+        assert_eq!(l.at_raw(&[1, 4, -1]), (15, 14));
+        assert_eq!(l.at_raw(&[3, 2, 1]), (4725, 512));
+        assert_eq!(l.at(&[3, 2, 1]), Ratio::new(4725, 4096));
+    }
+
     #[test]
     #[should_panic]
     fn default_i32_lattice_panics() {
@@ -71,6 +262,25 @@ mod tests {
         l.at(&[7, 7, 7]);
     }
 
+    #[test]
+    #[cfg(feature = "audio")]
+    fn play_region() {
+        use crate::play::{PlaybackConfig, CHANNELS, SAMPLE_RATE};
+
+        let l = Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), Infinite),
+            LatticeDimension::new(Ratio::new(5, 4), Infinite),
+        ]);
+        let player = Player::recorder(PlaybackConfig {
+            duration: 0.1,
+            ..PlaybackConfig::default()
+        });
+        l.play_region(&[-1..=1, 0..=1], &player);
+
+        let note = SAMPLE_RATE as usize / 10 * CHANNELS as usize;
+        assert_eq!(player.samples().len(), 6 * note);
+    }
+
     #[test]
     fn can_create_an_i64_lattice() {
         let l: Lattice<i64> = Lattice::new(vec![
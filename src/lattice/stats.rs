@@ -0,0 +1,112 @@
+//! Summary statistics of a region of a [Lattice], to judge whether it makes a usable
+//! gamut.
+use super::Lattice;
+use crate::ratio::Ratio;
+
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+use num::traits::PrimInt;
+
+/// Statistics of the pitch classes in a region of a [Lattice], as returned by
+/// [Lattice::region_stats].
+///
+/// Every ratio in a lattice lies within the octave, so each distinct ratio in the
+/// region is a distinct pitch class.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RegionStats<T: PrimInt> {
+    /// The number of distinct pitch classes in the region.
+    pub pitch_classes: usize,
+    /// The distance, in cents, from the lowest pitch class to the highest.
+    pub span: f64,
+    /// The [Tenney height][Ratio::tenney_height] of the simplest pitch class.
+    pub min_tenney_height: f64,
+    /// The [Tenney height][Ratio::tenney_height] of the most complex pitch class.
+    pub max_tenney_height: f64,
+    /// The smallest interval between any two pitch classes, taken around the octave,
+    /// or `None` if there is only one.
+    pub smallest_interval: Option<Ratio<T>>,
+}
+
+impl<T: PrimInt> Lattice<T> {
+    /// Returns statistics of the pitch classes in the region of the lattice spanned by
+    /// the given range of indices along each dimension, as in [Lattice::region], or
+    /// `None` if the region is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let stats = lattice.region_stats(&[-1..=1, 0..=1]).unwrap();
+    ///
+    /// assert_eq!(stats.pitch_classes, 6);
+    /// assert_eq!(stats.min_tenney_height, 0.);
+    /// assert_eq!(stats.max_tenney_height, 120f64.log2());
+    /// // From 5/4 up to 4/3
+    /// assert_eq!(stats.smallest_interval, Some(Ratio::new(16, 15)));
+    /// ```
+    pub fn region_stats(&self, ranges: &[RangeInclusive<i32>]) -> Option<RegionStats<T>> {
+        let mut ratios = self.region(ranges);
+        ratios.sort_by(|a, b| a.cents().total_cmp(&b.cents()));
+        ratios.dedup();
+        let (lowest, highest) = (*ratios.first()?, *ratios.last()?);
+
+        let heights: Vec<f64> = ratios.iter().map(|r| r.tenney_height()).collect();
+        let smallest_interval = ratios
+            .iter()
+            .zip(ratios.iter().cycle().skip(1))
+            .map(|(&a, &b)| b / a)
+            .min_by(|a, b| a.cents().total_cmp(&b.cents()))
+            .filter(|_| ratios.len() > 1);
+
+        Some(RegionStats {
+            pitch_classes: ratios.len(),
+            span: highest.cents() - lowest.cents(),
+            min_tenney_height: heights.iter().copied().fold(f64::INFINITY, f64::min),
+            max_tenney_height: heights.iter().copied().fold(0., f64::max),
+            smallest_interval,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{LatticeDimension, LatticeDimensionBounds::*};
+    use alloc::vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn pythagorean_region() {
+        let lattice = Lattice::new(vec![LatticeDimension::new(Ratio::new(3, 2), Infinite)]);
+        let stats = lattice.region_stats(&[-1..=5]).unwrap();
+
+        assert_eq!(stats.pitch_classes, 7);
+        assert_eq!(stats.span, Ratio::new(243, 128).cents());
+        assert_eq!(stats.max_tenney_height, (243f64 * 128.).log2());
+        // Around the octave, from 243/128 up to 2/1
+        assert_eq!(stats.smallest_interval, Some(Ratio::new(256, 243)));
+    }
+
+    #[test]
+    fn repeated_pitch_classes() {
+        let lattice = Lattice::new(vec![LatticeDimension::new(
+            Ratio::new(3, 2),
+            LengthBounded(2),
+        )]);
+        let stats = lattice.region_stats(&[0..=5]).unwrap();
+
+        assert_eq!(stats.pitch_classes, 2);
+        assert_eq!(stats.smallest_interval, Some(Ratio::new(4, 3)));
+        assert_eq!(
+            lattice.region_stats(&[0..=0]).unwrap().smallest_interval,
+            None
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = [1..=0];
+        assert_eq!(lattice.region_stats(&empty), None);
+    }
+}
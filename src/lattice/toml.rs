@@ -0,0 +1,308 @@
+//! Reading and writing [Lattices][Lattice] as TOML descriptions, so that lattices with
+//! many dimensions can be kept under version control and passed to the CLI as a file.
+//!
+//! A description has a `[[dimension]]` table for each dimension of the lattice, in
+//! order, holding its `ratio` as a string, its bounds as either a `length` or an
+//! inclusive `range` (or neither, if it is infinite), and an optional `offset` of its
+//! origin and `label`. It is read and written with the [toml](https://docs.rs/toml)
+//! crate, so requires the `std` feature.
+//!
+//! ```toml
+//! # A 7-limit lattice
+//! [[dimension]]
+//! ratio = "3/2"
+//! offset = -1
+//! label = "fifths"
+//!
+//! [[dimension]]
+//! ratio = "5/4"
+//! length = 3
+//!
+//! [[dimension]]
+//! ratio = "7/4"
+//! range = [-1, 1]
+//! label = "septimal"
+//! ```
+use super::{Lattice, LatticeDimension, LatticeDimensionBounds};
+use crate::ratio::Ratio;
+
+use core::fmt::{Display, Write};
+use num::traits::PrimInt;
+use std::path::Path;
+use thiserror::Error;
+use toml::{Table, Value};
+
+/// Describes why a TOML description of a lattice could not be read. Dimensions are
+/// numbered from 1, in the order of their `[[dimension]]` tables.
+#[derive(Debug, Error)]
+pub enum LatticeTomlError {
+    /// The file could not be read.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The description is not valid TOML.
+    #[error(transparent)]
+    Parse(#[from] toml::de::Error),
+    /// The description has a key other than the `[[dimension]]` tables.
+    #[error("unknown key `{key}`, expected `[[dimension]]` tables")]
+    InvalidTable { key: String },
+    /// A key is not one of those a dimension can have.
+    #[error("dimension {dimension}: unknown key `{key}`")]
+    UnknownKey { dimension: usize, key: String },
+    /// A value is not of the type its key expects.
+    #[error("dimension {dimension}: invalid value for `{key}`")]
+    InvalidValue { dimension: usize, key: String },
+    /// A dimension was given both a `length` and a `range`.
+    #[error("dimension {dimension}: a dimension can only have one `length` or `range`")]
+    DuplicateBounds { dimension: usize },
+    /// A dimension has no `ratio`.
+    #[error("dimension {dimension}: dimension has no `ratio`")]
+    MissingRatio { dimension: usize },
+}
+
+impl<T: PrimInt + Display> Lattice<T> {
+    /// Construct a new [Lattice] from its [TOML description](self).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, Ratio};
+    /// let text = r#"
+    /// [[dimension]]
+    /// ratio = "3/2"
+    /// label = "fifths"
+    ///
+    /// [[dimension]]
+    /// ratio = "5/4"
+    /// range = [-1, 1]
+    /// "#;
+    /// let lattice: Lattice<i32> = Lattice::from_toml(text).unwrap();
+    /// assert_eq!(lattice.dimensions[0].label.as_deref(), Some("fifths"));
+    /// assert_eq!(lattice.at(&[1, 2]), Ratio::new(6, 5));
+    /// ```
+    pub fn from_toml(text: &str) -> Result<Self, LatticeTomlError> {
+        let mut table: Table = text.parse()?;
+        let tables = match table.remove("dimension") {
+            Some(Value::Array(tables)) => tables,
+            Some(_) => {
+                return Err(LatticeTomlError::InvalidTable {
+                    key: "dimension".to_string(),
+                })
+            }
+            None => Vec::new(),
+        };
+        if let Some(key) = table.keys().next() {
+            return Err(LatticeTomlError::InvalidTable { key: key.clone() });
+        }
+        let dimensions = tables
+            .iter()
+            .enumerate()
+            .map(|(i, table)| match table {
+                Value::Table(table) => read_dimension(table, i + 1),
+                _ => Err(LatticeTomlError::InvalidTable {
+                    key: "dimension".to_string(),
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(dimensions))
+    }
+
+    /// Returns the [TOML description](self) of the lattice, which
+    /// [Lattice::from_toml] reads back.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite).with_label("fifths"),
+    ///     LatticeDimension::new(Ratio::new(5, 4), LengthBounded(3)),
+    /// ]);
+    /// assert_eq!(
+    ///     lattice.to_toml(),
+    ///     "[[dimension]]\nratio = \"3/2\"\nlabel = \"fifths\"\n\n[[dimension]]\nratio = \"5/4\"\nlength = 3\n"
+    /// );
+    /// ```
+    pub fn to_toml(&self) -> String {
+        let tables: Vec<String> = self
+            .dimensions
+            .iter()
+            .map(|dimension| {
+                let mut table = format!("[[dimension]]\nratio = \"{}\"\n", dimension.ratio);
+                match dimension.bounds {
+                    LatticeDimensionBounds::Infinite => {}
+                    LatticeDimensionBounds::LengthBounded(n) => {
+                        writeln!(table, "length = {}", n).unwrap()
+                    }
+                    LatticeDimensionBounds::RangeBounded(a, b) => {
+                        writeln!(table, "range = [{}, {}]", a, b).unwrap()
+                    }
+                }
+                if dimension.offset != 0 {
+                    writeln!(table, "offset = {}", dimension.offset).unwrap();
+                }
+                if let Some(label) = &dimension.label {
+                    writeln!(table, "label = {}", Value::from(label.as_str())).unwrap();
+                }
+                table
+            })
+            .collect();
+        tables.join("\n")
+    }
+
+    /// Read a lattice from the [TOML description](self) at the given path.
+    pub fn read_toml<P: AsRef<Path>>(path: P) -> Result<Self, LatticeTomlError> {
+        Self::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Write the [TOML description](self) of the lattice to the given path.
+    pub fn write_toml<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_toml())
+    }
+}
+
+/// Reads the dimension from its `[[dimension]]` table, the given number in order.
+fn read_dimension<T: PrimInt>(
+    table: &Table,
+    dimension: usize,
+) -> Result<LatticeDimension<T>, LatticeTomlError> {
+    let mut ratio = None;
+    let mut bounds = None;
+    let mut offset = 0;
+    let mut label = None;
+
+    for (key, value) in table {
+        let invalid = || LatticeTomlError::InvalidValue {
+            dimension,
+            key: key.clone(),
+        };
+        let integer = || {
+            let n = value.as_integer().ok_or_else(invalid)?;
+            i32::try_from(n).map_err(|_| invalid())
+        };
+        let bound = match key.as_str() {
+            "ratio" => {
+                let text = value.as_str().ok_or_else(invalid)?;
+                ratio = Some(parse_ratio(text).ok_or_else(invalid)?);
+                None
+            }
+            "length" => Some(LatticeDimensionBounds::LengthBounded(integer()?)),
+            "range" => Some(parse_range(value).ok_or_else(invalid)?),
+            "offset" => {
+                offset = integer()?;
+                None
+            }
+            "label" => {
+                label = Some(value.as_str().ok_or_else(invalid)?.to_string());
+                None
+            }
+            _ => {
+                return Err(LatticeTomlError::UnknownKey {
+                    dimension,
+                    key: key.clone(),
+                })
+            }
+        };
+        if let Some(bound) = bound {
+            if bounds.replace(bound).is_some() {
+                return Err(LatticeTomlError::DuplicateBounds { dimension });
+            }
+        }
+    }
+    Ok(LatticeDimension {
+        ratio: ratio.ok_or(LatticeTomlError::MissingRatio { dimension })?,
+        bounds: bounds.unwrap_or(LatticeDimensionBounds::Infinite),
+        label,
+        offset,
+    })
+}
+
+/// Parses a ratio of positive integers, `n/d`, or a whole number.
+fn parse_ratio<T: PrimInt>(text: &str) -> Option<Ratio<T>> {
+    let (numer, denom) = text.split_once('/').unwrap_or((text, "1"));
+    let parse = |n: &str| T::from_str_radix(n.trim(), 10).ok();
+    Ratio::try_new(parse(numer)?, parse(denom)?).ok()
+}
+
+/// Parses an inclusive range of indices written as an array, `[a, b]`.
+fn parse_range(value: &Value) -> Option<LatticeDimensionBounds> {
+    let index = |value: &Value| i32::try_from(value.as_integer()?).ok();
+    match value.as_array()?.as_slice() {
+        [a, b] => Some(LatticeDimensionBounds::RangeBounded(index(a)?, index(b)?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::LatticeDimensionBounds::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trip() {
+        let lattice: Lattice<i32> = Lattice::new(alloc::vec![
+            LatticeDimension::new(Ratio::new(3, 2), Infinite)
+                .with_label("fifths # \"pure\"\nor \\tempered"),
+            LatticeDimension::new(Ratio::new(5, 4), LengthBounded(-2)),
+            LatticeDimension::new(Ratio::new(7, 4), RangeBounded(-1, 1))
+                .with_offset(2)
+                .with_label("sevenths"),
+        ]);
+        let read = Lattice::<i32>::from_toml(&lattice.to_toml()).unwrap();
+
+        assert_eq!(read.to_toml(), lattice.to_toml());
+        assert_eq!(
+            read.dimensions[0].label.as_deref(),
+            Some("fifths # \"pure\"\nor \\tempered")
+        );
+        assert_eq!(read.at(&[1, 1, -2]), lattice.at(&[1, 1, -2]));
+    }
+
+    #[test]
+    fn comments_and_whole_numbers() {
+        let text = "# octaves and twelfths\n[[dimension]] # first\nratio = \"2\"\n\n[[ dimension ]]\nratio = \"3/1\" # twelfth\nlength = 2\n";
+        let lattice = Lattice::<i32>::from_toml(text).unwrap();
+
+        assert_eq!(lattice.at(&[1, 3]), Ratio::new(6, 1));
+        assert_eq!(lattice.dimensions[1].label, None);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| Lattice::<i32>::from_toml(text).err().unwrap();
+
+        assert!(matches!(
+            error("[[dimension]]\nratio \"3/2\""),
+            LatticeTomlError::Parse(_)
+        ));
+        assert_eq!(
+            error("ratio = \"3/2\"").to_string(),
+            "unknown key `ratio`, expected `[[dimension]]` tables"
+        );
+        assert_eq!(
+            error("[[dimension]]\nratio = \"3/2\"\n\n[[dimension]]\nratio = \"5/4\"\nbounds = 2")
+                .to_string(),
+            "dimension 2: unknown key `bounds`"
+        );
+        assert_eq!(
+            error("[[dimension]]\nratio = \"3/0\"").to_string(),
+            "dimension 1: invalid value for `ratio`"
+        );
+        assert_eq!(
+            error("[[dimension]]\nratio = \"3/2\"\nrange = [1]").to_string(),
+            "dimension 1: invalid value for `range`"
+        );
+        assert_eq!(
+            error("[[dimension]]\nratio = \"3/2\"\noffset = 3000000000").to_string(),
+            "dimension 1: invalid value for `offset`"
+        );
+        assert_eq!(
+            error("[[dimension]]\nratio = \"3/2\"\nlength = 2\nrange = [0, 1]").to_string(),
+            "dimension 1: a dimension can only have one `length` or `range`"
+        );
+        assert_eq!(
+            error("[[dimension]]\nratio = \"3/2\"\n[[dimension]]\nlength = 2").to_string(),
+            "dimension 2: dimension has no `ratio`"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Seeded random walks through a [Lattice], for generative composition.
+use super::Lattice;
+use crate::ratio::Ratio;
+
+use alloc::{vec, vec::Vec};
+use num::traits::PrimInt;
+
+/// The rules a [random walk][Lattice::random_walk] follows in choosing each step.
+///
+/// By default, every step to a neighboring point is allowed.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::lattice::WalkConstraints;
+/// let constraints = WalkConstraints::default().without_backtracking().within_radius(2);
+/// assert!(constraints.no_backtracking);
+/// assert_eq!(constraints.max_radius, Some(2));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WalkConstraints {
+    /// Never step straight back to the point the walk has just left.
+    pub no_backtracking: bool,
+    /// Never step further than this taxicab distance, the sum of the absolute indices,
+    /// from the origin.
+    pub max_radius: Option<u32>,
+}
+
+impl WalkConstraints {
+    /// Returns the constraints, forbidding steps straight back to the previous point.
+    pub fn without_backtracking(mut self) -> Self {
+        self.no_backtracking = true;
+        self
+    }
+
+    /// Returns the constraints, keeping the walk within the given taxicab distance of
+    /// the origin.
+    pub fn within_radius(mut self, radius: u32) -> Self {
+        self.max_radius = Some(radius);
+        self
+    }
+
+    /// Returns whether a step to `to` is allowed, where the walk was at `previous` before
+    /// the point it is stepping from.
+    fn allow(&self, previous: Option<&Vec<i32>>, to: &[i32]) -> bool {
+        let backtracks = self.no_backtracking && previous.is_some_and(|p| p == to);
+        let radius: u32 = to.iter().map(|i| i.unsigned_abs()).sum();
+        !backtracks && self.max_radius.is_none_or(|max| radius <= max)
+    }
+}
+
+/// A path through a [Lattice] taken by [Lattice::random_walk], with the ratio at each
+/// point along it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RandomWalk<T: PrimInt> {
+    /// The indices of each point along the walk, starting at the origin.
+    pub path: Vec<Vec<i32>>,
+    /// The ratio at each point along the walk, in order.
+    pub ratios: Vec<Ratio<T>>,
+}
+
+impl<T: PrimInt> Lattice<T> {
+    /// Returns a random walk of up to `length` steps from the origin, each moving one
+    /// index up or down along a single dimension, chosen among those the constraints
+    /// allow.
+    ///
+    /// The walk is the same for the same seed, so a generated walk can be reproduced.
+    /// It ends early if the constraints leave no step to take, e.g. when a walk without
+    /// backtracking reaches its radius along a single dimension, from where the only
+    /// step inward is back.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio};
+    /// # use rust_intonation::lattice::WalkConstraints;
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let constraints = WalkConstraints::default().within_radius(2);
+    /// let walk = lattice.random_walk(7, 16, constraints);
+    ///
+    /// assert_eq!(walk.path.len(), 17);
+    /// assert_eq!(walk.ratios[0], Ratio::new(1, 1));
+    /// assert_eq!(walk, lattice.random_walk(7, 16, constraints));
+    /// ```
+    pub fn random_walk(
+        &self,
+        seed: u64,
+        length: usize,
+        constraints: WalkConstraints,
+    ) -> RandomWalk<T> {
+        let mut rng = SplitMix64(seed);
+        let mut path = vec![vec![0; self.dimensions.len()]];
+
+        for _ in 0..length {
+            let current = &path[path.len() - 1];
+            let previous = path.len().checked_sub(2).map(|i| &path[i]);
+            let steps: Vec<Vec<i32>> = (0..current.len())
+                .flat_map(|dimension| [-1, 1].map(|step| (dimension, step)))
+                .map(|(dimension, step)| {
+                    let mut next = current.clone();
+                    next[dimension] += step;
+                    next
+                })
+                .filter(|next| constraints.allow(previous, next))
+                .collect();
+            if steps.is_empty() {
+                break;
+            }
+            let step = steps[(rng.next() % steps.len() as u64) as usize].clone();
+            path.push(step);
+        }
+
+        let ratios = self.walk(&path);
+        RandomWalk { path, ratios }
+    }
+}
+
+/// The SplitMix64 generator, a small, fast source of pseudorandom numbers that gives
+/// the same sequence on every platform.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{LatticeDimension, LatticeDimensionBounds::*};
+    use pretty_assertions::assert_eq;
+
+    fn lattice() -> Lattice<i32> {
+        Lattice::new(vec![
+            LatticeDimension::new(Ratio::new(3, 2), Infinite),
+            LatticeDimension::new(Ratio::new(5, 4), Infinite),
+            LatticeDimension::new(Ratio::new(7, 4), Infinite),
+        ])
+    }
+
+    #[test]
+    fn seeded() {
+        let walk = lattice().random_walk(1, 32, WalkConstraints::default());
+
+        assert_eq!(
+            walk,
+            lattice().random_walk(1, 32, WalkConstraints::default())
+        );
+        assert_ne!(
+            walk,
+            lattice().random_walk(2, 32, WalkConstraints::default())
+        );
+        assert_eq!(walk.ratios, lattice().walk(&walk.path));
+        for pair in walk.path.windows(2) {
+            let distance: i32 = pair[0]
+                .iter()
+                .zip(&pair[1])
+                .map(|(a, b)| (a - b).abs())
+                .sum();
+            assert_eq!(distance, 1);
+        }
+    }
+
+    #[test]
+    fn constrained() {
+        let constraints = WalkConstraints::default()
+            .without_backtracking()
+            .within_radius(2);
+        let walk = lattice().random_walk(3, 64, constraints);
+
+        assert!(walk.path.len() > 2);
+        for (i, point) in walk.path.iter().enumerate() {
+            assert!(point.iter().map(|i| i.abs()).sum::<i32>() <= 2);
+            if i >= 2 {
+                assert_ne!(point, &walk.path[i - 2]);
+            }
+        }
+    }
+
+    #[test]
+    fn ends_when_no_step_is_allowed() {
+        let lattice = Lattice::new(vec![LatticeDimension::new(Ratio::new(3, 2), Infinite)]);
+        let constraints = WalkConstraints::default()
+            .without_backtracking()
+            .within_radius(1);
+        let walk = lattice.random_walk(0, 8, constraints);
+
+        assert_eq!(walk.path.len(), 2);
+        assert_eq!(
+            lattice.random_walk(0, 8, WalkConstraints::default().within_radius(0)),
+            RandomWalk {
+                path: vec![vec![0]],
+                ratios: vec![Ratio::new(1, 1)]
+            }
+        );
+    }
+}
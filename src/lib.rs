@@ -1,14 +1,62 @@
-#![doc = include_str!("../README.md")]
+// The README's examples play audio, so it is only included, and its examples tested, with
+// the `audio` feature
+#![cfg_attr(feature = "audio", doc = include_str!("../README.md"))]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod chord;
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "std")]
+pub mod comma_pump;
+#[cfg(feature = "std")]
+pub mod consonance;
+#[cfg(feature = "std")]
+pub mod cps;
 pub mod diamond;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod farey;
+#[cfg(feature = "std")]
+pub mod harmonic_entropy;
+#[cfg(feature = "std")]
+pub mod html;
 pub mod interval;
+#[cfg(feature = "std")]
+pub mod json;
 pub mod lattice;
 mod math;
+#[cfg(feature = "std")]
+pub mod midi;
+pub mod monzo;
+#[cfg(feature = "std")]
+pub mod notation;
+pub mod numtheory;
+#[cfg(feature = "std")]
+pub mod pitch;
+#[cfg(feature = "std")]
 pub mod play;
 pub mod ratio;
+#[cfg(feature = "audio")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod scale;
+#[cfg(feature = "std")]
 pub mod temperaments;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+#[cfg(feature = "std")]
+pub use chord::Chord;
+pub use error::IntonationError;
 pub use lattice::{Lattice, LatticeDimension, LatticeDimensionBounds};
+pub use monzo::Monzo;
+#[cfg(feature = "std")]
+pub use pitch::{Frequency, Pitch};
 pub use ratio::Ratio;
+#[cfg(feature = "std")]
+pub use scale::Scale;
+#[cfg(feature = "std")]
 pub use temperaments::Edo;
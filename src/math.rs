@@ -1,48 +1,173 @@
-use num::{traits::PrimInt, zero};
+use crate::numtheory::{ilog2, reduce};
+use num::traits::PrimInt;
 
-pub(crate) fn normalize_pair<T: PrimInt>(a: T, b: T) -> (T, T) {
-    let f: f32 = num::cast(a / b).unwrap();
-    let two: T = num::cast(2i32).unwrap();
+/// Reduces `a / b` to lowest terms and multiplies `a` or `b` by the power of two that
+/// brings it within the octave [1, 2), or returns `None` if that would overflow.
+///
+/// The power is found in one step from the terms' integer logarithms: shifting the
+/// smaller term to the bit length of the larger can't overflow, and leaves the ratio
+/// at most one octave out.
+pub(crate) fn try_normalize<T: PrimInt>(a: T, b: T) -> Option<(T, T)> {
+    let (a, b) = reduce(a, b);
+    let (a_log, b_log) = (ilog2(a), ilog2(b));
+    let (a, b) = if a_log <= b_log {
+        let a = a << (b_log - a_log) as usize;
+        if a < b {
+            (a.checked_mul(&num::cast(2).unwrap())?, b)
+        } else {
+            (a, b)
+        }
+    } else {
+        let b = b << (a_log - b_log) as usize;
+        if a < b {
+            (a, b >> 1)
+        } else {
+            (a, b)
+        }
+    };
+    Some(reduce(a, b))
+}
 
-    match f {
-        f if f < 1. => normalize_pair(a * two, b),
-        f if f >= 2. => normalize_pair(a, b * two),
-        _ => (a, b),
+/// Returns the greatest common divisor of `a` and `b`, as
+/// [gcd][crate::numtheory::gcd] does, in `const` contexts.
+pub(crate) const fn const_gcd(a: u128, b: u128) -> u128 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        (a, b) = (b, a % b);
     }
+    a
 }
 
-pub(crate) fn reduce<T: PrimInt>(a: T, b: T) -> (T, T) {
-    let g = gcd(a, b);
-    (a / g, b / g)
+/// Normalizes `a / b` as [try_normalize] does, in `const` contexts.
+pub(crate) const fn const_normalize(a: u128, b: u128) -> Option<(u128, u128)> {
+    let gcd = const_gcd(a, b);
+    let (a, b) = (a / gcd, b / gcd);
+    let (a_log, b_log) = (a.ilog2(), b.ilog2());
+    let (a, b) = if a_log <= b_log {
+        let a = a << (b_log - a_log);
+        if a < b {
+            let Some(a) = a.checked_mul(2) else {
+                return None;
+            };
+            (a, b)
+        } else {
+            (a, b)
+        }
+    } else {
+        let b = b << (a_log - b_log);
+        if a < b {
+            (a, b >> 1)
+        } else {
+            (a, b)
+        }
+    };
+    let gcd = const_gcd(a, b);
+    Some((a / gcd, b / gcd))
 }
 
-pub(crate) fn sign_preserving_mod(a: i32, b: i32) -> i32 {
-    (a % b + b) % b
+/// Reduces an integer matrix to Hermite normal form using unimodular row operations,
+/// dropping any rows that reduce to zero.
+#[cfg(feature = "std")]
+pub(crate) fn hermite_normal_form(rows: Vec<Vec<i64>>) -> Vec<Vec<i64>> {
+    let mut m = rows;
+    let ncols = m.first().map_or(0, |row| row.len());
+    let mut pivot = 0;
+
+    for col in 0..ncols {
+        if pivot >= m.len() {
+            break;
+        }
+
+        while let Some(min) = (pivot..m.len())
+            .filter(|&r| m[r][col] != 0)
+            .min_by_key(|&r| m[r][col].abs())
+        {
+            m.swap(pivot, min);
+            for r in pivot + 1..m.len() {
+                let q = m[r][col] / m[pivot][col];
+                subtract_row(&mut m, r, pivot, q);
+            }
+            if (pivot + 1..m.len()).all(|r| m[r][col] == 0) {
+                break;
+            }
+        }
+
+        if m[pivot][col] == 0 {
+            continue;
+        }
+        if m[pivot][col] < 0 {
+            m[pivot].iter_mut().for_each(|e| *e = -*e);
+        }
+        for r in 0..pivot {
+            let q = m[r][col].div_euclid(m[pivot][col]);
+            subtract_row(&mut m, r, pivot, q);
+        }
+        pivot += 1;
+    }
+
+    m.retain(|row| row.iter().any(|&e| e != 0));
+    m
 }
 
-fn gcd<T: PrimInt>(a: T, b: T) -> T {
-    let mut a = a;
-    let mut b = b;
-    while a % b > zero() {
-        let t = a % b;
-        a = b;
-        b = t;
+#[cfg(feature = "std")]
+fn subtract_row(m: &mut [Vec<i64>], target: usize, source: usize, times: i64) {
+    for c in 0..m[target].len() {
+        m[target][c] -= times * m[source][c];
     }
-    b
 }
 
-pub(crate) fn greatest_prime_factor<T: PrimInt>(a: T) -> T {
+/// Returns a basis, in Hermite normal form, for the integer vectors `v` of length `ncols`
+/// such that every row of the given matrix dotted with `v` is zero.
+#[cfg(feature = "std")]
+pub(crate) fn integer_kernel(rows: &[Vec<i64>], ncols: usize) -> Vec<Vec<i64>> {
+    let k = rows.len();
+    let augmented: Vec<Vec<i64>> = (0..ncols)
+        .map(|i| {
+            let mut row: Vec<i64> = rows.iter().map(|r| r[i]).collect();
+            row.extend((0..ncols).map(|j| if i == j { 1 } else { 0 }));
+            row
+        })
+        .collect();
+
+    let kernel = hermite_normal_form(augmented)
+        .into_iter()
+        .filter(|row| row[..k].iter().all(|&e| e == 0))
+        .map(|row| row[k..].to_vec())
+        .collect();
+
+    hermite_normal_form(kernel)
+}
+
+/// Solves the square linear system `a * x = b` by Gaussian elimination with partial pivoting.
+#[cfg(feature = "std")]
+pub(crate) fn solve(a: Vec<Vec<f64>>, b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
     let mut a = a;
-    let mut p = num::cast(2).unwrap();
+    let mut b = b;
 
-    while a > num::one() {
-        if a % p == num::zero() {
-            a = a / p;
-        } else {
-            p = p + num::one();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap();
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let pivot_row = a[col].clone();
+        for r in col + 1..n {
+            let factor = a[r][col] / pivot_row[col];
+            for (value, pivot_value) in a[r].iter_mut().zip(pivot_row.iter()).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            b[r] -= factor * b[col];
         }
     }
-    p
+
+    let mut x = vec![0.; n];
+    for r in (0..n).rev() {
+        let sum: f64 = (r + 1..n).map(|c| a[r][c] * x[c]).sum();
+        x[r] = (b[r] - sum) / a[r][r];
+    }
+    x
 }
 
 #[cfg(test)]
@@ -51,9 +176,62 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[test]
-    fn test_greatest_prime_factor() {
-        assert_eq!(greatest_prime_factor(5), 5);
-        assert_eq!(greatest_prime_factor(15), 5);
-        assert_eq!(greatest_prime_factor(14), 7);
+    fn test_try_normalize() {
+        assert_eq!(try_normalize(1, 3), Some((4, 3)));
+        assert_eq!(try_normalize(9, 2), Some((9, 8)));
+        assert_eq!(try_normalize(3, 4), Some((3, 2)));
+        assert_eq!(try_normalize(1, i32::MAX), None);
+    }
+
+    #[test]
+    fn const_normalize_matches_try_normalize() {
+        for (a, b) in [(1, 3), (9, 2), (3, 4), (1 << 30, 3 << 29), (81, 80)] {
+            let expected = try_normalize(a, b).map(|(a, b)| (a as u128, b as u128));
+            assert_eq!(const_normalize(a as u128, b as u128), expected);
+        }
+        assert_eq!(const_normalize(1, u128::MAX), None);
+    }
+
+    #[test]
+    fn try_normalize_reduces_before_shifting() {
+        // Doubling 2^30 before reducing 2^30 / (3 * 2^29) to 2/3 would overflow
+        assert_eq!(try_normalize(1 << 30, 3 << 29), Some((4, 3)));
+        assert_eq!(try_normalize(i32::MAX, 1), Some((i32::MAX, 1 << 30)));
+        assert_eq!(try_normalize(1u128, 1 << 120), Some((1, 1)));
+        assert_eq!(try_normalize(3u128 << 100, 1), Some((3, 2)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hermite_normal_form() {
+        assert_eq!(
+            hermite_normal_form(vec![vec![12, 19, 28], vec![7, 11, 16]]),
+            vec![vec![1, 0, -4], vec![0, 1, 4]]
+        );
+        assert_eq!(
+            hermite_normal_form(vec![vec![2, 4], vec![1, 2]]),
+            vec![vec![1, 2]]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_integer_kernel() {
+        assert_eq!(
+            integer_kernel(&[vec![-4, 4, -1]], 3),
+            vec![vec![1, 0, -4], vec![0, 1, 4]]
+        );
+        assert_eq!(
+            integer_kernel(&[vec![-4, 4, -1], vec![7, 0, -3]], 3),
+            vec![vec![12, 19, 28]]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_solve() {
+        let x = solve(vec![vec![2., 1.], vec![1., 3.]], vec![3., 5.]);
+        assert!((x[0] - 0.8).abs() < 1e-9);
+        assert!((x[1] - 1.4).abs() < 1e-9);
     }
 }
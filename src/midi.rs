@@ -0,0 +1,353 @@
+//! Export of JI material to Standard MIDI Files, with each pitch realized as the
+//! nearest 12 EDO note plus a pitch bend.
+//!
+//! Since pitch bend applies to a whole MIDI channel, each note sounding at the same
+//! time is given its own channel.
+//!
+//! Synths that support the MIDI Tuning Standard can instead be retuned directly with
+//! a [TuningTable].
+//!
+//! With the `mpe` feature, the same pitches can be sent live to a MIDI output port
+//! using [MpeOutput].
+#[cfg(feature = "mpe")]
+mod mpe;
+mod mts;
+
+#[cfg(feature = "mpe")]
+pub use mpe::MpeOutput;
+pub use mts::TuningTable;
+
+use crate::ratio::Ratio;
+use num::traits::PrimInt;
+use std::path::Path;
+
+/// The MIDI note number of middle C (C4).
+pub const MIDDLE_C_KEY: u8 = 60;
+
+/// The value of an unbent 14-bit MIDI pitch bend.
+pub const BEND_CENTER: u16 = 8192;
+
+/// The channels notes are assigned to, in order, skipping channel 10, which is
+/// reserved for percussion.
+const VOICE_CHANNELS: [u8; 15] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 11, 12, 13, 14, 15];
+
+/// Models a pitch as a MIDI note number and a 14-bit pitch bend.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MidiPitch {
+    pub key: u8,
+    pub bend: u16,
+}
+
+impl MidiPitch {
+    /// Find the MIDI note and pitch bend for a pitch the given number of cents above (or
+    /// below) middle C, for a synth with the given pitch bend range in semitones.
+    ///
+    /// ## Example
+    ///
+    /// A JI major third above middle C is an E, bent down by about 14 cents
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, midi::MidiPitch};
+    /// let pitch = MidiPitch::from_cents(Ratio::new(5, 4).cents(), 2);
+    /// assert_eq!(pitch.key, 64);
+    /// assert_eq!(pitch.bend, 7631);
+    /// ```
+    ///
+    /// Panics if the bend range is 0.
+    pub fn from_cents(cents: f64, bend_range: u8) -> Self {
+        assert!(bend_range > 0, "the pitch bend range must be at least 1");
+        let semitones = (cents / 100.).round();
+        let key = (MIDDLE_C_KEY as f64 + semitones).clamp(0., 127.) as u8;
+        let offset = cents - 100. * (key as f64 - MIDDLE_C_KEY as f64);
+        let bend = BEND_CENTER as f64 + offset / (100. * bend_range as f64) * BEND_CENTER as f64;
+
+        Self {
+            key,
+            bend: bend.round().clamp(0., 16383.) as u16,
+        }
+    }
+
+    /// Returns the number of cents the bent note is above (or below) middle C, for a
+    /// synth with the given pitch bend range in semitones. The inverse of
+    /// [MidiPitch::from_cents].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::midi::MidiPitch;
+    /// assert_eq!(MidiPitch { key: 64, bend: 4096 }.cents(2), 300.);
+    /// ```
+    ///
+    /// Panics if the bend range is 0.
+    pub fn cents(&self, bend_range: u8) -> f64 {
+        assert!(bend_range > 0, "the pitch bend range must be at least 1");
+        let offset = (self.bend as f64 - BEND_CENTER as f64) / BEND_CENTER as f64;
+        100. * (self.key as f64 - MIDDLE_C_KEY as f64) + offset * 100. * bend_range as f64
+    }
+}
+
+/// Builds a Standard MIDI File from a sequence of steps, each made up of one or more
+/// pitches that sound together.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use rust_intonation::{Ratio, midi::MidiExport};
+/// let mut export = MidiExport::default();
+/// export.add_sequence(&[Ratio::new(1, 1), Ratio::new(9, 8), Ratio::new(5, 4)]);
+/// export.add_chord(&[Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+/// export.write("example.mid").unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiExport {
+    /// The pitch, in cents above middle C, that ratios are placed above.
+    pub root: f64,
+    /// The pitch bend range, in semitones, of the synth the file is played on.
+    pub bend_range: u8,
+    /// The number of ticks in a quarter note.
+    pub ticks_per_beat: u16,
+    /// The length, in ticks, of each step.
+    pub note_length: u32,
+    /// The velocity each note is played with.
+    pub velocity: u8,
+    steps: Vec<Vec<f64>>,
+}
+
+impl Default for MidiExport {
+    /// Places ratios above middle C, with quarter notes at 480 ticks per beat, for a
+    /// synth with a pitch bend range of 2 semitones.
+    fn default() -> Self {
+        Self {
+            root: 0.,
+            bend_range: 2,
+            ticks_per_beat: 480,
+            note_length: 480,
+            velocity: 100,
+            steps: vec![],
+        }
+    }
+}
+
+impl MidiExport {
+    /// Add a step of pitches, given in cents above the root, that sound together.
+    ///
+    /// Panics if more than 15 pitches are given, as there are not enough MIDI channels
+    /// to bend each one separately.
+    pub fn add_cents(&mut self, cents: &[f64]) {
+        assert!(
+            cents.len() <= VOICE_CHANNELS.len(),
+            "at most {} notes can sound together",
+            VOICE_CHANNELS.len()
+        );
+        self.steps.push(cents.to_vec());
+    }
+
+    /// Add each of the given ratios, one after another, e.g. a scale, a row of a
+    /// [Diamond](crate::diamond::Diamond), or a walk through a [Lattice](crate::Lattice).
+    pub fn add_sequence<T: PrimInt>(&mut self, ratios: &[Ratio<T>]) {
+        for ratio in ratios {
+            self.add_cents(&[ratio.cents()]);
+        }
+    }
+
+    /// Add the given ratios as a single step, sounding together.
+    pub fn add_chord<T: PrimInt>(&mut self, ratios: &[Ratio<T>]) {
+        let cents: Vec<f64> = ratios.iter().map(|r| r.cents()).collect();
+        self.add_cents(&cents);
+    }
+
+    /// Returns the contents of a format 0 Standard MIDI File playing each step in turn.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let voices = self.steps.iter().map(|step| step.len()).max().unwrap_or(0);
+        let mut track = Track::default();
+
+        for &channel in VOICE_CHANNELS.iter().take(voices) {
+            for message in bend_range_messages(channel, self.bend_range) {
+                track.event(0, &message);
+            }
+        }
+
+        for step in &self.steps {
+            let pitches: Vec<MidiPitch> = step
+                .iter()
+                .map(|cents| MidiPitch::from_cents(self.root + cents, self.bend_range))
+                .collect();
+
+            for (pitch, &channel) in pitches.iter().zip(VOICE_CHANNELS.iter()) {
+                track.event(0, &pitch_bend(channel, pitch.bend));
+                track.event(0, &note_on(channel, pitch.key, self.velocity));
+            }
+            for (i, (pitch, &channel)) in pitches.iter().zip(VOICE_CHANNELS.iter()).enumerate() {
+                let delta = if i == 0 { self.note_length } else { 0 };
+                track.event(delta, &note_off(channel, pitch.key));
+            }
+        }
+        track.event(0, &[0xFF, 0x2F, 0x00]);
+
+        let mut bytes = b"MThd".to_vec();
+        bytes.extend(6u32.to_be_bytes());
+        bytes.extend(0u16.to_be_bytes());
+        bytes.extend(1u16.to_be_bytes());
+        bytes.extend(self.ticks_per_beat.to_be_bytes());
+        bytes.extend(b"MTrk");
+        bytes.extend((track.0.len() as u32).to_be_bytes());
+        bytes.extend(track.0);
+        bytes
+    }
+
+    /// Write the Standard MIDI File to the given path.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+}
+
+/// Returns the messages setting the pitch bend range of the given channel, in
+/// semitones, with RPN 0.
+fn bend_range_messages(channel: u8, semitones: u8) -> [[u8; 3]; 6] {
+    rpn_messages(channel, 0, semitones)
+}
+
+/// Returns the messages setting the given registered parameter of the given channel,
+/// followed by the null RPN, so later data entry messages are ignored.
+fn rpn_messages(channel: u8, parameter: u8, value: u8) -> [[u8; 3]; 6] {
+    [
+        (101, 0),
+        (100, parameter),
+        (6, value),
+        (38, 0),
+        (101, 127),
+        (100, 127),
+    ]
+    .map(|(controller, value)| [0xB0 | channel, controller, value])
+}
+
+fn pitch_bend(channel: u8, bend: u16) -> [u8; 3] {
+    [0xE0 | channel, (bend & 0x7F) as u8, (bend >> 7) as u8]
+}
+
+fn note_on(channel: u8, key: u8, velocity: u8) -> [u8; 3] {
+    [0x90 | channel, key, velocity]
+}
+
+fn note_off(channel: u8, key: u8) -> [u8; 3] {
+    [0x80 | channel, key, 0]
+}
+
+/// The bytes of the events of a MIDI track.
+#[derive(Default)]
+struct Track(Vec<u8>);
+
+impl Track {
+    fn event(&mut self, delta: u32, data: &[u8]) {
+        self.0.extend(variable_length_quantity(delta));
+        self.0.extend(data);
+    }
+}
+
+/// Encodes a number as a MIDI variable-length quantity, seven bits per byte, most
+/// significant first, with the high bit set on all but the last byte.
+fn variable_length_quantity(value: u32) -> Vec<u8> {
+    let mut bytes = vec![(value & 0x7F) as u8];
+    let mut value = value >> 7;
+    while value > 0 {
+        bytes.insert(0, (value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn midi_pitch() {
+        assert_eq!(
+            MidiPitch::from_cents(0., 2),
+            MidiPitch {
+                key: 60,
+                bend: 8192
+            }
+        );
+        assert_eq!(
+            MidiPitch::from_cents(Ratio::new(3, 2).cents(), 2),
+            MidiPitch {
+                key: 67,
+                bend: 8272
+            }
+        );
+        assert_eq!(
+            MidiPitch::from_cents(-1200., 2),
+            MidiPitch {
+                key: 48,
+                bend: 8192
+            }
+        );
+        assert_eq!(MidiPitch::from_cents(40., 1).bend, 11469);
+    }
+
+    #[test]
+    #[should_panic(expected = "the pitch bend range must be at least 1")]
+    fn midi_pitch_without_a_bend_range() {
+        MidiPitch::from_cents(0., 0);
+    }
+
+    #[test]
+    fn variable_length_quantities() {
+        assert_eq!(variable_length_quantity(0), vec![0x00]);
+        assert_eq!(variable_length_quantity(0x7F), vec![0x7F]);
+        assert_eq!(variable_length_quantity(0x80), vec![0x81, 0x00]);
+        assert_eq!(variable_length_quantity(480), vec![0x83, 0x60]);
+        assert_eq!(
+            variable_length_quantity(0x0FFFFFFF),
+            vec![0xFF, 0xFF, 0xFF, 0x7F]
+        );
+    }
+
+    #[test]
+    fn to_bytes() {
+        let mut export = MidiExport::default();
+        export.add_sequence(&[Ratio::new(1, 1)]);
+        let bytes = export.to_bytes();
+
+        assert_eq!(&bytes[0..4], b"MThd");
+        assert_eq!(&bytes[12..14], &480u16.to_be_bytes());
+        assert_eq!(&bytes[14..18], b"MTrk");
+
+        let track = &bytes[22..];
+        assert_eq!(
+            track.len() as u32,
+            u32::from_be_bytes(bytes[18..22].try_into().unwrap())
+        );
+        // Six RPN messages, a pitch bend, note on, note off, and end of track
+        assert_eq!(track.len(), 6 * 4 + 4 + 4 + 5 + 4);
+        assert_eq!(
+            &track[24..],
+            &[
+                0x00, 0xE0, 0x00, 0x40, 0x00, 0x90, 60, 100, 0x83, 0x60, 0x80, 60, 0, 0x00, 0xFF,
+                0x2F, 0x00,
+            ]
+        );
+    }
+
+    #[test]
+    fn chords_use_a_channel_per_note() {
+        let mut export = MidiExport::default();
+        export.add_chord(&[Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let bytes = export.to_bytes();
+
+        let note_ons: Vec<u8> = bytes
+            .windows(2)
+            .filter(|w| w[0] & 0xF0 == 0x90 && w[1] >= 60 && w[1] <= 67)
+            .map(|w| w[0] & 0x0F)
+            .collect();
+        assert_eq!(note_ons, vec![0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 15 notes")]
+    fn too_many_voices() {
+        MidiExport::default().add_cents(&[0.; 16]);
+    }
+}
@@ -0,0 +1,201 @@
+use super::{bend_range_messages, note_off, note_on, pitch_bend, rpn_messages, MidiPitch};
+use crate::ratio::Ratio;
+use midir::{MidiOutput, MidiOutputConnection};
+use num::traits::PrimInt;
+use std::error::Error;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// The name the output announces itself with to the system's MIDI driver.
+const CLIENT_NAME: &str = "rust-intonation";
+
+/// The channel that configures the MPE zone, rather than playing notes.
+const MANAGER_CHANNEL: u8 = 0;
+
+/// The channels notes are played on, one note per channel.
+const MEMBER_CHANNELS: [u8; 15] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+/// Sends JI pitches live to a MIDI output port as an MPE lower zone, with every note
+/// sounding at the same time played on its own member channel with its own pitch bend.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use rust_intonation::{Ratio, midi::MpeOutput};
+/// let mut output = MpeOutput::connect(None, 48).unwrap();
+/// output.play_sequence(&[Ratio::new(1, 1), Ratio::new(5, 4)]);
+/// output.play_chord(&[Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+/// ```
+pub struct MpeOutput {
+    connection: MidiOutputConnection,
+    bend_range: u8,
+    /// The pitch, in cents above middle C, that ratios are placed above.
+    pub root: f64,
+    /// The length, in seconds, of each note.
+    pub duration: f32,
+    /// The silence, in seconds, between successive notes.
+    pub gap: f32,
+    /// The velocity each note is played with.
+    pub velocity: u8,
+}
+
+impl MpeOutput {
+    /// Returns the names of the available MIDI output ports.
+    pub fn ports() -> Result<Vec<String>, Box<dyn Error>> {
+        let output = MidiOutput::new(CLIENT_NAME)?;
+        let names = output
+            .ports()
+            .iter()
+            .map(|port| output.port_name(port))
+            .collect::<Result<_, _>>()?;
+        Ok(names)
+    }
+
+    /// Connect to the first MIDI output port whose name contains the given string, or
+    /// to the first port if none is given, and configure it as an MPE lower zone with
+    /// the given pitch bend range, in semitones, on each member channel.
+    pub fn connect(port: Option<&str>, bend_range: u8) -> Result<Self, Box<dyn Error>> {
+        let output = MidiOutput::new(CLIENT_NAME)?;
+        let port = output
+            .ports()
+            .into_iter()
+            .find(|p| match port {
+                Some(name) => output
+                    .port_name(p)
+                    .is_ok_and(|port_name| port_name.contains(name)),
+                None => true,
+            })
+            .ok_or("no matching MIDI output port")?;
+        let connection = output.connect(&port, CLIENT_NAME)?;
+
+        let mut mpe = Self {
+            connection,
+            bend_range,
+            root: 0.,
+            duration: 1.,
+            gap: 0.25,
+            velocity: 100,
+        };
+        for message in zone_messages(bend_range) {
+            mpe.send(&message);
+        }
+        Ok(mpe)
+    }
+
+    /// Play the given pitches, in cents above the root, together, and wait for them to
+    /// finish.
+    ///
+    /// Panics if more than 15 pitches are given, as there are not enough member
+    /// channels to bend each one separately.
+    pub fn play_cents(&mut self, cents: &[f64]) {
+        assert!(
+            cents.len() <= MEMBER_CHANNELS.len(),
+            "at most {} notes can sound together",
+            MEMBER_CHANNELS.len()
+        );
+        let pitches: Vec<MidiPitch> = cents
+            .iter()
+            .map(|cents| MidiPitch::from_cents(self.root + cents, self.bend_range))
+            .collect();
+        let (on, off) = step_messages(&pitches, self.velocity);
+
+        for message in on {
+            self.send(&message);
+        }
+        sleep(Duration::from_secs_f32(self.duration));
+        for message in off {
+            self.send(&message);
+        }
+        sleep(Duration::from_secs_f32(self.gap));
+    }
+
+    /// Play each of the given ratios, one after another.
+    pub fn play_sequence<T: PrimInt>(&mut self, ratios: &[Ratio<T>]) {
+        for ratio in ratios {
+            self.play_cents(&[ratio.cents()]);
+        }
+    }
+
+    /// Play the given ratios together.
+    pub fn play_chord<T: PrimInt>(&mut self, ratios: &[Ratio<T>]) {
+        let cents: Vec<f64> = ratios.iter().map(|r| r.cents()).collect();
+        self.play_cents(&cents);
+    }
+
+    fn send(&mut self, message: &[u8]) {
+        self.connection.send(message).unwrap();
+    }
+}
+
+/// Returns the messages setting up a lower zone using every member channel, with
+/// the given pitch bend range, in semitones, on each member channel.
+///
+/// The MPE Configuration Message (RPN 6) resets the bend range of the members to
+/// 48 semitones, so their range is set after it.
+fn zone_messages(bend_range: u8) -> Vec<[u8; 3]> {
+    let mut messages = rpn_messages(MANAGER_CHANNEL, 6, MEMBER_CHANNELS.len() as u8).to_vec();
+    for &channel in &MEMBER_CHANNELS {
+        messages.extend(bend_range_messages(channel, bend_range));
+    }
+    messages
+}
+
+/// Returns the messages that start and stop the given pitches, each on its own member
+/// channel.
+fn step_messages(pitches: &[MidiPitch], velocity: u8) -> (Vec<[u8; 3]>, Vec<[u8; 3]>) {
+    let channels = pitches.iter().zip(MEMBER_CHANNELS.iter());
+    let on = channels
+        .clone()
+        .flat_map(|(pitch, &channel)| {
+            [
+                pitch_bend(channel, pitch.bend),
+                note_on(channel, pitch.key, velocity),
+            ]
+        })
+        .collect();
+    let off = channels
+        .map(|(pitch, &channel)| note_off(channel, pitch.key))
+        .collect();
+    (on, off)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn zone_configuration() {
+        let messages = zone_messages(48);
+
+        assert_eq!(messages.len(), 6 * 16);
+        assert_eq!(
+            &messages[..3],
+            &[[0xB0, 101, 0], [0xB0, 100, 6], [0xB0, 6, 15]]
+        );
+        assert_eq!(
+            &messages[6..9],
+            &[[0xB1, 101, 0], [0xB1, 100, 0], [0xB1, 6, 48]]
+        );
+    }
+
+    #[test]
+    fn notes_play_on_member_channels() {
+        let pitches = [
+            MidiPitch::from_cents(0., 48),
+            MidiPitch::from_cents(Ratio::new(5, 4).cents(), 48),
+        ];
+        let (on, off) = step_messages(&pitches, 100);
+
+        assert_eq!(
+            on,
+            vec![
+                [0xE1, 0x00, 0x40],
+                [0x91, 60, 100],
+                pitch_bend(2, pitches[1].bend),
+                [0x92, 64, 100]
+            ]
+        );
+        assert_eq!(off, vec![[0x81, 60, 0], [0x82, 64, 0]]);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    play::MIDDLE_C,
+    ratio::Ratio,
+    scale::{KeyboardMapping, Scale},
+    Edo,
+};
+use num::traits::PrimInt;
+use std::path::Path;
+
+/// The sysex device ID that addresses every device.
+const ALL_DEVICES: u8 = 0x7F;
+
+/// The number of MIDI keys.
+const KEYS: usize = 128;
+
+/// The largest pitch, in cents above key 0, that MTS can express. `7F 7F 7F` is
+/// reserved to mean "no change", so the highest tuning is one step below it.
+const MAX_CENTS: f64 = 12700. + 100. * 16382. / 16384.;
+
+/// Models a tuning of all 128 MIDI keys, in cents above the 12 EDO pitch of key 0
+/// (C-1), that can be sent to a synth with the MIDI Tuning Standard (MTS).
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, midi::TuningTable};
+/// let table = TuningTable::from_ratios(&[Ratio::new(1, 1), Ratio::new(5, 4)], 60);
+/// assert_eq!(table.cents[60], 6000.);
+/// assert!((table.cents[61] - 6386.314).abs() < 0.001);
+/// assert_eq!(table.cents[62], 7200.);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TuningTable {
+    pub cents: [f64; KEYS],
+}
+
+impl TuningTable {
+    /// Create a tuning that repeats the given scale, in cents above its root, at every
+    /// period, with its root on the given key, tuned as in 12 EDO. An empty scale
+    /// leaves every key in its 12 EDO tuning.
+    pub fn from_scale(scale: &[f64], period: f64, root_key: u8) -> Self {
+        if scale.is_empty() {
+            return Self {
+                cents: std::array::from_fn(|key| 100. * key as f64),
+            };
+        }
+        let mut scale = scale.to_vec();
+        scale.sort_by(f64::total_cmp);
+        let size = scale.len() as i32;
+
+        let cents = std::array::from_fn(|key| {
+            let steps = key as i32 - root_key as i32;
+            let (periods, degree) = (steps.div_euclid(size), steps.rem_euclid(size));
+            100. * root_key as f64 + periods as f64 * period + scale[degree as usize]
+        });
+        Self { cents }
+    }
+
+    /// Create a tuning that repeats the given ratios at every octave, with `1/1` on the
+    /// given key, tuned as in 12 EDO.
+    pub fn from_ratios<T: PrimInt>(ratios: &[Ratio<T>], root_key: u8) -> Self {
+        let scale: Vec<f64> = ratios.iter().map(|r| r.cents()).collect();
+        Self::from_scale(&scale, 1200., root_key)
+    }
+
+    /// Create a tuning that maps each step of the EDO to successive keys, with the root
+    /// on the given key, tuned as in 12 EDO.
+    pub fn from_edo(edo: Edo, root_key: u8) -> Self {
+        let scale: Vec<f64> = (0..edo.divisions)
+            .map(|step| step as f64 * edo.step_size())
+            .collect();
+        Self::from_scale(&scale, edo.period_cents(), root_key)
+    }
+
+    /// Create a tuning that places the given scale on the keyboard, and tunes it, as
+    /// described by the given [KeyboardMapping]. Keys the mapping leaves out keep their
+    /// 12 EDO tuning.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Scale, midi::TuningTable, scale::KeyboardMapping};
+    /// let mapping = KeyboardMapping::linear(60, 60, 256.);
+    /// let table = TuningTable::from_mapping(&Scale::from_edo(Edo::new(19)), &mapping);
+    /// assert!((table.cents[79] - 7162.368).abs() < 0.001);
+    /// ```
+    pub fn from_mapping<T: PrimInt>(scale: &Scale<T>, mapping: &KeyboardMapping) -> Self {
+        let key_zero = MIDDLE_C / 32.;
+        let cents = std::array::from_fn(|key| match mapping.frequency(scale, key as u8) {
+            Some(freq) => 1200. * (freq / key_zero).log2(),
+            None => 100. * key as f64,
+        });
+        Self { cents }
+    }
+
+    /// Returns a non-real-time MTS bulk tuning dump, retuning every key of the given
+    /// tuning program, with the given name, which is truncated or padded to 16
+    /// characters.
+    ///
+    /// Pitches outside the range MTS can express are clamped into it.
+    pub fn bulk_dump(&self, program: u8, name: &str) -> Vec<u8> {
+        let mut name: Vec<u8> = name.bytes().filter(u8::is_ascii).take(16).collect();
+        name.resize(16, b' ');
+
+        let mut message = vec![0x7E, ALL_DEVICES, 0x08, 0x01, program & 0x7F];
+        message.extend(name.iter().map(|b| b & 0x7F));
+        for cents in self.cents {
+            message.extend(frequency_data(cents));
+        }
+        let checksum = message.iter().fold(0, |sum, b| sum ^ b) & 0x7F;
+
+        let mut bytes = vec![0xF0];
+        bytes.extend(message);
+        bytes.extend([checksum, 0xF7]);
+        bytes
+    }
+
+    /// Returns a non-real-time MTS scale/octave tuning message, retuning each of the 12
+    /// pitch classes on every channel by the offset, from 12 EDO, of the keys from
+    /// middle C up to the B above it.
+    ///
+    /// This is understood by more synths than [TuningTable::bulk_dump], but can only
+    /// express a tuning with 12 notes to the octave, with each offset rounded to the
+    /// nearest cent and clamped between -64 and 63 cents.
+    pub fn octave_dump(&self) -> Vec<u8> {
+        let mut bytes = vec![0xF0, 0x7E, ALL_DEVICES, 0x08, 0x08, 0x03, 0x7F, 0x7F];
+        for key in 60..72 {
+            let offset = (self.cents[key] - 100. * key as f64)
+                .round()
+                .clamp(-64., 63.);
+            bytes.push((offset + 64.) as u8);
+        }
+        bytes.push(0xF7);
+        bytes
+    }
+
+    /// Write the [bulk dump][TuningTable::bulk_dump] of the tuning to a `.syx` file at
+    /// the given path.
+    pub fn write_bulk_dump<P: AsRef<Path>>(
+        &self,
+        path: P,
+        program: u8,
+        name: &str,
+    ) -> std::io::Result<()> {
+        std::fs::write(path, self.bulk_dump(program, name))
+    }
+}
+
+/// Encodes a pitch, in cents above key 0, as MTS frequency data: the 12 EDO key at or
+/// below the pitch, and the 14-bit fraction of a semitone above that key.
+fn frequency_data(cents: f64) -> [u8; 3] {
+    let semitones = cents.clamp(0., MAX_CENTS) / 100.;
+    let mut key = semitones.floor() as u16;
+    let mut fraction = ((semitones - key as f64) * 16384.).round() as u16;
+    if fraction == 16384 {
+        key += 1;
+        fraction = 0;
+    }
+    [key as u8, (fraction >> 7) as u8, (fraction & 0x7F) as u8]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn frequency_data_encoding() {
+        assert_eq!(frequency_data(6000.), [60, 0, 0]);
+        assert_eq!(frequency_data(6050.), [60, 0x40, 0]);
+        assert_eq!(frequency_data(6099.9999), [61, 0, 0]);
+        assert_eq!(frequency_data(-100.), [0, 0, 0]);
+        assert_eq!(frequency_data(20000.), [0x7F, 0x7F, 0x7E]);
+    }
+
+    #[test]
+    fn from_edo() {
+        let table = TuningTable::from_edo(Edo::new(12), 60);
+        assert!((0..128).all(|key| (table.cents[key] - 100. * key as f64).abs() < 1e-9));
+
+        let table = TuningTable::from_edo(Edo::new(24), 60);
+        assert_eq!(table.cents[58], 5900.);
+        assert_eq!(table.cents[61], 6050.);
+        assert_eq!(table.cents[84], 7200.);
+    }
+
+    #[test]
+    fn from_empty_scale() {
+        let table = TuningTable::from_scale(&[], 1200., 60);
+        assert_eq!(table, TuningTable::from_edo(Edo::new(12), 60));
+    }
+
+    #[test]
+    fn bulk_dump() {
+        let table = TuningTable::from_edo(Edo::new(12), 0);
+        let bytes = table.bulk_dump(3, "12 EDO");
+
+        assert_eq!(bytes.len(), 408);
+        assert_eq!(&bytes[..6], &[0xF0, 0x7E, 0x7F, 0x08, 0x01, 3]);
+        assert_eq!(&bytes[6..22], b"12 EDO          ");
+        assert_eq!(&bytes[22 + 3 * 69..22 + 3 * 70], &[69, 0, 0]);
+        assert_eq!(bytes[407], 0xF7);
+
+        let checksum = bytes[1..406].iter().fold(0, |sum, b| sum ^ b) & 0x7F;
+        assert_eq!(bytes[406], checksum);
+    }
+
+    #[test]
+    fn octave_dump() {
+        let bytes = TuningTable::from_edo(Edo::new(12), 60).octave_dump();
+        assert_eq!(bytes.len(), 21);
+        assert_eq!(&bytes[8..20], &[64; 12]);
+
+        let mut table = TuningTable::from_edo(Edo::new(12), 60);
+        table.cents[64] = Ratio::new(5, 4).cents() + 6000.;
+        assert_eq!(table.octave_dump()[8 + 4], 64 - 14);
+    }
+}
@@ -0,0 +1,140 @@
+//! Provides [Monzos][Monzo], the prime-exponent vector representation of a JI interval.
+
+use crate::{numtheory::primes_up_to, ratio::Ratio};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+// The test harness links std, whose inherent float methods take precedence
+#[cfg(not(any(feature = "std", test)))]
+use num::traits::Float;
+use num::traits::PrimInt;
+
+/// Models a JI interval as a vector of exponents of consecutive primes, starting with 2.
+///
+/// ## Example
+///
+/// `81/80` is `3^4 / (2^4 * 5)`, so its monzo is `[-4 4 -1>`
+///
+/// ```rust
+/// # use rust_intonation::{monzo::Monzo, ratio::Ratio};
+/// let m = Monzo::from(Ratio::new(81, 80));
+/// assert_eq!(m.exponents, vec![-4, 4, -1]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monzo {
+    pub exponents: Vec<i32>,
+}
+
+impl Monzo {
+    /// Construct a new [Monzo] from a vector of prime exponents.
+    pub fn new(exponents: Vec<i32>) -> Self {
+        Self { exponents }
+    }
+
+    /// Returns the primes that each exponent of the monzo applies to.
+    pub fn primes(&self) -> Vec<u32> {
+        primes_for_len(self.exponents.len())
+    }
+
+    /// Returns the size of the interval described by the monzo, in cents.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::monzo::Monzo;
+    /// let m = Monzo::new(vec![-1, 1]);
+    /// assert!((m.cents() - 701.955).abs() < 0.001);
+    /// ```
+    pub fn cents(&self) -> f64 {
+        self.primes()
+            .iter()
+            .zip(self.exponents.iter())
+            .map(|(&p, &e)| 1200. * e as f64 * (p as f64).log2())
+            .sum()
+    }
+}
+
+impl<T: PrimInt> From<Ratio<T>> for Monzo {
+    fn from(value: Ratio<T>) -> Self {
+        // Only the primes up to the largest factor need listing, in the ratio's own
+        // integer type, so ratios of large integers with small factors convert quickly
+        let factors = value.factors();
+        let two = num::cast(2).unwrap();
+        let largest = factors.last().map_or(two, |(p, _)| *p);
+        let exponents = primes_up_to(largest)
+            .iter()
+            .map(|&p| {
+                factors
+                    .iter()
+                    .find(|(q, _)| *q == p)
+                    .map_or(0, |(_, exp)| *exp)
+            })
+            .collect();
+
+        Self { exponents }
+    }
+}
+
+impl Display for Monzo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let exponents = self
+            .exponents
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "[{}>", exponents)
+    }
+}
+
+pub(crate) fn primes_for_len(len: usize) -> Vec<u32> {
+    let mut limit = 2;
+    let mut primes = primes_up_to(limit);
+    while primes.len() < len {
+        limit *= 2;
+        primes = primes_up_to(limit);
+    }
+    primes.truncate(len);
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn from_ratio() {
+        assert_eq!(Monzo::from(Ratio::new(1, 1)).exponents, vec![0]);
+        assert_eq!(Monzo::from(Ratio::new(3, 2)).exponents, vec![-1, 1]);
+        assert_eq!(Monzo::from(Ratio::new(5, 4)).exponents, vec![-2, 0, 1]);
+        assert_eq!(Monzo::from(Ratio::new(7, 6)).exponents, vec![-1, -1, 0, 1]);
+    }
+
+    #[test]
+    fn from_large_ratio() {
+        let ratio = Ratio::<i64>::new(3i64.pow(31), 1 << 49);
+        assert_eq!(Monzo::from(ratio).exponents, vec![-49, 31]);
+    }
+
+    #[test]
+    fn primes() {
+        let m = Monzo::new(vec![0, 0, 0, 0, 1]);
+        assert_eq!(m.primes(), vec![2, 3, 5, 7, 11]);
+    }
+
+    #[test]
+    fn cents() {
+        let m = Monzo::from(Ratio::new(81, 80));
+        assert!((m.cents() - 21.506).abs() < 0.001);
+    }
+
+    #[test]
+    fn display() {
+        let m = Monzo::from(Ratio::new(81, 80));
+        assert_eq!(m.to_string(), "[-4 4 -1>");
+    }
+}
@@ -0,0 +1,4 @@
+//! Export of JI material to music notation, with each pitch written as its nearest
+//! 12 EDO note, marked with its deviation from that note in cents.
+pub mod lilypond;
+pub mod musicxml;
@@ -0,0 +1,136 @@
+//! [LilyPond](https://lilypond.org) snippets of scales and chords, with the deviation of
+//! each note from 12 EDO written above it.
+//!
+//! Each function returns a music expression, which can be placed in a score, or passed
+//! to [document] to be engraved on its own. Deviations are written as text rather than
+//! HEJI accidentals, which would need a font LilyPond does not ship with.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use rust_intonation::{Chord, notation::lilypond, pitch::Note};
+//! let chord = Chord::from_harmonics(&[4, 5, 6, 7]);
+//! assert_eq!(
+//!     lilypond::chord(&chord, Note::MIDDLE_C),
+//!     r#"{ <c' e' g' ais'>1^\markup \column { "−31¢" "+2¢" "−14¢" "+0¢" } }"#
+//! );
+//! ```
+use crate::{
+    chord::Chord,
+    pitch::{format_deviation, Note},
+    scale::Scale,
+};
+use num::traits::PrimInt;
+
+/// The LilyPond name of each pitch class, spelled with sharps.
+const PITCH_NAMES: [&str; 12] = [
+    "c", "cis", "d", "dis", "e", "f", "fis", "g", "gis", "a", "ais", "b",
+];
+
+/// The version of LilyPond the snippets are written for.
+const VERSION: &str = "2.24.0";
+
+/// Returns the degrees of the scale, as quarter notes ascending from the given root up
+/// to the period, with the deviation of each above it.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, Scale, notation::lilypond, pitch::Note};
+/// let scale = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+/// assert_eq!(
+///     lilypond::scale(&scale, "A3".parse().unwrap()),
+///     r#"{ a4^"+0¢" cis'4^"−14¢" e'4^"+2¢" a'4^"+0¢" }"#
+/// );
+/// ```
+pub fn scale<T: PrimInt>(scale: &Scale<T>, root: Note) -> String {
+    let root_cents = 100. * root.semitones() as f64;
+    let notes: Vec<String> = (0..=scale.len() as i32)
+        .map(|degree| {
+            let (note, deviation) = Note::nearest(root_cents + scale.cents_at(degree));
+            format!("{}4^\"{}\"", pitch(note), format_deviation(deviation))
+        })
+        .collect();
+    format!("{{ {} }}", notes.join(" "))
+}
+
+/// Returns the chord, above the given root, as a whole note, with the deviation of each
+/// of its notes stacked above it, from the highest note down.
+pub fn chord<T: PrimInt>(chord: &Chord<T>, root: Note) -> String {
+    let root_cents = 100. * root.semitones() as f64;
+    let mut cents: Vec<f64> = chord
+        .ratios
+        .iter()
+        .map(|ratio| root_cents + ratio.cents())
+        .collect();
+    cents.sort_by(f64::total_cmp);
+    let notes: Vec<(Note, f64)> = cents.into_iter().map(Note::nearest).collect();
+
+    let pitches: Vec<String> = notes.iter().map(|(note, _)| pitch(*note)).collect();
+    let deviations: Vec<String> = notes
+        .iter()
+        .rev()
+        .map(|(_, deviation)| format!("\"{}\"", format_deviation(*deviation)))
+        .collect();
+    format!(
+        "{{ <{}>1^\\markup \\column {{ {} }} }}",
+        pitches.join(" "),
+        deviations.join(" ")
+    )
+}
+
+/// Returns a LilyPond file engraving the given music expression on its own.
+pub fn document(music: &str) -> String {
+    format!("\\version \"{}\"\n\n{}\n", VERSION, music)
+}
+
+/// Returns the note's LilyPond name in absolute pitch, in which `c'` is middle C.
+fn pitch(note: Note) -> String {
+    let marks = note.octave - 3;
+    let mark = if marks < 0 { "," } else { "'" };
+    format!(
+        "{}{}",
+        PITCH_NAMES[note.pitch_class as usize],
+        mark.repeat(marks.unsigned_abs() as usize)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ratio::Ratio, Edo};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn pitches_across_octaves() {
+        assert_eq!(pitch(Note::MIDDLE_C), "c'");
+        assert_eq!(pitch("F#3".parse().unwrap()), "fis");
+        assert_eq!(pitch("Bb1".parse().unwrap()), "ais,,");
+        assert_eq!(pitch("D6".parse().unwrap()), "d'''");
+    }
+
+    #[test]
+    fn tempered_scale() {
+        let edo = Scale::<i32>::from_edo(Edo::new(5));
+
+        assert_eq!(
+            scale(&edo, Note::MIDDLE_C),
+            r#"{ c'4^"+0¢" d'4^"+40¢" f'4^"−20¢" g'4^"+20¢" ais'4^"−40¢" c''4^"+0¢" }"#
+        );
+    }
+
+    #[test]
+    fn chord_sorts_its_notes() {
+        let fifth = Chord::new(vec![Ratio::new(3, 2), Ratio::new(1, 1)]);
+
+        assert_eq!(
+            chord(&fifth, Note::MIDDLE_C),
+            r#"{ <c' g'>1^\markup \column { "+2¢" "+0¢" } }"#
+        );
+    }
+
+    #[test]
+    fn document_has_a_version() {
+        assert_eq!(document("{ c'1 }"), "\\version \"2.24.0\"\n\n{ c'1 }\n");
+    }
+}
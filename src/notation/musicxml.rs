@@ -0,0 +1,208 @@
+//! [MusicXML](https://www.w3.org/2021/06/musicxml40/) scores of scales and chord
+//! progressions, for import into notation software such as MuseScore or Dorico.
+//!
+//! Each note is spelled as its nearest 12 EDO note, and its deviation from it is encoded
+//! in the decimal part of its `<alter>`, e.g. `-0.14` for a note 14 cents flat.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use rust_intonation::{Chord, notation::musicxml, pitch::Note};
+//! let chord = Chord::from_harmonics(&[4, 5, 6, 7]);
+//! let score = musicxml::progression(&[chord], Note::MIDDLE_C);
+//! assert!(score.contains("<step>A</step><alter>0.69</alter><octave>4</octave>"));
+//! ```
+use crate::{chord::Chord, pitch::Note, scale::Scale};
+use num::traits::PrimInt;
+
+/// The step and alteration, in semitones, of each pitch class, spelled with sharps.
+const STEPS: [(char, i32); 12] = [
+    ('C', 0),
+    ('C', 1),
+    ('D', 0),
+    ('D', 1),
+    ('E', 0),
+    ('F', 0),
+    ('F', 1),
+    ('G', 0),
+    ('G', 1),
+    ('A', 0),
+    ('A', 1),
+    ('B', 0),
+];
+
+/// The number of quarter notes in each measure.
+const BEATS: usize = 4;
+
+/// Returns a score of the degrees of the scale, as quarter notes ascending from the
+/// given root up to the period, with the last measure filled out with rests.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, Scale, notation::musicxml, pitch::Note};
+/// let scale = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+/// let score = musicxml::scale(&scale, Note::MIDDLE_C);
+/// assert!(score.contains("<step>E</step><alter>-0.14</alter><octave>4</octave>"));
+/// assert_eq!(score.matches("<rest/>").count(), 0);
+/// ```
+pub fn scale<T: PrimInt>(scale: &Scale<T>, root: Note) -> String {
+    let root_cents = 100. * root.semitones() as f64;
+    let mut notes: Vec<String> = (0..=scale.len() as i32)
+        .map(|degree| note(root_cents + scale.cents_at(degree), "quarter", false))
+        .collect();
+    while !notes.len().is_multiple_of(BEATS) {
+        notes.push("<note><rest/><duration>1</duration><type>quarter</type></note>".to_string());
+    }
+    document(
+        notes
+            .chunks(BEATS)
+            .map(|measure| measure.to_vec())
+            .collect(),
+    )
+}
+
+/// Returns a score of the chords, one whole note chord to a measure, each above the
+/// given root. An empty chord is a measure's rest, and an empty progression a single
+/// measure's rest, so that the score always has a measure.
+pub fn progression<T: PrimInt>(chords: &[Chord<T>], root: Note) -> String {
+    if chords.is_empty() {
+        return document(vec![vec![measure_rest()]]);
+    }
+    let root_cents = 100. * root.semitones() as f64;
+    let measures = chords
+        .iter()
+        .map(|chord| {
+            let mut cents: Vec<f64> = chord
+                .ratios
+                .iter()
+                .map(|ratio| root_cents + ratio.cents())
+                .collect();
+            cents.sort_by(f64::total_cmp);
+            if cents.is_empty() {
+                return vec![measure_rest()];
+            }
+            cents
+                .into_iter()
+                .enumerate()
+                .map(|(i, cents)| note(cents, "whole", i > 0))
+                .collect()
+        })
+        .collect();
+    document(measures)
+}
+
+/// Returns a `<note>` of the given type at the given number of cents above middle C,
+/// marked as part of the previous note's chord if `chord` is set.
+fn note(cents: f64, kind: &str, chord: bool) -> String {
+    let (note, deviation) = Note::nearest(cents);
+    let (step, semitones) = STEPS[note.pitch_class as usize];
+    let alter = (semitones as f64 + deviation / 100.) * 100.;
+    let alter = match alter.round() as i32 {
+        0 => String::new(),
+        hundredths => format!("<alter>{}</alter>", hundredths as f64 / 100.),
+    };
+    let duration = if kind == "whole" { BEATS } else { 1 };
+    format!(
+        "<note>{}<pitch><step>{}</step>{}<octave>{}</octave></pitch><duration>{}</duration><type>{}</type></note>",
+        if chord { "<chord/>" } else { "" },
+        step,
+        alter,
+        note.octave,
+        duration,
+        kind
+    )
+}
+
+/// Returns a `<note>` resting for a whole measure.
+fn measure_rest() -> String {
+    format!(
+        "<note><rest measure=\"yes\"/><duration>{}</duration></note>",
+        BEATS
+    )
+}
+
+/// Returns a single part score of the given measures of notes, in 4/4 and treble clef.
+fn document(measures: Vec<Vec<String>>) -> String {
+    let mut xml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n",
+        "<!DOCTYPE score-partwise PUBLIC \"-//Recordare//DTD MusicXML 4.0 Partwise//EN\" ",
+        "\"http://www.musicxml.org/dtds/partwise.dtd\">\n",
+        "<score-partwise version=\"4.0\">\n",
+        "  <part-list>\n",
+        "    <score-part id=\"P1\"><part-name>Music</part-name></score-part>\n",
+        "  </part-list>\n",
+        "  <part id=\"P1\">\n",
+    ));
+    for (i, notes) in measures.iter().enumerate() {
+        xml.push_str(&format!("    <measure number=\"{}\">\n", i + 1));
+        if i == 0 {
+            xml.push_str(&format!(
+                "      <attributes><divisions>1</divisions><key><fifths>0</fifths></key><time><beats>{}</beats><beat-type>4</beat-type></time><clef><sign>G</sign><line>2</line></clef></attributes>\n",
+                BEATS
+            ));
+        }
+        for note in notes {
+            xml.push_str(&format!("      {}\n", note));
+        }
+        xml.push_str("    </measure>\n");
+    }
+    xml.push_str("  </part>\n</score-partwise>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ratio::Ratio, Edo};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn notes() {
+        assert_eq!(
+            note(0., "quarter", false),
+            "<note><pitch><step>C</step><octave>4</octave></pitch><duration>1</duration><type>quarter</type></note>"
+        );
+        assert_eq!(
+            note(-1186., "whole", true),
+            "<note><chord/><pitch><step>C</step><alter>0.14</alter><octave>3</octave></pitch><duration>4</duration><type>whole</type></note>"
+        );
+        assert!(note(960., "quarter", false).contains("<step>A</step><alter>0.6</alter>"));
+    }
+
+    #[test]
+    fn scale_fills_its_last_measure() {
+        let edo = Scale::<i32>::from_edo(Edo::new(5));
+        let score = scale(&edo, Note::MIDDLE_C);
+
+        assert_eq!(score.matches("<measure ").count(), 2);
+        assert_eq!(score.matches("<rest/>").count(), 2);
+        assert_eq!(score.matches("<attributes>").count(), 1);
+    }
+
+    #[test]
+    fn progression_of_chords() {
+        let chords = vec![
+            Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4)]),
+            Chord::new(vec![Ratio::new(3, 2), Ratio::new(4, 3)]),
+        ];
+        let score = progression(&chords, Note::MIDDLE_C);
+
+        assert_eq!(score.matches("<measure ").count(), 2);
+        assert_eq!(score.matches("<chord/>").count(), 2);
+        assert!(score.contains("<step>F</step><alter>-0.02</alter><octave>4</octave></pitch><duration>4</duration><type>whole</type></note>\n      <note><chord/><pitch><step>G</step><alter>0.02</alter>"));
+        assert!(score.ends_with("</score-partwise>\n"));
+    }
+
+    #[test]
+    fn empty_progression_rests_for_a_measure() {
+        let score = progression::<i32>(&[], Note::MIDDLE_C);
+
+        assert_eq!(score.matches("<measure ").count(), 1);
+        assert_eq!(score.matches("<attributes>").count(), 1);
+        assert!(score.contains("<note><rest measure=\"yes\"/><duration>4</duration></note>"));
+
+        let score = progression(&[Chord::<i32>::new(vec![])], Note::MIDDLE_C);
+        assert_eq!(score.matches("<rest measure=\"yes\"/>").count(), 1);
+    }
+}
@@ -0,0 +1,443 @@
+//! Number theory on integers of any [PrimInt] type: greatest common divisors and least
+//! common multiples, prime factorization and primality testing, and integer logarithms.
+//!
+//! Factorization divides out the small primes, then splits what remains with Pollard's
+//! rho algorithm, testing each factor for primality with the Miller–Rabin test, so that
+//! the 15 digit values found in `i64` lattices factor quickly.
+use alloc::{vec, vec::Vec};
+use num::{one, traits::PrimInt, zero};
+
+/// The primes divided out by trial division before resorting to Pollard's rho.
+const SMALL_PRIMES: [u128; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// The witnesses for the Miller–Rabin test, which together make it deterministic for
+/// every value below `3.3 * 10^24`, and so for every 64 bit integer.
+const WITNESSES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Returns the greatest common divisor of `a` and `b`, where the GCD of any number and
+/// zero is that number, and of zero and zero is zero.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::gcd;
+/// assert_eq!(gcd(12, 18), 6);
+/// assert_eq!(gcd(0u64, 5), 5);
+/// ```
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    let mut a = a;
+    let mut b = b;
+    while b != zero() {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// Returns the least common multiple of `a` and `b`, which is zero if either is zero.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::lcm;
+/// assert_eq!(lcm(4, 6), 12);
+/// ```
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    if a == zero() || b == zero() {
+        return zero();
+    }
+    a / gcd(a, b) * b
+}
+
+/// Divides `a` and `b` by their greatest common divisor.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::reduce;
+/// assert_eq!(reduce(12, 18), (2, 3));
+/// ```
+pub fn reduce<T: PrimInt>(a: T, b: T) -> (T, T) {
+    let g = gcd(a, b);
+    (a / g, b / g)
+}
+
+/// Returns the remainder of dividing `a` by `b`, taking the sign of `b`, so that
+/// counting up through `a` cycles through `0` to `b - 1` for a positive `b`, and
+/// through `b + 1` to `0` for a negative `b`.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::sign_preserving_mod;
+/// assert_eq!(sign_preserving_mod(-1, 3), 2);
+/// assert_eq!(sign_preserving_mod(1, -3), -2);
+/// ```
+pub fn sign_preserving_mod<T: PrimInt>(a: T, b: T) -> T {
+    let r = a % b;
+    if r != zero() && (r < zero()) != (b < zero()) {
+        r + b
+    } else {
+        r
+    }
+}
+
+/// Returns whether `n` is prime, by the Miller–Rabin test, which is exact for every
+/// value of a 64 bit or smaller type. Larger primes are always reported as prime, but
+/// a larger composite may, very rarely, be reported as prime too.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::is_prime;
+/// assert!(is_prime(97));
+/// assert!(!is_prime(91u64));
+/// assert!(is_prime(999_999_999_989i64));
+/// ```
+pub fn is_prime<T: PrimInt>(n: T) -> bool {
+    n > one() && miller_rabin(num::cast(n).unwrap())
+}
+
+/// Returns every prime no greater than `limit`, in ascending order.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::primes_up_to;
+/// assert_eq!(primes_up_to(13), vec![2, 3, 5, 7, 11, 13]);
+/// ```
+pub fn primes_up_to<T: PrimInt>(limit: T) -> Vec<T> {
+    let mut primes = vec![];
+    let mut n: T = num::cast(2).unwrap();
+    while n <= limit {
+        if is_prime(n) {
+            primes.push(n);
+        }
+        match n.checked_add(&one()) {
+            Some(next) => n = next,
+            None => break,
+        }
+    }
+    primes
+}
+
+/// Divides `p` out of `a` as many times as possible, returning the remaining value and
+/// the number of times `p` was divided out. As `0` and `1` could be divided out
+/// endlessly, nothing is divided out of `0`, nor when `p` is less than `2`.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::divide_out;
+/// assert_eq!(divide_out(80, 2), (5, 4));
+/// assert_eq!(divide_out(80, 1), (80, 0));
+/// ```
+pub fn divide_out<T: PrimInt>(a: T, p: T) -> (T, i32) {
+    if a == zero() || p <= one() {
+        return (a, 0);
+    }
+    let mut a = a;
+    let mut exp = 0;
+    while a % p == zero() {
+        a = a / p;
+        exp += 1;
+    }
+    (a, exp)
+}
+
+/// Returns the prime factorization of `n`, as each prime factor with its exponent, in
+/// ascending order of the primes. `1` has no prime factors.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::factorize;
+/// assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(factorize(1), vec![]);
+/// assert_eq!(
+///     factorize(999_999_937i64 * 999_983),
+///     vec![(999_983, 1), (999_999_937, 1)]
+/// );
+/// ```
+///
+/// Panics if `n` is not positive.
+pub fn factorize<T: PrimInt>(n: T) -> Vec<(T, u32)> {
+    assert!(n > zero(), "only positive integers can be factorized");
+    let mut primes = vec![];
+    let mut n: u128 = num::cast(n).unwrap();
+    for p in SMALL_PRIMES {
+        let (rest, exp) = divide_out(n, p);
+        primes.extend(core::iter::repeat_n(p, exp as usize));
+        n = rest;
+    }
+    split(n, &mut primes);
+    primes.sort();
+
+    let mut factors: Vec<(T, u32)> = vec![];
+    for p in primes {
+        let p = num::cast(p).unwrap();
+        match factors.last_mut() {
+            Some((q, exp)) if *q == p => *exp += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+/// Returns the greatest prime factor of `n`, or `1` for `1`, which has none.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::greatest_prime_factor;
+/// assert_eq!(greatest_prime_factor(81), 3);
+/// assert_eq!(greatest_prime_factor(1), 1);
+/// ```
+///
+/// Panics if `n` is not positive.
+pub fn greatest_prime_factor<T: PrimInt>(n: T) -> T {
+    factorize(n).last().map_or(one(), |(p, _)| *p)
+}
+
+/// Pushes the prime factors of `n`, which has no factors among [SMALL_PRIMES], onto
+/// `primes`, splitting it with Pollard's rho until only primes remain.
+fn split(n: u128, primes: &mut Vec<u128>) {
+    if n == 1 {
+        return;
+    }
+    if miller_rabin(n) {
+        primes.push(n);
+        return;
+    }
+    let divisor = pollard_rho(n);
+    split(divisor, primes);
+    split(n / divisor, primes);
+}
+
+/// Returns a non-trivial divisor of the odd composite `n`, by Pollard's rho, trying
+/// each polynomial `x^2 + c` in turn until one finds a divisor.
+fn pollard_rho(n: u128) -> u128 {
+    let f = |x: u128, c: u128| (mul_mod(x, x, n) + c) % n;
+    for c in 1.. {
+        let (mut x, mut y, mut divisor) = (2, 2, 1);
+        while divisor == 1 {
+            x = f(x, c);
+            y = f(f(y, c), c);
+            divisor = gcd(x.abs_diff(y), n);
+        }
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!()
+}
+
+/// Returns whether `n` is prime by the Miller–Rabin test with [WITNESSES].
+fn miller_rabin(n: u128) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if let Some(p) = WITNESSES.iter().find(|p| n.is_multiple_of(**p)) {
+        return n == *p;
+    }
+    let (d, s) = divide_out(n - 1, 2);
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        (1..s).any(|_| {
+            x = mul_mod(x, x, n);
+            x == n - 1
+        })
+    })
+}
+
+/// Returns `a * b mod m`, without overflowing however large `m` is.
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if m <= u64::MAX as u128 {
+        return a % m * (b % m) % m;
+    }
+    let (mut a, mut b, mut product) = (a % m, b % m, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    product
+}
+
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+fn pow_mod(base: u128, exp: u128, m: u128) -> u128 {
+    let (mut base, mut exp, mut result) = (base % m, exp, 1);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Returns the integer logarithm of `n` in the given base, rounded down.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::ilog;
+/// assert_eq!(ilog(80, 3), 3);
+/// assert_eq!(ilog(81, 3), 4);
+/// ```
+///
+/// Panics if `n` is not positive, or `base` is less than 2.
+pub fn ilog<T: PrimInt>(n: T, base: T) -> u32 {
+    assert!(n > zero(), "only positive integers have a logarithm");
+    assert!(base > one(), "logarithms are taken in bases of at least 2");
+    let mut n = n;
+    let mut log = 0;
+    while n >= base {
+        n = n / base;
+        log += 1;
+    }
+    log
+}
+
+/// Returns the integer base 2 logarithm of `n`, rounded down, found from its highest
+/// set bit.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::numtheory::ilog2;
+/// assert_eq!(ilog2(1), 0);
+/// assert_eq!(ilog2(1023u64), 9);
+/// ```
+///
+/// Panics if `n` is not positive.
+pub fn ilog2<T: PrimInt>(n: T) -> u32 {
+    assert!(n > zero(), "only positive integers have a logarithm");
+    T::zero().count_zeros() - 1 - n.leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_gcd_and_lcm_with_zero() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(5, 0), 5);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn test_sign_preserving_mod() {
+        assert_eq!(sign_preserving_mod(7, 3), 1);
+        assert_eq!(sign_preserving_mod(-4, 3), 2);
+        assert_eq!(sign_preserving_mod(-3, 3), 0);
+        assert_eq!(sign_preserving_mod(4, -3), -2);
+        assert_eq!(sign_preserving_mod(5u8, 3), 2);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<u8> = (0..=30).filter(|n| is_prime(*n)).collect();
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(is_prime(i32::MAX));
+        assert!(!is_prime(-7));
+    }
+
+    #[test]
+    fn test_primes_up_to() {
+        assert_eq!(primes_up_to(1), vec![]);
+        assert_eq!(primes_up_to(2), vec![2]);
+        assert_eq!(primes_up_to(13), vec![2, 3, 5, 7, 11, 13]);
+        assert_eq!(primes_up_to(u8::MAX).last(), Some(&251));
+    }
+
+    #[test]
+    fn test_greatest_prime_factor() {
+        assert_eq!(greatest_prime_factor(5), 5);
+        assert_eq!(greatest_prime_factor(15), 5);
+        assert_eq!(greatest_prime_factor(14), 7);
+        assert_eq!(greatest_prime_factor(1024), 2);
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(factorize(2), vec![(2, 1)]);
+        assert_eq!(factorize(81i64 * 49), vec![(3, 4), (7, 2)]);
+        assert_eq!(
+            factorize(u32::MAX),
+            vec![(3, 1), (5, 1), (17, 1), (257, 1), (65537, 1)]
+        );
+    }
+
+    #[test]
+    fn test_factorize_large_values() {
+        // The product of two primes near 10^7 has no small factors for trial division
+        assert_eq!(
+            factorize(9_999_991i64 * 10_000_019),
+            vec![(9_999_991, 1), (10_000_019, 1)]
+        );
+        assert_eq!(
+            factorize(3i64.pow(20) * 999_999_937),
+            vec![(3, 20), (999_999_937, 1)]
+        );
+        assert_eq!(factorize(u64::MAX).len(), 7);
+    }
+
+    #[test]
+    fn test_miller_rabin() {
+        // Carmichael numbers pass the Fermat test for every coprime base
+        assert!(!is_prime(561));
+        assert!(!is_prime(3_215_031_751u64));
+        assert!(is_prime(18_446_744_073_709_551_557u64));
+        // 2^127 - 1, a Mersenne prime, which the test can never report as composite
+        assert!(is_prime(i128::MAX));
+    }
+
+    #[test]
+    fn test_mul_mod_beyond_64_bits() {
+        let m = u128::MAX - 2;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(pow_mod(2, 128, m), 3);
+    }
+
+    #[test]
+    fn test_divide_out() {
+        assert_eq!(divide_out(80, 2), (5, 4));
+        assert_eq!(divide_out(81, 3), (1, 4));
+        assert_eq!(divide_out(7, 5), (7, 0));
+        assert_eq!(divide_out(0, 2), (0, 0));
+        assert_eq!(divide_out(12, 0), (12, 0));
+        assert_eq!(divide_out(-12, -1), (-12, 0));
+    }
+
+    #[test]
+    fn test_integer_logs() {
+        assert_eq!(ilog(1, 10), 0);
+        assert_eq!(ilog(999, 10), 2);
+        assert_eq!(ilog(1000, 10), 3);
+        assert_eq!(ilog2(2), 1);
+        assert_eq!(ilog2(i64::MAX), 62);
+        assert_eq!(ilog2(255u8), 7);
+    }
+}
@@ -0,0 +1,243 @@
+//! Provides [Frequencies][Frequency], and [Pitches][Pitch] that place a JI [Ratio]
+//! above a reference frequency, with conversions to and from cents, MIDI notes, and
+//! note names.
+mod note;
+
+pub(crate) use note::format_deviation;
+pub use note::Note;
+
+#[cfg(feature = "audio")]
+use crate::play::{Play, Player};
+use crate::{error::IntonationError, midi::MidiPitch, play::MIDDLE_C, ratio::Ratio};
+use num::traits::PrimInt;
+use std::{fmt::Display, ops::Mul};
+
+/// Models a frequency, in Hz.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Frequency, Ratio};
+/// let e = Frequency::A4 * Ratio::new(3, 2);
+/// assert_eq!(e, Frequency(660.));
+/// assert!((e.cents() - 1601.955).abs() < 0.001);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Frequency(pub f32);
+
+impl Frequency {
+    /// Middle C (C4), tuned relative to A4 = 440Hz in 12 EDO.
+    pub const MIDDLE_C: Self = Self(MIDDLE_C as f32);
+
+    /// The A above middle C (A4), the usual tuning reference, at 440Hz.
+    pub const A4: Self = Self(440.);
+
+    /// Returns the frequency the given number of cents above (or below) middle C.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Frequency;
+    /// assert!((Frequency::from_cents(900.).0 - 440.).abs() < 0.001);
+    /// ```
+    pub fn from_cents(cents: f64) -> Self {
+        Self::MIDDLE_C.transpose(cents)
+    }
+
+    /// Returns the number of cents the frequency is above (or below) middle C.
+    pub fn cents(&self) -> f64 {
+        1200. * (self.0 as f64 / MIDDLE_C).log2()
+    }
+
+    /// Returns the frequency the given number of cents above (or below) this one.
+    pub fn transpose(&self, cents: f64) -> Self {
+        Self((self.0 as f64 * 2f64.powf(cents / 1200.)) as f32)
+    }
+
+    /// Returns the frequency of a MIDI note, bent by its pitch bend on a synth with the
+    /// given pitch bend range in semitones.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Frequency, midi::MidiPitch};
+    /// let a = Frequency::from_midi(MidiPitch { key: 69, bend: 8192 }, 2);
+    /// assert!((a.0 - 440.).abs() < 0.001);
+    /// ```
+    pub fn from_midi(pitch: MidiPitch, bend_range: u8) -> Self {
+        Self::from_cents(pitch.cents(bend_range))
+    }
+
+    /// Returns the nearest MIDI note to the frequency, and the pitch bend to its exact
+    /// pitch on a synth with the given pitch bend range in semitones.
+    pub fn to_midi(&self, bend_range: u8) -> MidiPitch {
+        MidiPitch::from_cents(self.cents(), bend_range)
+    }
+}
+
+impl<T: PrimInt> Mul<Ratio<T>> for Frequency {
+    type Output = Self;
+
+    /// Returns the frequency the given ratio above this one.
+    fn mul(self, rhs: Ratio<T>) -> Self::Output {
+        Self(self.0 * rhs.to_f32())
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Play for Frequency {
+    /// Plays a single note at the frequency, ignoring the root of the player's
+    /// [PlaybackConfig](crate::play::PlaybackConfig), like a tuning fork.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rust_intonation::{Frequency, play::{Play, Player}};
+    /// Frequency(432.).transpose(-14.).play(&Player::default());
+    /// ```
+    fn play(&self, player: &Player) {
+        player.play_note(self.0);
+    }
+}
+
+impl Display for Frequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} Hz", self.0)
+    }
+}
+
+/// Models a pitch as a JI [Ratio] above a reference [Frequency].
+///
+/// ## Example
+///
+/// A JI major third above middle C is about 14 cents flatter than a 12 EDO E4
+///
+/// ```rust
+/// # use rust_intonation::{Frequency, Pitch, Ratio};
+/// let e = Pitch::new(Frequency::MIDDLE_C, Ratio::new(5, 4));
+/// assert!((e.frequency().0 - 327.032).abs() < 0.001);
+/// assert_eq!(e.to_midi(2).key, 64);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pitch<T: PrimInt = i32> {
+    pub reference: Frequency,
+    pub ratio: Ratio<T>,
+}
+
+impl<T: PrimInt> Pitch<T> {
+    /// Construct a new [Pitch] the given ratio above the reference frequency.
+    pub fn new(reference: Frequency, ratio: Ratio<T>) -> Self {
+        Self { reference, ratio }
+    }
+
+    /// Returns the frequency of the pitch.
+    pub fn frequency(&self) -> Frequency {
+        self.reference * self.ratio
+    }
+
+    /// Returns the number of cents the pitch is above (or below) middle C.
+    pub fn cents(&self) -> f64 {
+        self.reference.cents() + self.ratio.cents()
+    }
+
+    /// Returns the nearest MIDI note to the pitch, and the pitch bend to its exact pitch
+    /// on a synth with the given pitch bend range in semitones.
+    pub fn to_midi(&self, bend_range: u8) -> MidiPitch {
+        MidiPitch::from_cents(self.cents(), bend_range)
+    }
+
+    /// Parses a note name, e.g. `A4` or `C#3`, into the pitch of that note in 12 EDO,
+    /// tuned relative to the given frequency of A4. See [Note].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Frequency, Pitch};
+    /// let baroque: Pitch = Pitch::from_note_name("A4", Frequency(415.)).unwrap();
+    /// assert_eq!(baroque.frequency(), Frequency(415.));
+    /// assert!(Pitch::<i32>::from_note_name("H4", Frequency::A4).is_err());
+    /// ```
+    pub fn from_note_name(name: &str, a4: Frequency) -> Result<Self, IntonationError> {
+        Ok(name.parse::<Note>()?.pitch(a4))
+    }
+
+    /// Names the pitch by its nearest 12 EDO note, tuned relative to the given frequency
+    /// of A4, and its deviation from that note, rounded to the nearest cent.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Frequency, Pitch, Ratio};
+    /// let pitch = Pitch::new(Frequency(220.), Ratio::new(5, 4));
+    /// assert_eq!(pitch.name(Frequency::A4), "C#4 −14¢");
+    /// assert_eq!(pitch.name(Frequency(415.)), "D4 −12¢");
+    /// ```
+    pub fn name(&self, a4: Frequency) -> String {
+        let cents = 1200. * (self.frequency().0 as f64 / a4.0 as f64).log2() + 900.;
+        note::name(cents)
+    }
+}
+
+#[cfg(feature = "audio")]
+impl<T: PrimInt> Play for Pitch<T> {
+    /// Plays a single note at the frequency of the pitch, ignoring the root of the
+    /// player's [PlaybackConfig](crate::play::PlaybackConfig).
+    fn play(&self, player: &Player) {
+        self.frequency().play(player);
+    }
+}
+
+impl<T: PrimInt> From<Frequency> for Pitch<T> {
+    /// A pitch at the frequency itself, a `1/1` above it.
+    fn from(value: Frequency) -> Self {
+        Self::new(value, Ratio::new(T::one(), T::one()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cents_round_trip() {
+        assert!(Frequency::MIDDLE_C.cents().abs() < 1e-4);
+        assert!((Frequency::A4.cents() - 900.).abs() < 1e-4);
+        assert!((Frequency::from_cents(-1200.).0 - MIDDLE_C as f32 / 2.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn midi_round_trip() {
+        let pitch = Pitch::new(Frequency::A4, Ratio::new(7, 4));
+        let midi = pitch.to_midi(2);
+
+        assert_eq!(midi.key, 79);
+        let frequency = Frequency::from_midi(midi, 2);
+        assert!((frequency.0 - pitch.frequency().0).abs() < 0.05);
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    fn play_ignores_the_root() {
+        use crate::play::PlaybackConfig;
+
+        let config = PlaybackConfig {
+            duration: 0.1,
+            ..PlaybackConfig::default()
+        };
+        let fork = Player::recorder(config);
+        Pitch::new(Frequency(220.), Ratio::new(3, 2)).play(&fork);
+        let note = Player::recorder(config);
+        note.play_note(330.);
+
+        assert_eq!(fork.samples(), note.samples());
+    }
+
+    #[test]
+    fn pitch_from_frequency() {
+        let pitch: Pitch = Frequency::A4.into();
+
+        assert_eq!(pitch.frequency(), Frequency::A4);
+        assert_eq!(pitch.ratio, Ratio::new(1, 1));
+    }
+}
@@ -0,0 +1,213 @@
+//! Provides [Notes][Note], the names of 12 EDO pitches in scientific pitch notation, for
+//! naming JI pitches by their nearest note and its deviation in cents.
+use super::{Frequency, Pitch};
+use crate::{error::IntonationError, ratio::Ratio};
+use num::traits::PrimInt;
+use std::{fmt::Display, str::FromStr};
+
+/// The name of each pitch class, spelled with sharps.
+const NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Models a 12 EDO note by its name in scientific pitch notation, e.g. `C#3`, as a pitch
+/// class, the number of semitones above C, and an octave, in which middle C is `C4`.
+///
+/// Notes are parsed from a letter, any number of sharps (`#` or `♯`) or flats (`b` or
+/// `♭`), and an octave, and are displayed spelled with sharps.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Frequency, pitch::Note};
+/// let note: Note = "Bb3".parse().unwrap();
+/// assert_eq!(note, Note { pitch_class: 10, octave: 3 });
+/// assert_eq!(note.to_string(), "A#3");
+/// assert!((note.frequency(Frequency::A4).0 - 233.082).abs() < 0.001);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Note {
+    pub pitch_class: u8,
+    pub octave: i32,
+}
+
+impl Note {
+    /// Middle C (C4).
+    pub const MIDDLE_C: Self = Self {
+        pitch_class: 0,
+        octave: 4,
+    };
+
+    /// Returns the note the given number of semitones above (or below) middle C.
+    pub fn from_semitones(semitones: i32) -> Self {
+        Self {
+            pitch_class: semitones.rem_euclid(12) as u8,
+            octave: 4 + semitones.div_euclid(12),
+        }
+    }
+
+    /// Returns the number of semitones the note is above (or below) middle C.
+    ///
+    /// Panics if the number overflows, which a parsed note never does.
+    pub fn semitones(&self) -> i32 {
+        semitones(self.octave, self.pitch_class as i32)
+            .unwrap_or_else(|| panic!("{}", IntonationError::Overflow))
+    }
+
+    /// Returns the frequency of the note in 12 EDO, tuned relative to the given
+    /// frequency of A4.
+    pub fn frequency(&self, a4: Frequency) -> Frequency {
+        a4.transpose(100. * (self.semitones() - 9) as f64)
+    }
+
+    /// Returns the [Pitch] of the note, tuned relative to the given frequency of A4.
+    pub fn pitch<T: PrimInt>(&self, a4: Frequency) -> Pitch<T> {
+        self.frequency(a4).into()
+    }
+
+    /// Returns the nearest note to a pitch the given number of cents above (or below)
+    /// middle C, and the number of cents the pitch deviates from it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::pitch::Note;
+    /// let (note, deviation) = Note::nearest(386.314);
+    /// assert_eq!(note.to_string(), "E4");
+    /// assert!((deviation + 13.686).abs() < 0.001);
+    /// ```
+    pub fn nearest(cents: f64) -> (Self, f64) {
+        let semitones = (cents / 100.).round() as i32;
+        (
+            Self::from_semitones(semitones),
+            cents - 100. * semitones as f64,
+        )
+    }
+
+    /// Names the pitch the given ratio above the note, by its nearest note and its
+    /// deviation from it, rounded to the nearest cent.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, pitch::Note};
+    /// let c4 = Note::MIDDLE_C;
+    /// assert_eq!(c4.name_above(Ratio::new(5, 4)), "E4 −14¢");
+    /// assert_eq!(c4.name_above(Ratio::new(3, 2)), "G4 +2¢");
+    /// ```
+    pub fn name_above<T: PrimInt>(&self, ratio: Ratio<T>) -> String {
+        name(100. * self.semitones() as f64 + ratio.cents())
+    }
+}
+
+/// Names a pitch the given number of cents above (or below) middle C by its nearest note
+/// and its deviation from it, e.g. `E4 −14¢`.
+pub(super) fn name(cents: f64) -> String {
+    let (note, deviation) = Note::nearest(cents);
+    format!("{} {}", note, format_deviation(deviation))
+}
+
+/// Writes a deviation in cents rounded to the nearest cent, with its sign, e.g. `−14¢`.
+pub(crate) fn format_deviation(deviation: f64) -> String {
+    let deviation = deviation.round() as i32;
+    let sign = if deviation < 0 { '−' } else { '+' };
+    format!("{}{}¢", sign, deviation.abs())
+}
+
+impl FromStr for Note {
+    type Err = IntonationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || IntonationError::InvalidNoteName(s.to_string());
+        let mut chars = s.trim().chars();
+        let letter = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some('C') => 0,
+            Some('D') => 2,
+            Some('E') => 4,
+            Some('F') => 5,
+            Some('G') => 7,
+            Some('A') => 9,
+            Some('B') => 11,
+            _ => return Err(invalid()),
+        };
+
+        let rest = chars.as_str();
+        let octave_start = rest
+            .find(|c: char| c == '-' || c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (accidentals, octave) = rest.split_at(octave_start);
+        let alteration = accidentals
+            .chars()
+            .map(|c| match c {
+                '#' | '♯' => Ok(1),
+                'b' | '♭' => Ok(-1),
+                _ => Err(invalid()),
+            })
+            .sum::<Result<i32, IntonationError>>()?;
+        let octave: i32 = octave.parse().map_err(|_| invalid())?;
+
+        let semitones = semitones(octave, letter + alteration).ok_or_else(invalid)?;
+        Ok(Self::from_semitones(semitones))
+    }
+}
+
+/// Returns the number of semitones a pitch class in the given octave is above (or below)
+/// middle C, or `None` if it overflows.
+fn semitones(octave: i32, pitch_class: i32) -> Option<i32> {
+    octave
+        .checked_sub(4)?
+        .checked_mul(12)?
+        .checked_add(pitch_class)
+}
+
+impl Display for Note {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", NAMES[self.pitch_class as usize], self.octave)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse() {
+        let note = |s: &str| s.parse::<Note>().map(|note| note.to_string());
+
+        assert_eq!(note("A4"), Ok("A4".to_string()));
+        assert_eq!(note("c#3"), Ok("C#3".to_string()));
+        assert_eq!(note("E♭5"), Ok("D#5".to_string()));
+        assert_eq!(note("Cb4"), Ok("B3".to_string()));
+        assert_eq!(note("B#3"), Ok("C4".to_string()));
+        assert_eq!(note("G-1"), Ok("G-1".to_string()));
+        assert_eq!(
+            note("H4"),
+            Err(IntonationError::InvalidNoteName("H4".to_string()))
+        );
+        assert!(note("C").is_err());
+        assert!(note("Cx4").is_err());
+        assert_eq!(
+            note("C300000000"),
+            Err(IntonationError::InvalidNoteName("C300000000".to_string()))
+        );
+    }
+
+    #[test]
+    fn frequency_with_reference() {
+        let a4 = "A4".parse::<Note>().unwrap();
+
+        assert_eq!(a4.frequency(Frequency::A4), Frequency::A4);
+        assert_eq!(a4.frequency(Frequency(415.)), Frequency(415.));
+        assert!((Note::MIDDLE_C.frequency(Frequency::A4).0 - Frequency::MIDDLE_C.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn name_above() {
+        let a3 = "A3".parse::<Note>().unwrap();
+
+        assert_eq!(a3.name_above(Ratio::new(7, 4)), "G4 −31¢");
+        assert_eq!(a3.name_above(Ratio::new(1, 1)), "A3 +0¢");
+        assert_eq!(a3.name_above(Ratio::new(81, 64)), "C#4 +8¢");
+    }
+}
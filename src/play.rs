@@ -1,43 +1,375 @@
-//! Helpers for playback via [rodio](https://docs.rs/rodio).
-use rodio::{
-    source::{Amplify, SineWave, Source, TakeDuration},
-    OutputStream, Sink,
-};
-use std::time::Duration;
+//! Settings and helpers for playback via [rodio](https://docs.rs/rodio).
+//!
+//! Playing audio requires the default-on `audio` feature. The settings and analysis
+//! in this module are available without it.
+mod comparison;
+mod drone;
+#[cfg(feature = "audio")]
+mod player;
+#[cfg(feature = "audio")]
+mod source;
 
-/// Trait to allow playback using [rodio](https://docs.rs/rodio).
-pub trait Play {
-    fn play(&self);
+pub use comparison::PitchComparison;
+pub use drone::{Drone, DroneVoice};
+#[cfg(feature = "audio")]
+pub use player::{compare, Play, PlaybackHandle, Player};
+
+use crate::pitch::Frequency;
+
+/// The frequency of middle C (C4), in Hz, tuned relative to A4 = 440Hz in 12 EDO.
+pub const MIDDLE_C: f64 = 261.625_565_300_598_6;
+
+/// Settings controlling how intervals are played back.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::play::PlaybackConfig;
+/// let config = PlaybackConfig {
+///     root: 440.,
+///     duration: 1.,
+///     ..PlaybackConfig::default()
+/// };
+/// assert_eq!(config.volume, 0.2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlaybackConfig {
+    /// The frequency, in Hz, of the root pitch that intervals are played above.
+    pub root: f32,
+    /// The silence, in seconds, between successive sounds.
+    pub gap: f32,
+    /// The length, in seconds, of each note.
+    pub duration: f32,
+    /// The amplitude of each note, from 0 to 1.
+    pub volume: f32,
+    /// The shape of the wave each note is played with.
+    pub waveform: Waveform,
+    /// The envelope shaping the volume of each note over its duration.
+    pub envelope: Envelope,
+    /// The volume, relative to the notes, at which the [difference tones][difference_tones]
+    /// of notes played together are mixed in. `0` leaves them out.
+    pub difference_tones: f32,
+    /// The stereo position of each note, from `-1` (left) through `0` (center) to `1`
+    /// (right).
+    pub pan: f32,
+    /// Whether notes played together are spread evenly from left to right, in the order
+    /// they are given, rather than all placed at [PlaybackConfig::pan]. A dyad is played
+    /// with its root in the left ear and its interval in the right.
+    pub spread: bool,
+    /// How the notes of a chord are presented: together, one at a time, or strummed.
+    pub chord_style: ChordStyle,
+    /// The order in which the degrees of a scale are played.
+    pub scale_direction: ScaleDirection,
+}
+
+impl PlaybackConfig {
+    /// Returns the config with each note lasting one beat at the given tempo, in beats
+    /// per minute.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::play::PlaybackConfig;
+    /// let config = PlaybackConfig::default().at_tempo(120.);
+    /// assert_eq!(config.duration, 0.5);
+    /// ```
+    pub fn at_tempo(self, bpm: f32) -> Self {
+        Self {
+            duration: 60. / bpm,
+            ..self
+        }
+    }
+}
+
+/// Returns the first-order difference tones of the given frequencies sounding together,
+/// that is, the difference between each pair of frequencies, in ascending order and
+/// without duplicates.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::play::difference_tones;
+/// assert_eq!(difference_tones(&[400., 500., 600.]), vec![100., 200.]);
+/// ```
+pub fn difference_tones(freqs: &[f64]) -> Vec<f64> {
+    let mut tones: Vec<f64> = freqs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| freqs[i + 1..].iter().map(move |b| (a - b).abs()))
+        .filter(|tone| *tone > DIFFERENCE_TONE_TOLERANCE)
+        .collect();
+    tones.sort_by(f64::total_cmp);
+    tones.dedup_by(|a, b| (*a - *b).abs() < DIFFERENCE_TONE_TOLERANCE);
+    tones
+}
+
+/// Tolerance, in Hz, within which two difference tones are considered the same.
+const DIFFERENCE_TONE_TOLERANCE: f64 = 1e-6;
+
+/// An attack/decay/sustain/release envelope shaping the volume of a note over its
+/// duration, to avoid clicks where notes start and stop.
+///
+/// The attack, decay, and release are given in seconds, and the sustain as a level from
+/// 0 to 1. The release takes place within the duration of the note, rather than after it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Envelope {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
 }
 
-pub(crate) fn play_interval(root_freq: f32, interval_freq: f32) {
-    let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-    let sink = Sink::try_new(&stream_handle).unwrap();
+impl Envelope {
+    /// Create an envelope that fades in and out over the given times, in seconds, at full
+    /// volume in between.
+    pub fn fade(attack: f32, release: f32) -> Self {
+        Self {
+            attack,
+            decay: 0.,
+            sustain: 1.,
+            release,
+        }
+    }
+
+    /// Create an envelope that plays the note at full volume throughout.
+    pub fn none() -> Self {
+        Self::fade(0., 0.)
+    }
+
+    /// Returns the gain, from 0 to 1, of the envelope at the given time, in seconds, into
+    /// a note of the given duration.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::play::Envelope;
+    /// let envelope = Envelope::fade(0.1, 0.5);
+    /// assert_eq!(envelope.gain(0.05, 2.), 0.5);
+    /// assert_eq!(envelope.gain(1., 2.), 1.);
+    /// assert_eq!(envelope.gain(1.75, 2.), 0.5);
+    /// ```
+    pub fn gain(&self, time: f32, duration: f32) -> f32 {
+        let level = if time < self.attack {
+            time / self.attack
+        } else if time < self.attack + self.decay {
+            1. - (1. - self.sustain) * (time - self.attack) / self.decay
+        } else {
+            self.sustain
+        };
 
-    let root = create_sine_wave(root_freq);
-    let interval = create_sine_wave(interval_freq);
+        let remaining = duration - time;
+        if remaining < self.release {
+            level * (remaining / self.release).max(0.)
+        } else {
+            level
+        }
+    }
+}
+
+impl Default for Envelope {
+    /// Fades in over 10ms and out over 50ms.
+    fn default() -> Self {
+        Self::fade(0.01, 0.05)
+    }
+}
 
-    sink.append(root);
-    sink.append(interval);
-    sink.sleep_until_end();
+/// The shape of the wave a note is played with.
+///
+/// Waveforms other than [Waveform::Sine] contain overtones, whose beating makes
+/// differences in interval quality easier to hear.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Triangle,
+    Sawtooth,
+    Square,
+    /// An additive wave made up of the given number of partials of the harmonic series,
+    /// with the amplitude of the `n`th partial scaled by `1 / n^rolloff`.
+    Harmonic {
+        partials: u32,
+        rolloff: f32,
+    },
 }
 
-pub(crate) fn play_dyad(root_freq: f32, interval_freq: f32) {
-    let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-    let sink = Sink::try_new(&stream_handle).unwrap();
-    let sink2 = Sink::try_new(&stream_handle).unwrap();
+impl Waveform {
+    /// Returns the value of the wave, between -1 and 1, at the given phase, from 0 to 1,
+    /// of a note at the given frequency.
+    ///
+    /// Partials of a [Waveform::Harmonic] wave above the Nyquist frequency of
+    /// [SAMPLE_RATE] are left out.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::play::Waveform;
+    /// assert_eq!(Waveform::Square.value(0.25, 440.), 1.);
+    /// assert_eq!(Waveform::Square.value(0.75, 440.), -1.);
+    /// ```
+    pub fn value(&self, phase: f32, freq: f32) -> f32 {
+        use std::f32::consts::TAU;
 
-    let root = create_sine_wave(root_freq);
-    let interval = create_sine_wave(interval_freq);
+        match self {
+            Self::Sine => (TAU * phase).sin(),
+            Self::Triangle => 1. - 4. * ((phase + 0.25).rem_euclid(1.) - 0.5).abs(),
+            Self::Sawtooth => 2. * (phase + 0.5).rem_euclid(1.) - 1.,
+            Self::Square => {
+                if phase.rem_euclid(1.) < 0.5 {
+                    1.
+                } else {
+                    -1.
+                }
+            }
+            Self::Harmonic { partials, rolloff } => {
+                let nyquist = SAMPLE_RATE as f32 / 2.;
+                let (sum, total) = (1..=*partials)
+                    .map(|n| n as f32)
+                    .filter(|n| n * freq < nyquist)
+                    .map(|n| ((TAU * n * phase).sin(), n.powf(-rolloff)))
+                    .fold((0., 0.), |(sum, total), (value, amplitude)| {
+                        (sum + value * amplitude, total + amplitude)
+                    });
+                if total == 0. {
+                    0.
+                } else {
+                    sum / total
+                }
+            }
+        }
+    }
+}
 
-    sink.append(root);
-    sink2.append(interval);
-    sink.sleep_until_end();
-    sink2.sleep_until_end();
+/// How the notes of a chord are presented when it is played.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ChordStyle {
+    /// Every note at once.
+    #[default]
+    Block,
+    /// One note at a time, from the lowest to the highest.
+    Ascending,
+    /// One note at a time, from the highest to the lowest.
+    Descending,
+    /// Each note starting the given number of seconds after the one below it, and held
+    /// for the full duration, as when strumming a guitar.
+    Strum(f32),
 }
 
-pub(crate) fn create_sine_wave(freq: f32) -> Amplify<TakeDuration<SineWave>> {
-    SineWave::new(freq)
-        .take_duration(Duration::from_secs_f32(2.))
-        .amplify(0.2)
+/// The order in which the degrees of a scale are played, each including the period
+/// above the root.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ScaleDirection {
+    /// From the root up to the period.
+    #[default]
+    Ascending,
+    /// From the period down to the root.
+    Descending,
+    /// From the root up to the period, and back down to the root.
+    UpDown,
+}
+
+impl Default for PlaybackConfig {
+    /// Plays 2 second sine waves above middle C at an amplitude of 0.2 in the center of
+    /// the stereo field, with a quarter second between them, and a short fade in and out,
+    /// chords as [block chords][ChordStyle::Block], and scales
+    /// [ascending][ScaleDirection::Ascending].
+    fn default() -> Self {
+        Self {
+            root: Frequency::MIDDLE_C.0,
+            gap: 0.25,
+            duration: 2.,
+            volume: 0.2,
+            waveform: Waveform::Sine,
+            envelope: Envelope::default(),
+            difference_tones: 0.,
+            pan: 0.,
+            spread: false,
+            chord_style: ChordStyle::Block,
+            scale_direction: ScaleDirection::Ascending,
+        }
+    }
+}
+
+/// The sample rate, in Hz, at which notes are mixed together and recorded.
+pub const SAMPLE_RATE: u32 = 48000;
+
+/// The number of channels that are played and recorded.
+pub const CHANNELS: u16 = 2;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn quarter_periods(waveform: Waveform) -> Vec<f32> {
+        [0., 0.25, 0.5, 0.75]
+            .iter()
+            .map(|phase| (waveform.value(*phase, 440.) * 1000.).round() / 1000.)
+            .collect()
+    }
+
+    #[test]
+    fn waveforms() {
+        assert_eq!(quarter_periods(Waveform::Sine), vec![0., 1., 0., -1.]);
+        assert_eq!(quarter_periods(Waveform::Triangle), vec![0., 1., 0., -1.]);
+        assert_eq!(
+            quarter_periods(Waveform::Sawtooth),
+            vec![0., 0.5, -1., -0.5]
+        );
+        assert_eq!(quarter_periods(Waveform::Square), vec![1., 1., -1., -1.]);
+    }
+
+    #[test]
+    fn difference_tones_of_a_chord() {
+        assert_eq!(difference_tones(&[200., 300.]), vec![100.]);
+        assert_eq!(
+            difference_tones(&[400., 500., 600., 700.]),
+            vec![100., 200., 300.]
+        );
+        assert_eq!(difference_tones(&[440., 440.]), vec![]);
+    }
+
+    #[test]
+    fn envelope() {
+        let envelope = Envelope {
+            attack: 0.1,
+            decay: 0.2,
+            sustain: 0.5,
+            release: 0.4,
+        };
+
+        assert_eq!(envelope.gain(0., 2.), 0.);
+        assert_eq!(envelope.gain(0.1, 2.), 1.);
+        assert_eq!(envelope.gain(0.2, 2.), 0.75);
+        assert_eq!(envelope.gain(1., 2.), 0.5);
+        assert!((envelope.gain(1.8, 2.) - 0.25).abs() < 1e-6);
+        assert_eq!(envelope.gain(2., 2.), 0.);
+        assert_eq!(Envelope::none().gain(0., 2.), 1.);
+    }
+
+    #[test]
+    fn harmonic_waveform_skips_partials_above_nyquist() {
+        let harmonic = Waveform::Harmonic {
+            partials: 8,
+            rolloff: 1.,
+        };
+
+        assert_eq!(
+            harmonic.value(0.25, SAMPLE_RATE as f32 / 4.),
+            Waveform::Sine.value(0.25, SAMPLE_RATE as f32 / 4.)
+        );
+    }
+
+    #[test]
+    fn harmonic_waveform_stays_within_range() {
+        let harmonic = Waveform::Harmonic {
+            partials: 8,
+            rolloff: 0.5,
+        };
+        let samples: Vec<f32> = (0..100)
+            .map(|i| harmonic.value(i as f32 / 100., 220.))
+            .collect();
+
+        assert!(samples.iter().all(|s| s.abs() <= 1.));
+        assert!(samples.iter().any(|s| s.abs() > 0.5));
+    }
 }
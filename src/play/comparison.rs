@@ -0,0 +1,151 @@
+//! A reference tone sustained along with a second tone that can be retuned while they
+//! sound, to train the ear to hear small intervals, such as commas.
+use crate::interval::constants;
+#[cfg(feature = "audio")]
+use crate::play::{Play, Player};
+use crate::ratio::Ratio;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Models a reference tone at the root, sustained along with a second tone a number of
+/// cents above it, which can be changed while they sound.
+///
+/// Clones share the offset of the second tone, so that it can be nudged from one thread
+/// while the comparison is played on another, with [play_async][crate::play::Play::play_async].
+#[cfg_attr(
+    feature = "audio",
+    doc = r#"
+## Example
+
+```rust,no_run
+# use rust_intonation::play::{PitchComparison, Play, PlaybackConfig};
+let comparison = PitchComparison::new(0.);
+let handle = comparison.play_async(&PlaybackConfig::default());
+comparison.nudge(21.5);
+// ...
+handle.stop();
+```"#
+)]
+#[derive(Clone, Debug)]
+pub struct PitchComparison {
+    /// The bits of the offset, in cents, of the second tone, as an [f64].
+    pub(crate) offset: Arc<AtomicU64>,
+}
+
+impl PitchComparison {
+    /// Construct a new [PitchComparison] with the second tone the given number of cents
+    /// above the reference.
+    pub fn new(cents: f64) -> Self {
+        Self {
+            offset: Arc::new(AtomicU64::new(cents.to_bits())),
+        }
+    }
+
+    /// Returns the number of cents the second tone is above the reference.
+    pub fn cents(&self) -> f64 {
+        f64::from_bits(self.offset.load(Ordering::Relaxed))
+    }
+
+    /// Retunes the second tone to the given number of cents above the reference.
+    pub fn set_cents(&self, cents: f64) {
+        self.offset.store(cents.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Raises (or, if negative, lowers) the second tone by the given number of cents,
+    /// returning its new offset from the reference.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::play::PitchComparison;
+    /// let comparison = PitchComparison::new(700.);
+    /// assert_eq!(comparison.nudge(2.), 702.);
+    /// assert_eq!(comparison.nudge(-4.), 698.);
+    /// ```
+    pub fn nudge(&self, cents: f64) -> f64 {
+        let cents = self.cents() + cents;
+        self.set_cents(cents);
+        cents
+    }
+
+    /// Returns the ratio the interval between the tones approximates, taken within the
+    /// octave: the nearest [named interval][constants] within `tolerance` cents of it,
+    /// if there is one, and otherwise the simplest of its
+    /// [approximations][Ratio::approximations] with a denominator no greater than
+    /// `max_denominator`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{play::PitchComparison, Ratio};
+    /// let comparison = PitchComparison::new(21.);
+    /// assert_eq!(comparison.approximation(1., 128), Some(Ratio::new(81, 80)));
+    /// comparison.set_cents(-498.);
+    /// assert_eq!(comparison.approximation(1., 128), Some(Ratio::new(3, 2)));
+    /// ```
+    pub fn approximation(&self, tolerance: f64, max_denominator: i32) -> Option<Ratio<i32>> {
+        let cents = self.cents().rem_euclid(1200.);
+        let distance = |ratio: &Ratio<i32>| {
+            let distance = (ratio.cents() - cents).abs();
+            distance.min(1200. - distance)
+        };
+        constants::up_to_limit(i32::MAX)
+            .map(|(_, ratio)| (distance(ratio), *ratio))
+            .filter(|(distance, _)| *distance <= tolerance)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, ratio)| ratio)
+            .or_else(|| {
+                Ratio::approximations(cents, tolerance, max_denominator)
+                    .first()
+                    .copied()
+            })
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Play for PitchComparison {
+    /// Sustains the reference tone, at the root, along with the second tone, retuning
+    /// it whenever it is nudged, until the player is stopped.
+    ///
+    /// Panics if the player is a [recorder][Player::recorder], as it would never finish
+    /// recording.
+    fn play(&self, player: &Player) {
+        player.play_comparison(&self.offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn clones_share_the_offset() {
+        let comparison = PitchComparison::new(0.);
+        let clone = comparison.clone();
+        clone.nudge(-13.7);
+
+        assert_eq!(comparison.cents(), -13.7);
+    }
+
+    #[test]
+    fn approximations() {
+        let comparison = PitchComparison::new(1199.5);
+        assert_eq!(comparison.approximation(1., 128), Some(Ratio::new(1, 1)));
+
+        // No named interval lies within a cent of 750 cents
+        comparison.set_cents(750.);
+        assert_eq!(comparison.approximation(1., 128), Some(Ratio::new(37, 24)));
+        assert_eq!(comparison.approximation(1., 16), None);
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    #[should_panic(expected = "cannot record a comparison")]
+    fn recording_a_comparison() {
+        let player = Player::recorder(crate::play::PlaybackConfig::default());
+        PitchComparison::new(0.).play(&player);
+    }
+}
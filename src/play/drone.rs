@@ -0,0 +1,154 @@
+//! Sustained drones of several voices above the root, to practice intonation against.
+use crate::ratio::Ratio;
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::Frequency,
+    play::{Play, Player},
+};
+use num::traits::PrimInt;
+
+/// One voice of a [Drone]: a pitch, in cents above the root, and its amplitude, from 0 to
+/// 1, relative to the volume of the player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DroneVoice {
+    pub cents: f64,
+    pub amplitude: f32,
+}
+
+/// Models a drone as a set of voices sustained together above the root, either for a
+/// number of seconds or until playback is stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drone {
+    pub voices: Vec<DroneVoice>,
+    /// The number of seconds the drone sounds for, or `None` to sound until the player
+    /// is stopped. The duration of the player's
+    /// [PlaybackConfig](crate::play::PlaybackConfig) is ignored.
+    pub duration: Option<f32>,
+}
+
+impl Drone {
+    /// Construct a new [Drone] from the given voices, sounding until stopped.
+    pub fn new(voices: Vec<DroneVoice>) -> Self {
+        Self {
+            voices,
+            duration: None,
+        }
+    }
+
+    /// Construct a new [Drone] from the given harmonics of the root, at full amplitude,
+    /// where `1` is the root itself.
+    ///
+    /// Unlike the [Ratios][Ratio] of [Drone::from_ratios], the harmonics are not reduced
+    /// to within the octave, so the drone can span several octaves.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::play::Drone;
+    /// let drone = Drone::from_harmonics(&[1, 2, 3]);
+    /// let cents: Vec<f64> = drone.voices.iter().map(|v| v.cents.round()).collect();
+    /// assert_eq!(cents, vec![0., 1200., 1902.]);
+    /// ```
+    pub fn from_harmonics(harmonics: &[u32]) -> Self {
+        Self::from_cents(harmonics.iter().map(|h| 1200. * (*h as f64).log2()))
+    }
+
+    /// Construct a new [Drone] from the given ratios above the root, at full amplitude.
+    pub fn from_ratios<T: PrimInt>(ratios: &[Ratio<T>]) -> Self {
+        Self::from_cents(ratios.iter().map(|r| r.cents()))
+    }
+
+    /// Returns the drone with the amplitude of each voice, in order, set to the given
+    /// amplitudes. Voices beyond the amplitudes given keep their amplitude.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::play::Drone;
+    /// let drone = Drone::from_harmonics(&[1, 3, 5]).with_amplitudes(&[1., 0.5]);
+    /// let amplitudes: Vec<f32> = drone.voices.iter().map(|v| v.amplitude).collect();
+    /// assert_eq!(amplitudes, vec![1., 0.5, 1.]);
+    /// ```
+    pub fn with_amplitudes(mut self, amplitudes: &[f32]) -> Self {
+        for (voice, amplitude) in self.voices.iter_mut().zip(amplitudes) {
+            voice.amplitude = *amplitude;
+        }
+        self
+    }
+
+    /// Returns the drone sounding for the given number of seconds, rather than until
+    /// stopped.
+    pub fn sustained_for(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+
+    fn from_cents<I: Iterator<Item = f64>>(cents: I) -> Self {
+        Self::new(
+            cents
+                .map(|cents| DroneVoice {
+                    cents,
+                    amplitude: 1.,
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Play for Drone {
+    /// Sustains every voice of the drone together, for the drone's duration, or until
+    /// the player is stopped.
+    ///
+    /// Panics if the player is a [recorder][Player::recorder] and the drone has no
+    /// duration, as it would never finish recording.
+    fn play(&self, player: &Player) {
+        let root = Frequency(player.config.root);
+        let voices: Vec<(f32, f32)> = self
+            .voices
+            .iter()
+            .map(|voice| (root.transpose(voice.cents).0, voice.amplitude))
+            .collect();
+        player.play_drone(&voices, self.duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn from_ratios() {
+        let drone = Drone::from_ratios(&[Ratio::new(1, 1), Ratio::new(3, 2)]).sustained_for(5.);
+
+        assert_eq!(drone.voices[1].cents, Ratio::new(3, 2).cents());
+        assert_eq!(drone.duration, Some(5.));
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    fn play_for_duration() {
+        use crate::play::{PlaybackConfig, CHANNELS, SAMPLE_RATE};
+
+        let player = Player::recorder(PlaybackConfig::default());
+        let drone = Drone::from_harmonics(&[1, 3]).with_amplitudes(&[1., 0.]);
+        drone.sustained_for(0.1).play(&player);
+        let quiet = Player::recorder(PlaybackConfig::default());
+        Drone::from_harmonics(&[1]).sustained_for(0.1).play(&quiet);
+
+        assert_eq!(
+            player.samples().len(),
+            SAMPLE_RATE as usize / 10 * CHANNELS as usize
+        );
+        assert_eq!(player.samples(), quiet.samples());
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    #[should_panic(expected = "cannot record a drone")]
+    fn recording_an_endless_drone() {
+        let player = Player::recorder(crate::play::PlaybackConfig::default());
+        Drone::from_harmonics(&[1]).play(&player);
+    }
+}
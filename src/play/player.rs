@@ -0,0 +1,677 @@
+//! Playback of sounds through the audio output device, or into a buffer of samples.
+use super::{
+    difference_tones,
+    source::{create_endless_note, create_note, create_retunable_note, Panned},
+    ChordStyle, PlaybackConfig, CHANNELS, SAMPLE_RATE,
+};
+use crate::{pitch::Frequency, ratio::Ratio};
+use num::traits::PrimInt;
+use rodio::{dynamic_mixer, source::UniformSourceIterator, OutputStream, Sink, Source};
+use std::{
+    cell::RefCell,
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// Trait to allow playback using [rodio](https://docs.rs/rodio).
+pub trait Play {
+    /// Play using the given [Player].
+    fn play(&self, player: &Player);
+
+    /// Play using a new [Player] with the given [PlaybackConfig].
+    fn play_with(&self, config: &PlaybackConfig) {
+        self.play(&Player::new(*config));
+    }
+
+    /// Play on a background thread using a new [Player] with the given [PlaybackConfig],
+    /// returning immediately with a [PlaybackHandle] to stop or wait for the playback.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rust_intonation::{Ratio, play::{Play, PlaybackConfig}};
+    /// let handle = Ratio::new(7, 4).play_async(&PlaybackConfig::default());
+    /// // ...
+    /// handle.stop();
+    /// handle.wait();
+    /// ```
+    fn play_async(&self, config: &PlaybackConfig) -> PlaybackHandle
+    where
+        Self: Clone + Send + 'static,
+    {
+        let playable = self.clone();
+        let config = *config;
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+
+        let thread = std::thread::spawn(move || {
+            let mut player = Player::new(config);
+            player.stopped = thread_stopped;
+            playable.play(&player);
+        });
+
+        PlaybackHandle { stopped, thread }
+    }
+}
+
+/// Alternates between playing `a` and `b` the given number of times, with a gap of the
+/// given number of seconds between each sound.
+///
+/// If `stereo` is set, `a` is played in the left ear and `b` in the right, each
+/// placed there whole rather than [spread](PlaybackConfig::spread) from left to right.
+/// Otherwise both are played with the player's [PlaybackConfig] as it is.
+///
+/// ## Example
+///
+/// Compare a JI major third with its 12 EDO equivalent three times
+///
+/// ```rust,no_run
+/// # use rust_intonation::{Ratio, interval::TwelveEDOInterval, play::{compare, Player}};
+/// let mut player = Player::default();
+/// compare(&mut player, &Ratio::new(5, 4), &TwelveEDOInterval::MajorThird, 3, 0.5, false);
+/// ```
+pub fn compare<A: Play + ?Sized, B: Play + ?Sized>(
+    player: &mut Player,
+    a: &A,
+    b: &B,
+    repeats: u32,
+    gap: f32,
+    stereo: bool,
+) {
+    let config = player.config;
+    let split = |pan: f32| PlaybackConfig {
+        pan,
+        spread: false,
+        ..config
+    };
+
+    for repeat in 0..repeats {
+        if repeat > 0 {
+            player.rest_for(gap);
+        }
+        if stereo {
+            player.config = split(-1.);
+        }
+        a.play(player);
+        player.rest_for(gap);
+        if stereo {
+            player.config = split(1.);
+        }
+        b.play(player);
+    }
+
+    player.config = config;
+}
+
+/// A handle to playback started by [Play::play_async].
+pub struct PlaybackHandle {
+    stopped: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl PlaybackHandle {
+    /// Stop the playback as soon as possible, without waiting for it to finish.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true once the playback has finished or been stopped.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Block until the playback has finished or been stopped.
+    pub fn wait(self) {
+        self.thread.join().unwrap();
+    }
+}
+
+/// Owns an audio output stream, so that successive sounds can be played without
+/// reopening the audio device for each one.
+///
+/// A player can also [record][Player::recorder] the sounds it is given into a buffer
+/// of samples instead of playing them aloud, or [describe][Player::log_to] each sound
+/// as it is given.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use rust_intonation::{Ratio, play::{Play, Player}};
+/// let player = Player::default();
+/// Ratio::new(3, 2).play(&player);
+/// Ratio::new(5, 4).play(&player);
+/// ```
+pub struct Player {
+    output: Output,
+    stopped: Arc<AtomicBool>,
+    log: Option<RefCell<Box<dyn Write>>>,
+    pub config: PlaybackConfig,
+}
+
+/// Where a [Player] sends the sounds it is given.
+enum Output {
+    Speakers { _stream: OutputStream, sink: Sink },
+    Buffer(RefCell<Vec<f32>>),
+    Silent,
+}
+
+impl Player {
+    /// Open the default audio output device for playback with the given [PlaybackConfig].
+    ///
+    /// Panics if no audio output device is available.
+    pub fn new(config: PlaybackConfig) -> Self {
+        let (_stream, stream_handle) = OutputStream::try_default().unwrap();
+        let sink = Sink::try_new(&stream_handle).unwrap();
+
+        Self {
+            output: Output::Speakers { _stream, sink },
+            stopped: Arc::new(AtomicBool::new(false)),
+            log: None,
+            config,
+        }
+    }
+
+    /// Create a player that records the sounds it is given, as interleaved stereo samples
+    /// at [SAMPLE_RATE], rather than playing them. Does not require an audio output
+    /// device.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::play::{PlaybackConfig, Player, SAMPLE_RATE};
+    /// let player = Player::recorder(PlaybackConfig {
+    ///     duration: 0.5,
+    ///     ..PlaybackConfig::default()
+    /// });
+    /// player.play_note(440.);
+    /// assert_eq!(player.samples().len(), SAMPLE_RATE as usize);
+    /// ```
+    pub fn recorder(config: PlaybackConfig) -> Self {
+        Self {
+            output: Output::Buffer(RefCell::new(vec![])),
+            stopped: Arc::new(AtomicBool::new(false)),
+            log: None,
+            config,
+        }
+    }
+
+    /// Create a player that neither plays nor records the sounds it is given, and
+    /// returns immediately rather than waiting for them to finish. Does not require an
+    /// audio output device.
+    ///
+    /// Combined with [Player::log_to], it prints what would have been played.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, play::{Play, PlaybackConfig, Player}};
+    /// let player = Player::dry_run(PlaybackConfig::default()).log_to(std::io::stdout());
+    /// Ratio::new(3, 2).play(&player);
+    /// ```
+    pub fn dry_run(config: PlaybackConfig) -> Self {
+        Self {
+            output: Output::Silent,
+            stopped: Arc::new(AtomicBool::new(false)),
+            log: None,
+            config,
+        }
+    }
+
+    /// Describe each sound and rest the player is given on a line of the given writer,
+    /// with tab-separated fields: `play`, the frequency of each note, e.g. `261.63 Hz`,
+    /// and the length of the sound, e.g. `1 s`, or `rest` and the length of the rest.
+    pub fn log_to<W: Write + 'static>(mut self, writer: W) -> Self {
+        self.log = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Returns the samples recorded so far by a [recorder][Player::recorder]. Any other
+    /// player records no samples.
+    pub fn samples(&self) -> Vec<f32> {
+        match &self.output {
+            Output::Speakers { .. } | Output::Silent => vec![],
+            Output::Buffer(samples) => samples.borrow().clone(),
+        }
+    }
+
+    /// Stop anything currently playing, and ignore any further sounds the player is
+    /// given.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true if the player has been stopped.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    /// Play a single note at the given frequency, blocking until it has finished.
+    pub fn play_note(&self, freq: f32) {
+        self.play_sequence(&[freq]);
+    }
+
+    /// Play two notes at the given frequencies simultaneously, blocking until they have
+    /// finished.
+    pub fn play_dyad(&self, root_freq: f32, interval_freq: f32) {
+        self.play_simultaneously(&[root_freq, interval_freq], &self.config);
+    }
+
+    /// Play two notes at the given frequencies simultaneously for the given number of
+    /// seconds, rather than the duration in the player's [PlaybackConfig].
+    pub fn play_dyad_for(&self, root_freq: f32, interval_freq: f32, duration: f32) {
+        let config = PlaybackConfig {
+            duration,
+            ..self.config
+        };
+        self.play_simultaneously(&[root_freq, interval_freq], &config);
+    }
+
+    /// Play each of the given ratios above the given root frequency, in the
+    /// [ChordStyle] given in the player's [PlaybackConfig], blocking until they have
+    /// finished.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rust_intonation::{Ratio, play::Player};
+    /// let player = Player::default();
+    /// let ratios = [4, 5, 6, 7].map(|h| Ratio::new(h, 4));
+    /// player.play_chord(220., &ratios);
+    /// ```
+    pub fn play_chord<T: PrimInt>(&self, root_hz: f32, ratios: &[Ratio<T>]) {
+        let mut freqs: Vec<f32> = ratios.iter().map(|r| (Frequency(root_hz) * *r).0).collect();
+
+        match self.config.chord_style {
+            ChordStyle::Block => self.play_simultaneously(&freqs, &self.config),
+            ChordStyle::Ascending => {
+                freqs.sort_by(f32::total_cmp);
+                self.play_sequence(&freqs);
+            }
+            ChordStyle::Descending => {
+                freqs.sort_by(|a, b| b.total_cmp(a));
+                self.play_sequence(&freqs);
+            }
+            ChordStyle::Strum(delay) => {
+                freqs.sort_by(f32::total_cmp);
+                self.play_staggered(&freqs, delay, &self.config);
+            }
+        }
+    }
+
+    /// Play notes at each of the given frequencies one after another, blocking until
+    /// they have all finished.
+    pub fn play_sequence(&self, freqs: &[f32]) {
+        for freq in freqs {
+            self.describe(&[*freq], self.config.duration);
+            self.output(Panned::new(
+                create_note(*freq, &self.config),
+                self.config.pan,
+            ));
+        }
+    }
+
+    /// Sustain notes at each of the given frequencies together, each paired with its
+    /// amplitude, from 0 to 1, relative to the volume in the player's [PlaybackConfig],
+    /// for the given number of seconds, or until the player is stopped if none is given.
+    ///
+    /// Panics if the player is a [recorder][Player::recorder] and no duration is given.
+    pub fn play_drone(&self, voices: &[(f32, f32)], duration: Option<f32>) {
+        assert!(
+            duration.is_some() || !matches!(self.output, Output::Buffer(_)),
+            "cannot record a drone that sounds until stopped"
+        );
+        let freqs: Vec<f32> = voices.iter().map(|(freq, _)| *freq).collect();
+        match duration {
+            Some(duration) => self.describe(&freqs, duration),
+            None => self.describe_for(&freqs, "until stopped".to_string()),
+        }
+        let (controller, mixer) = dynamic_mixer::mixer(CHANNELS, SAMPLE_RATE);
+        for (i, (freq, amplitude)) in voices.iter().enumerate() {
+            let config = PlaybackConfig {
+                volume: self.config.volume * amplitude,
+                duration: duration.unwrap_or(self.config.duration),
+                ..self.config
+            };
+            let pan = pan(&config, i, voices.len());
+            match duration {
+                Some(_) => controller.add(Panned::new(create_note(*freq, &config), pan)),
+                None => controller.add(Panned::new(create_endless_note(*freq, &config), pan)),
+            }
+        }
+        self.output(mixer);
+    }
+
+    /// Sustain a note at the root together with a second note the shared number of cents
+    /// above it, retuning the second note whenever the number changes, until the player
+    /// is stopped.
+    ///
+    /// Panics if the player is a [recorder][Player::recorder].
+    pub(crate) fn play_comparison(&self, offset: &Arc<AtomicU64>) {
+        assert!(
+            !matches!(self.output, Output::Buffer(_)),
+            "cannot record a comparison that sounds until stopped"
+        );
+        let root = self.config.root;
+        let cents = f64::from_bits(offset.load(Ordering::Relaxed));
+        let freqs = [root, Frequency(root).transpose(cents).0];
+        self.describe_for(&freqs, "until stopped".to_string());
+        let (controller, mixer) = dynamic_mixer::mixer(CHANNELS, SAMPLE_RATE);
+        let (left, right) = (pan(&self.config, 0, 2), pan(&self.config, 1, 2));
+        controller.add(Panned::new(create_endless_note(root, &self.config), left));
+        let note = create_retunable_note(root, offset.clone(), &self.config);
+        controller.add(Panned::new(note, right));
+        self.output(mixer);
+    }
+
+    /// Wait for the gap given in the player's [PlaybackConfig].
+    pub fn rest(&self) {
+        self.rest_for(self.config.gap);
+    }
+
+    /// Wait for the given number of seconds.
+    pub fn rest_for(&self, seconds: f32) {
+        if self.is_stopped() {
+            return;
+        }
+        if let Some(log) = &self.log {
+            writeln!(log.borrow_mut(), "rest\t{} s", seconds).ok();
+        }
+        match &self.output {
+            Output::Speakers { .. } => {
+                let end = Instant::now() + Duration::from_secs_f32(seconds);
+                while Instant::now() < end && !self.is_stopped() {
+                    std::thread::sleep(POLL_INTERVAL.min(end - Instant::now()));
+                }
+            }
+            Output::Buffer(samples) => {
+                let silence = (seconds * SAMPLE_RATE as f32) as usize * CHANNELS as usize;
+                samples
+                    .borrow_mut()
+                    .extend(std::iter::repeat_n(0., silence));
+            }
+            Output::Silent => {}
+        }
+    }
+
+    fn play_simultaneously(&self, freqs: &[f32], config: &PlaybackConfig) {
+        self.play_staggered(freqs, 0., config);
+    }
+
+    /// Plays the given frequencies together, each starting the given number of seconds
+    /// after the one before it, with any difference tones starting with the last.
+    fn play_staggered(&self, freqs: &[f32], delay: f32, config: &PlaybackConfig) {
+        let onset = |i: usize| Duration::from_secs_f32(delay * i as f32);
+        let last = onset(freqs.len().saturating_sub(1));
+        self.describe(freqs, config.duration + last.as_secs_f32());
+        let (controller, mixer) = dynamic_mixer::mixer(CHANNELS, SAMPLE_RATE);
+        for (i, freq) in freqs.iter().enumerate() {
+            let pan = pan(config, i, freqs.len());
+            controller.add(Panned::new(create_note(*freq, config).delay(onset(i)), pan));
+        }
+
+        if config.difference_tones > 0. {
+            let difference_config = PlaybackConfig {
+                volume: config.volume * config.difference_tones,
+                ..*config
+            };
+            let freqs: Vec<f64> = freqs.iter().map(|f| *f as f64).collect();
+            for tone in difference_tones(&freqs) {
+                controller.add(Panned::new(
+                    create_note(tone as f32, &difference_config).delay(last),
+                    config.pan,
+                ));
+            }
+        }
+
+        self.output(mixer);
+    }
+
+    fn output<S>(&self, source: S)
+    where
+        S: Source<Item = f32> + Send + 'static,
+    {
+        if self.is_stopped() {
+            return;
+        }
+        match &self.output {
+            Output::Speakers { sink, .. } => {
+                sink.append(source);
+                while !sink.empty() {
+                    if self.is_stopped() {
+                        sink.stop();
+                        return;
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+            Output::Buffer(samples) => {
+                let source: UniformSourceIterator<S, f32> =
+                    UniformSourceIterator::new(source, CHANNELS, SAMPLE_RATE);
+                samples.borrow_mut().extend(source);
+            }
+            Output::Silent => {}
+        }
+    }
+
+    /// Writes the frequencies and length of a sound to the log, if there is one.
+    fn describe(&self, freqs: &[f32], duration: f32) {
+        self.describe_for(freqs, format!("{} s", duration));
+    }
+
+    /// Writes the frequencies of a sound to the log, if there is one, with a description
+    /// of its length.
+    fn describe_for(&self, freqs: &[f32], length: String) {
+        if self.is_stopped() {
+            return;
+        }
+        if let Some(log) = &self.log {
+            let freqs: Vec<String> = freqs.iter().map(|f| format!("{:.2} Hz", f)).collect();
+            writeln!(log.borrow_mut(), "play\t{}\t{}", freqs.join(", "), length).ok();
+        }
+    }
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new(PlaybackConfig::default())
+    }
+}
+
+/// Returns the stereo position of the `i`th of the given number of notes played together,
+/// spread from left to right if the [PlaybackConfig] has [spread](PlaybackConfig::spread)
+/// set.
+fn pan(config: &PlaybackConfig, i: usize, count: usize) -> f32 {
+    if config.spread && count > 1 {
+        2. * i as f32 / (count - 1) as f32 - 1.
+    } else {
+        config.pan
+    }
+}
+
+/// How often a [Player] checks whether it has been stopped while playing.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chord::Chord, play::Envelope, play::Waveform};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn stopped_player_ignores_sounds() {
+        let player = Player::recorder(PlaybackConfig::default());
+        player.play_note(440.);
+        let samples = player.samples().len();
+
+        player.stop();
+        player.play_dyad(440., 660.);
+        player.rest();
+
+        assert!(player.is_stopped());
+        assert_eq!(player.samples().len(), samples);
+    }
+
+    struct Log(std::rc::Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Log {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn dry_run_describes_sounds() {
+        let log = std::rc::Rc::new(RefCell::new(vec![]));
+        let player = Player::dry_run(PlaybackConfig::default()).log_to(Log(log.clone()));
+        player.play_note(440.);
+        player.rest_for(0.5);
+        player.play_dyad_for(440., 660., 1.);
+
+        assert_eq!(
+            String::from_utf8(log.borrow().clone()).unwrap(),
+            "play\t440.00 Hz\t2 s\nrest\t0.5 s\nplay\t440.00 Hz, 660.00 Hz\t1 s\n"
+        );
+        assert!(player.samples().is_empty());
+    }
+
+    #[test]
+    fn difference_tones_are_mixed_in() {
+        let config = PlaybackConfig {
+            duration: 0.1,
+            envelope: Envelope::none(),
+            ..PlaybackConfig::default()
+        };
+        let with_tones = PlaybackConfig {
+            difference_tones: 0.5,
+            ..config
+        };
+
+        let player = Player::recorder(config);
+        player.play_dyad(4800., 7200.);
+        let tones_player = Player::recorder(with_tones);
+        tones_player.play_dyad(4800., 7200.);
+
+        // A quarter of the way through a period of the 2400Hz difference tone
+        let without = player.samples()[10];
+        let with = tones_player.samples()[10];
+        assert!((with - without - 0.1).abs() < 0.001);
+    }
+
+    #[test]
+    fn spread_dyads() {
+        let config = PlaybackConfig {
+            duration: 0.1,
+            envelope: Envelope::none(),
+            waveform: Waveform::Square,
+            spread: true,
+            ..PlaybackConfig::default()
+        };
+        let player = Player::recorder(config);
+        player.play_dyad(12000., 24000.);
+
+        // The first half period of the 12000Hz root sounds only on the left, and of the
+        // 24000Hz interval only on the right.
+        let samples = player.samples();
+        assert_eq!(&samples[0..4], &[0.2, 0.2, 0.2, -0.2]);
+    }
+
+    #[test]
+    fn compare_alternates() {
+        let config = PlaybackConfig {
+            duration: 0.1,
+            gap: 0.05,
+            ..PlaybackConfig::default()
+        };
+        let mut player = Player::recorder(config);
+        compare(
+            &mut player,
+            &Chord::new(vec![Ratio::new(1, 1)]),
+            &Chord::new(vec![Ratio::new(3, 2)]),
+            2,
+            0.05,
+            false,
+        );
+
+        // Each chord plays its note alone, rests, and then plays it "together", and a gap
+        // follows all but the last chord
+        let note = 4800 * CHANNELS as usize;
+        let gap = 2400 * CHANNELS as usize;
+        assert_eq!(player.samples().len(), 4 * (2 * note + gap) + 3 * gap);
+        assert_eq!(player.config, config);
+    }
+
+    #[test]
+    fn chord_styles() {
+        let ratios = [Ratio::new(5, 4), Ratio::new(1, 1), Ratio::new(3, 2)];
+        let note = 4800 * CHANNELS as usize;
+        let play = |chord_style| {
+            let log = std::rc::Rc::new(RefCell::new(vec![]));
+            let player = Player::recorder(PlaybackConfig {
+                duration: 0.1,
+                chord_style,
+                ..PlaybackConfig::default()
+            })
+            .log_to(Log(log.clone()));
+            player.play_chord(400., &ratios);
+            let log = String::from_utf8(log.borrow().clone()).unwrap();
+            (player.samples().len(), log)
+        };
+
+        assert_eq!(
+            play(ChordStyle::Block),
+            (
+                note,
+                "play\t500.00 Hz, 400.00 Hz, 600.00 Hz\t0.1 s\n".to_string()
+            )
+        );
+        assert_eq!(
+            play(ChordStyle::Ascending),
+            (
+                3 * note,
+                "play\t400.00 Hz\t0.1 s\nplay\t500.00 Hz\t0.1 s\nplay\t600.00 Hz\t0.1 s\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            play(ChordStyle::Descending).1,
+            "play\t600.00 Hz\t0.1 s\nplay\t500.00 Hz\t0.1 s\nplay\t400.00 Hz\t0.1 s\n"
+        );
+        assert_eq!(
+            play(ChordStyle::Strum(0.05)),
+            (
+                2 * note,
+                "play\t400.00 Hz, 500.00 Hz, 600.00 Hz\t0.2 s\n".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn compare_stereo_pans_each_side() {
+        let mut player = Player::recorder(PlaybackConfig {
+            duration: 0.1,
+            ..PlaybackConfig::default()
+        });
+        compare(
+            &mut player,
+            &Chord::new(vec![Ratio::new(3, 2)]),
+            &Chord::new(vec![Ratio::new(3, 2)]),
+            1,
+            0.,
+            true,
+        );
+
+        let samples = player.samples();
+        let (a, b) = samples.split_at(samples.len() / 2);
+        assert!(a.chunks(2).all(|frame| frame[1] == 0.));
+        assert!(b.chunks(2).all(|frame| frame[0] == 0.));
+        assert!(!player.config.spread);
+    }
+}
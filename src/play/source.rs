@@ -0,0 +1,319 @@
+//! [Sources](rodio::Source) synthesizing the notes that are played.
+use super::{Envelope, PlaybackConfig, Waveform, CHANNELS, SAMPLE_RATE};
+use rodio::source::{Amplify, Source, TakeDuration};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+/// An infinite [Source] playing a note at a single frequency with a given [Waveform].
+///
+/// The phase is carried over from sample to sample, rather than found from the number
+/// of samples played, so the note stays in tune however long it sounds.
+#[derive(Clone, Debug)]
+pub(crate) struct Oscillator {
+    freq: f32,
+    waveform: Waveform,
+    phase: f64,
+}
+
+impl Oscillator {
+    pub(crate) fn new(freq: f32, waveform: Waveform) -> Self {
+        Self {
+            freq,
+            waveform,
+            phase: 0.,
+        }
+    }
+}
+
+impl Iterator for Oscillator {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let value = self.waveform.value(self.phase as f32, self.freq);
+        self.phase = (self.phase + self.freq as f64 / SAMPLE_RATE as f64).fract();
+        Some(value)
+    }
+}
+
+impl Source for Oscillator {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// An infinite [Source] like [Oscillator], playing a note a shared number of cents
+/// above a base frequency, which can be changed while it plays.
+///
+/// The phase is carried over from sample to sample, so the note is retuned without a
+/// click.
+#[derive(Clone, Debug)]
+pub(crate) struct Retunable {
+    base: f32,
+    /// The bits of the offset, in cents, as an [f64].
+    offset: Arc<AtomicU64>,
+    bits: Option<u64>,
+    freq: f32,
+    waveform: Waveform,
+    phase: f32,
+}
+
+impl Retunable {
+    pub(crate) fn new(base: f32, offset: Arc<AtomicU64>, waveform: Waveform) -> Self {
+        Self {
+            base,
+            offset,
+            bits: None,
+            freq: base,
+            waveform,
+            phase: 0.,
+        }
+    }
+}
+
+impl Iterator for Retunable {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let bits = self.offset.load(Ordering::Relaxed);
+        if self.bits != Some(bits) {
+            self.bits = Some(bits);
+            self.freq = self.base * 2f64.powf(f64::from_bits(bits) / 1200.) as f32;
+        }
+        let value = self.waveform.value(self.phase, self.freq);
+        self.phase = (self.phase + self.freq / SAMPLE_RATE as f32).fract();
+        Some(value)
+    }
+}
+
+impl Source for Retunable {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A [Source] applying an [Envelope] to a note of a given duration.
+#[derive(Clone, Debug)]
+pub(crate) struct Enveloped<S> {
+    source: S,
+    envelope: Envelope,
+    duration: f32,
+    num_sample: usize,
+}
+
+impl<S: Source<Item = f32>> Iterator for Enveloped<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let time = self.num_sample as f32 / self.source.sample_rate() as f32;
+        self.num_sample += 1;
+        self.source
+            .next()
+            .map(|s| s * self.envelope.gain(time, self.duration))
+    }
+}
+
+impl<S: Source<Item = f32>> Source for Enveloped<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.source.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.source.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.source.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.source.total_duration()
+    }
+}
+
+/// A stereo [Source] placing a mono source at a position in the stereo field, from `-1`
+/// (left) to `1` (right).
+///
+/// A centered source plays at full volume in both channels, and is faded out of the
+/// opposite channel as it moves to one side.
+#[derive(Clone, Debug)]
+pub(crate) struct Panned<S> {
+    source: S,
+    gains: [f32; 2],
+    current: Option<f32>,
+    channel: usize,
+}
+
+impl<S: Source<Item = f32>> Panned<S> {
+    pub(crate) fn new(source: S, pan: f32) -> Self {
+        let pan = pan.clamp(-1., 1.);
+        Self {
+            source,
+            gains: [(1. - pan).min(1.), (1. + pan).min(1.)],
+            current: None,
+            channel: 0,
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for Panned<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            self.current = self.source.next();
+        }
+        let sample = self.current.map(|s| s * self.gains[self.channel]);
+        self.channel = (self.channel + 1) % 2;
+        sample
+    }
+}
+
+impl<S: Source<Item = f32>> Source for Panned<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        CHANNELS
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.source.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.source.total_duration()
+    }
+}
+
+pub(crate) fn create_note(
+    freq: f32,
+    config: &PlaybackConfig,
+) -> Amplify<Enveloped<TakeDuration<Oscillator>>> {
+    Enveloped {
+        source: Oscillator::new(freq, config.waveform)
+            .take_duration(Duration::from_secs_f32(config.duration)),
+        envelope: config.envelope,
+        duration: config.duration,
+        num_sample: 0,
+    }
+    .amplify(config.volume)
+}
+
+/// Creates a note that sounds until it is stopped, fading in as it starts.
+pub(crate) fn create_endless_note(
+    freq: f32,
+    config: &PlaybackConfig,
+) -> Amplify<Enveloped<Oscillator>> {
+    Enveloped {
+        source: Oscillator::new(freq, config.waveform),
+        envelope: config.envelope,
+        duration: f32::INFINITY,
+        num_sample: 0,
+    }
+    .amplify(config.volume)
+}
+
+/// Creates a note that sounds until it is stopped, fading in as it starts, the shared
+/// number of cents above the given frequency.
+pub(crate) fn create_retunable_note(
+    base: f32,
+    offset: Arc<AtomicU64>,
+    config: &PlaybackConfig,
+) -> Amplify<Enveloped<Retunable>> {
+    Enveloped {
+        source: Retunable::new(base, offset, config.waveform),
+        envelope: config.envelope,
+        duration: f32::INFINITY,
+        num_sample: 0,
+    }
+    .amplify(config.volume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn panning() {
+        let note = || SamplesBuffer::new(1, SAMPLE_RATE, vec![1., -1.]);
+
+        let centered: Vec<f32> = Panned::new(note(), 0.).collect();
+        assert_eq!(centered, vec![1., 1., -1., -1.]);
+
+        let left: Vec<f32> = Panned::new(note(), -1.).collect();
+        assert_eq!(left, vec![1., 0., -1., 0.]);
+
+        let half_right: Vec<f32> = Panned::new(note(), 0.5).collect();
+        assert_eq!(half_right, vec![0.5, 1., -0.5, -1.]);
+    }
+
+    #[test]
+    fn notes_fade_in_and_out() {
+        let note: Vec<f32> = create_note(440., &PlaybackConfig::default()).collect();
+
+        assert!(note.len().abs_diff(2 * SAMPLE_RATE as usize) <= 1);
+        assert_eq!(note[0], 0.);
+        assert!(note[note.len() - 1].abs() < 0.001);
+        assert!(note.iter().any(|s| (s.abs() - 0.2).abs() < 0.001));
+    }
+
+    #[test]
+    fn retuning() {
+        let offset = Arc::new(AtomicU64::new(1200f64.to_bits()));
+        let mut note = Retunable::new(440., offset.clone(), Waveform::Sine);
+        let octave = Oscillator::new(880., Waveform::Sine);
+
+        for (a, b) in note.by_ref().zip(octave).take(100) {
+            assert!((a - b).abs() < 0.001);
+        }
+        offset.store(0f64.to_bits(), Ordering::Relaxed);
+        note.next();
+        assert_eq!(note.freq, 440.);
+    }
+
+    #[test]
+    fn oscillator_stays_in_tune() {
+        // Past 2^24 samples, a sample count no longer converts exactly to an f32
+        let start = 1 << 24;
+        let mut note = Oscillator::new(440., Waveform::Sine);
+        for _ in 0..start {
+            note.next();
+        }
+
+        for (i, sample) in note.take(100).enumerate() {
+            let phase = (440. * (start + i) as f64 / SAMPLE_RATE as f64).fract();
+            let expected = (std::f64::consts::TAU * phase).sin() as f32;
+            assert!((sample - expected).abs() < 0.001, "{} {}", sample, expected);
+        }
+    }
+}
@@ -2,16 +2,22 @@
 //!
 
 use crate::{
+    error::IntonationError,
     interval::Approximate12EDOInterval,
-    math::{greatest_prime_factor, normalize_pair, reduce},
-    play::{play_dyad, play_interval, Play},
+    math::{const_normalize, try_normalize},
+    numtheory::{factorize, gcd, greatest_prime_factor},
 };
-use num::traits::PrimInt;
-use std::time::Duration;
-use std::{
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::{Frequency, Pitch},
+    play::{Play, Player},
+};
+use alloc::{vec, vec::Vec};
+use core::{
     fmt::Display,
     ops::{Div, Mul, Neg},
 };
+use num::traits::{Euclid, Float, PrimInt};
 
 /// Models a ratio of two integral types, defining an interval in just intonation.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -37,10 +43,43 @@ impl<T: PrimInt> Ratio<T> {
     /// assert_eq!(r.numer, 7);
     /// assert_eq!(r.denom, 4);
     /// ```
+    ///
+    /// Panics if either integer is zero or negative, or if normalizing the fraction
+    /// overflows. See [Ratio::try_new] for a version that returns an error instead.
     pub fn new(numer: T, denom: T) -> Self {
-        let (numer, denom) = normalize_pair(numer, denom);
-        let (numer, denom) = reduce(numer, denom);
-        Self { numer, denom }
+        Self::try_new(numer, denom).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Construct a new [Ratio] from two integers, as [Ratio::new], but returning an
+    /// [IntonationError] rather than panicking if they do not describe an interval.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, Ratio};
+    /// assert_eq!(Ratio::try_new(6, 4), Ok(Ratio::new(3, 2)));
+    /// assert_eq!(Ratio::try_new(3, 0), Err(IntonationError::ZeroDenominator));
+    /// assert_eq!(Ratio::try_new(-3, 2), Err(IntonationError::NonPositiveRatio));
+    /// assert_eq!(Ratio::try_new(1, i32::MAX), Err(IntonationError::Overflow));
+    /// ```
+    ///
+    /// The fraction is reduced before it is normalized, so only a result that does not
+    /// fit the integer type overflows.
+    ///
+    /// ```rust
+    /// # use rust_intonation::Ratio;
+    /// assert_eq!(Ratio::try_new(1 << 30, 3 << 29), Ok(Ratio::new(4, 3)));
+    /// assert_eq!(Ratio::try_new(1, 1i64 << 62), Ok(Ratio::new(1, 1)));
+    /// ```
+    pub fn try_new(numer: T, denom: T) -> Result<Self, IntonationError> {
+        if denom == T::zero() {
+            return Err(IntonationError::ZeroDenominator);
+        }
+        if numer <= T::zero() || denom < T::zero() {
+            return Err(IntonationError::NonPositiveRatio);
+        }
+        let (numer, denom) = try_normalize(numer, denom).ok_or(IntonationError::Overflow)?;
+        Ok(Self { numer, denom })
     }
 
     /// Returns the [Ratio] that, when multiplied by the given argument, gives `2/1`.
@@ -53,8 +92,13 @@ impl<T: PrimInt> Ratio<T> {
     /// assert_eq!(r.complement(), Ratio::new(4, 3));
     /// ```
     pub fn complement(&self) -> Self {
+        self.try_complement()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_complement(&self) -> Result<Self, IntonationError> {
         let two: T = num::cast(2).unwrap();
-        Self::new(two, num::one()) / *self
+        Self::try_new(two, num::one())?.try_div(*self)
     }
 
     /// Raises the given [Ratio] to the given integral power
@@ -69,14 +113,67 @@ impl<T: PrimInt> Ratio<T> {
     /// assert_eq!(r.pow(2), Ratio::new(9, 8));
     /// assert_eq!(r.pow(-2), Ratio::new(16, 9));
     /// ```
+    ///
+    /// Panics if the result overflows. See [Ratio::try_pow] for a version that returns
+    /// an error instead.
     pub fn pow(&self, exp: i32) -> Self {
-        match exp {
-            0 => Self::new(num::one(), num::one()),
-            e if e < 0 => self.complement().pow(-exp),
-            _ => Self::new(self.numer.pow(exp as u32), self.denom.pow(exp as u32)),
+        self.try_pow(exp).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Raises the given [Ratio] to the given integral power, as [Ratio::pow], but
+    /// returning an [IntonationError] rather than panicking if the result overflows.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, Ratio};
+    /// assert_eq!(Ratio::new(3, 2).try_pow(-2), Ok(Ratio::new(16, 9)));
+    /// assert_eq!(Ratio::new(3, 2).try_pow(30), Err(IntonationError::Overflow));
+    /// ```
+    pub fn try_pow(&self, exp: i32) -> Result<Self, IntonationError> {
+        let base = if exp < 0 {
+            self.try_complement()?
+        } else {
+            *self
+        };
+        let exp = exp.unsigned_abs() as usize;
+        let numer = num::checked_pow(base.numer, exp).ok_or(IntonationError::Overflow)?;
+        let denom = num::checked_pow(base.denom, exp).ok_or(IntonationError::Overflow)?;
+        Self::try_new(numer, denom)
+    }
+
+    /// Multiplies two ratios, stacking the intervals they describe, as the `*` operator
+    /// does, but returning an [IntonationError] rather than panicking if the result
+    /// overflows.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, Ratio};
+    /// assert_eq!(Ratio::new(3, 2).try_mul(Ratio::new(5, 4)), Ok(Ratio::new(15, 8)));
+    /// assert_eq!(
+    ///     Ratio::new(65_537, 65_536).try_mul(Ratio::new(65_537, 65_536)),
+    ///     Err(IntonationError::Overflow)
+    /// );
+    /// ```
+    pub fn try_mul(&self, rhs: Ratio<T>) -> Result<Self, IntonationError> {
+        let numer = self.numer.checked_mul(&rhs.numer);
+        let denom = self.denom.checked_mul(&rhs.denom);
+        match (numer, denom) {
+            (Some(numer), Some(denom)) => Self::try_new(numer, denom),
+            _ => Err(IntonationError::Overflow),
         }
     }
 
+    /// Divides one ratio by another, as the `/` operator does, but returning an
+    /// [IntonationError] rather than panicking if the result overflows.
+    pub fn try_div(&self, rhs: Ratio<T>) -> Result<Self, IntonationError> {
+        self.try_mul(Self {
+            numer: rhs.denom,
+            denom: rhs.numer,
+        })
+    }
+
     /// Converts the ratio into a tuple pair of an equal tempered interval and
     /// the number of cents difference between the ET interval and the JI ratio
     ///
@@ -96,6 +193,131 @@ impl<T: PrimInt> Ratio<T> {
         (*self).into()
     }
 
+    /// Returns the size of the ratio in cents
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// let r = Ratio::new(3, 2);
+    /// assert!((r.cents() - 701.955).abs() < 0.001);
+    /// ```
+    pub fn cents(&self) -> f64 {
+        1200. * self.to_float::<f64>().log2()
+    }
+
+    /// Returns the value of the ratio as the given floating point type
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// let r = Ratio::new(5, 4);
+    /// assert_eq!(r.to_float::<f64>(), 1.25);
+    /// assert_eq!(r.to_float::<f32>(), 1.25);
+    /// ```
+    pub fn to_float<F: Float>(&self) -> F {
+        let n: F = num::cast(self.numer).unwrap();
+        let d: F = num::cast(self.denom).unwrap();
+        n / d
+    }
+
+    /// Returns the value of the ratio as an `f32`, the precision samples and
+    /// [Frequencies][crate::Frequency] are played at.
+    pub fn to_f32(&self) -> f32 {
+        self.to_float()
+    }
+
+    /// Returns whether the value of the ratio is within `epsilon` of the given value
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// let r = Ratio::new(3, 2);
+    /// assert!(r.approx_eq(1.5, 1e-12));
+    /// assert!(r.approx_eq(1.498f32, 0.01));
+    /// assert!(!r.approx_eq(2f64.powf(7. / 12.), 1e-3));
+    /// ```
+    pub fn approx_eq<F: Float>(&self, value: F, epsilon: F) -> bool {
+        (self.to_float::<F>() - value).abs() <= epsilon
+    }
+
+    /// Returns the Tenney height of the ratio, `log2(n * d)`, a measure of its complexity
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// let r = Ratio::new(3, 2);
+    /// assert_eq!(r.tenney_height(), 6f64.log2());
+    /// ```
+    pub fn tenney_height(&self) -> f64 {
+        let n: f64 = num::cast(self.numer).unwrap();
+        let d: f64 = num::cast(self.denom).unwrap();
+        (n * d).log2()
+    }
+
+    /// Returns the rate, in Hz, at which the dyad formed by the ratio above the given root
+    /// frequency beats.
+    ///
+    /// Beats are heard between the nearly coincident partials of the two notes. The
+    /// pair of partials, among the first eight of each note, that are closest in
+    /// frequency is used, so a simple JI ratio does not beat at all.
+    ///
+    /// ## Example
+    ///
+    /// The Pythagorean major third `81/64` beats against the `5/4` formed by the fifth
+    /// partial of the root and the fourth partial of the third
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// assert_eq!(Ratio::new(5, 4).beat_rate(256.), 0.);
+    /// assert_eq!(Ratio::new(81, 64).beat_rate(256.), 16.);
+    /// ```
+    pub fn beat_rate(&self, root_hz: f64) -> f64 {
+        let r: f64 = self.to_float();
+        let mismatch = (1..=BEAT_PARTIAL_LIMIT)
+            .map(|partial| {
+                let upper = partial as f64 * r;
+                (upper - upper.round()).abs()
+            })
+            .fold(f64::INFINITY, f64::min);
+        root_hz * mismatch
+    }
+
+    /// Returns the first-order difference tone, in Hz, of the dyad formed by the ratio
+    /// above the given root frequency.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// assert_eq!(Ratio::new(5, 4).difference_tone(400.), 100.);
+    /// ```
+    pub fn difference_tone(&self, root_hz: f64) -> f64 {
+        let r: f64 = self.to_float();
+        root_hz * (r - 1.)
+    }
+
+    /// Plays the dyad formed by the ratio, sustained long enough to hear several of the
+    /// beats between its partials. See [Ratio::beat_rate].
+    ///
+    /// Sine waves have no upper partials to beat, so this is best heard with a
+    /// [Waveform](crate::play::Waveform) other than a sine wave.
+    #[cfg(feature = "audio")]
+    pub fn play_beats(&self, player: &Player) {
+        let root = player.config.root;
+        let beat_rate = self.beat_rate(root as f64) as f32;
+        let duration = if beat_rate > 0. {
+            (BEATS_TO_HEAR / beat_rate).clamp(player.config.duration, MAX_BEAT_DURATION)
+        } else {
+            player.config.duration
+        };
+
+        player.play_dyad_for(root, (Frequency(root) * *self).0, duration);
+    }
+
     /// Finds the prime limit of the ratio
     ///
     /// ## Examples
@@ -112,27 +334,120 @@ impl<T: PrimInt> Ratio<T> {
     /// assert_eq!(r.limit(), 5);
     /// ```
     pub fn limit(&self) -> T {
-        greatest_prime_factor(self.numer).max(greatest_prime_factor(self.denom))
+        // Octaves of `1/1`, which has no prime factors, are 2-limit
+        let two = num::cast(2).unwrap();
+        greatest_prime_factor(self.numer)
+            .max(greatest_prime_factor(self.denom))
+            .max(two)
+    }
+
+    /// Returns the prime factorization of the ratio, as each prime with its exponent,
+    /// positive in the numerator and negative in the denominator, in ascending order of
+    /// prime.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// let r = Ratio::new(81, 80);
+    /// assert_eq!(r.factors(), vec![(2, -4), (3, 4), (5, -1)]);
+    /// ```
+    pub fn factors(&self) -> Vec<(T, i32)> {
+        let numer = factorize(self.numer)
+            .into_iter()
+            .map(|(p, e)| (p, e as i32));
+        let denom = factorize(self.denom)
+            .into_iter()
+            .map(|(p, e)| (p, -(e as i32)));
+        let mut factors: Vec<(T, i32)> = numer.chain(denom).collect();
+        factors.sort_by_key(|(p, _)| *p);
+        factors
+    }
+
+    /// Returns the size, in cents, of the interval from the given ratio up to this one,
+    /// taken within half an octave either way, so that near-identical intervals differ
+    /// by a small amount whichever side of the octave they lie.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// let comma = Ratio::new(10, 9).difference(&Ratio::new(9, 8));
+    /// assert!((comma + 21.506).abs() < 0.001);
+    ///
+    /// let comma = Ratio::new(1, 1).difference(&Ratio::new(160, 81));
+    /// assert!((comma - 21.506).abs() < 0.001);
+    /// ```
+    pub fn difference(&self, other: &Ratio<T>) -> f64 {
+        let cents = (*self / *other).cents();
+        if cents > 600. {
+            cents - 1200.
+        } else {
+            cents
+        }
     }
 }
 
-impl<T: PrimInt> Play for Ratio<T> {
-    fn play(&self) {
-        // let middle_c = 261.625565;
-        let middle_c = 440. * 2.0_f32.powf(-9. / 12.);
-        let r: f64 = self.into();
-        let ratio_freq = middle_c * r as f32;
+impl Ratio<i32> {
+    /// Returns every ratio with a denominator no greater than `max_denominator` that lies
+    /// within `tolerance` cents of the given size, taken within the octave, ordered from
+    /// simplest to most complex by [Tenney height][Ratio::tenney_height].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::ratio::Ratio;
+    /// let ratios = Ratio::approximations(386., 15., 32);
+    /// assert_eq!(ratios[..3], [Ratio::new(5, 4), Ratio::new(31, 25), Ratio::new(34, 27)]);
+    /// ```
+    pub fn approximations(cents: f64, tolerance: f64, max_denominator: i32) -> Vec<Self> {
+        let cents = Euclid::rem_euclid(&cents, &1200.);
+        let (low, high) = (
+            2f64.powf((cents - tolerance) / 1200.),
+            2f64.powf((cents + tolerance) / 1200.),
+        );
+
+        let mut ratios: Vec<Self> = vec![];
+        for d in 1..=max_denominator {
+            let first = (low * d as f64).ceil().max(1.) as i32;
+            let last = (high * d as f64).floor() as i32;
+            for n in (first..=last).filter(|n| gcd(*n, d) == 1) {
+                let ratio = Ratio::new(n, d);
+                if !ratios.contains(&ratio) {
+                    ratios.push(ratio);
+                }
+            }
+        }
+        ratios.sort_by(|a, b| a.tenney_height().total_cmp(&b.tenney_height()));
+        ratios
+    }
+}
+
+/// The number of partials of each note considered when finding the beat rate of a dyad.
+const BEAT_PARTIAL_LIMIT: u32 = 8;
+
+/// The number of beats [Ratio::play_beats] sustains a dyad long enough to hear.
+#[cfg(feature = "audio")]
+const BEATS_TO_HEAR: f32 = 8.;
 
-        play_interval(middle_c, ratio_freq);
+/// The longest, in seconds, [Ratio::play_beats] will sustain a dyad.
+#[cfg(feature = "audio")]
+const MAX_BEAT_DURATION: f32 = 20.;
 
-        std::thread::sleep(Duration::from_secs_f32(0.25));
+#[cfg(feature = "audio")]
+impl<T: PrimInt> Play for Ratio<T> {
+    fn play(&self, player: &Player) {
+        let root = player.config.root;
+        let ratio_freq = Pitch::new(Frequency(root), *self).frequency().0;
 
-        play_dyad(middle_c, ratio_freq);
+        player.play_sequence(&[root, ratio_freq]);
+        player.rest();
+        player.play_dyad(root, ratio_freq);
     }
 }
 
-impl<T: PrimInt + std::fmt::Display> Display for Ratio<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T: PrimInt + core::fmt::Display> Display for Ratio<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}/{}", self.numer, self.denom)
     }
 }
@@ -149,7 +464,7 @@ impl<T: PrimInt> Mul<Ratio<T>> for Ratio<T> {
     type Output = Self;
 
     fn mul(self, rhs: Ratio<T>) -> Self::Output {
-        Self::new(self.numer * rhs.numer, self.denom * rhs.denom)
+        self.try_mul(rhs).unwrap_or_else(|err| panic!("{}", err))
     }
 }
 
@@ -157,18 +472,102 @@ impl<T: PrimInt> Div<Ratio<T>> for Ratio<T> {
     type Output = Self;
 
     fn div(self, rhs: Ratio<T>) -> Self::Output {
-        Self::new(self.numer * rhs.denom, self.denom * rhs.numer)
+        self.try_div(rhs).unwrap_or_else(|err| panic!("{}", err))
     }
 }
 
 impl<T: PrimInt> From<&Ratio<T>> for f64 {
     fn from(value: &Ratio<T>) -> Self {
-        let n: f64 = num::cast(value.numer).unwrap();
-        let d: f64 = num::cast(value.denom).unwrap();
-        n / d
+        value.to_float()
+    }
+}
+
+impl<T: PrimInt> From<Ratio<T>> for f64 {
+    fn from(value: Ratio<T>) -> Self {
+        value.to_float()
+    }
+}
+
+impl<T: PrimInt> From<Ratio<T>> for f32 {
+    fn from(value: Ratio<T>) -> Self {
+        value.to_float()
     }
 }
 
+macro_rules! const_ratio {
+    ($($t:ty)*) => {
+        $(
+            impl Ratio<$t> {
+                /// Construct a new [Ratio], normalized into the octave and reduced, as
+                /// [Ratio::new] does, but usable in `const` contexts, e.g. to define
+                /// interval constants.
+                ///
+                /// ## Example
+                ///
+                /// ```rust
+                /// # use rust_intonation::Ratio;
+                #[doc = concat!("const TRITONE: Ratio<", stringify!($t), "> = Ratio::<", stringify!($t), ">::const_new(14, 5);")]
+                /// assert_eq!(TRITONE, Ratio::new(7, 5));
+                /// ```
+                ///
+                /// Panics, failing compilation in a `const` context, if either integer is
+                /// zero or negative, or if normalizing the fraction overflows.
+                pub const fn const_new(numer: $t, denom: $t) -> Self {
+                    assert!(
+                        numer > 0 && denom > 0,
+                        "a ratio must have a positive numerator and denominator"
+                    );
+                    Self::const_from_u128(numer as u128, denom as u128)
+                }
+
+                /// Multiplies two ratios, as the `*` operator does, in `const` contexts.
+                ///
+                /// ## Example
+                ///
+                /// ```rust
+                /// # use rust_intonation::Ratio;
+                #[doc = concat!("const FIFTH: Ratio<", stringify!($t), "> = Ratio::<", stringify!($t), ">::const_new(3, 2);")]
+                #[doc = concat!("const NINTH: Ratio<", stringify!($t), "> = FIFTH.const_mul(FIFTH);")]
+                /// assert_eq!(NINTH, Ratio::new(9, 8));
+                /// ```
+                pub const fn const_mul(self, rhs: Self) -> Self {
+                    let numer = (self.numer as u128).checked_mul(rhs.numer as u128);
+                    let denom = (self.denom as u128).checked_mul(rhs.denom as u128);
+                    match (numer, denom) {
+                        (Some(numer), Some(denom)) => Self::const_from_u128(numer, denom),
+                        _ => panic!("the result overflows its integer type"),
+                    }
+                }
+
+                /// Divides one ratio by another, as the `/` operator does, in `const`
+                /// contexts.
+                pub const fn const_div(self, rhs: Self) -> Self {
+                    self.const_mul(Self {
+                        numer: rhs.denom,
+                        denom: rhs.numer,
+                    })
+                }
+
+                const fn const_from_u128(numer: u128, denom: u128) -> Self {
+                    match const_normalize(numer, denom) {
+                        Some((numer, denom))
+                            if numer <= <$t>::MAX as u128 && denom <= <$t>::MAX as u128 =>
+                        {
+                            Self {
+                                numer: numer as $t,
+                                denom: denom as $t,
+                            }
+                        }
+                        _ => panic!("the result overflows its integer type"),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+const_ratio! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
 impl<T: PrimInt> From<(T, T)> for Ratio<T> {
     fn from(value: (T, T)) -> Self {
         let (n, d) = value;
@@ -182,6 +581,16 @@ mod tests {
     use crate::interval::TwelveEDOInterval;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn approximations_across_the_octave() {
+        assert_eq!(
+            Ratio::approximations(1195., 10., 10),
+            vec![Ratio::new(1, 1)]
+        );
+        assert_eq!(Ratio::approximations(-500., 2., 10), vec![Ratio::new(3, 2)]);
+        assert!(Ratio::approximations(50., 1., 10).is_empty());
+    }
+
     #[test]
     fn new_simple_ratio() {
         let r = Ratio::new(3, 2);
@@ -244,6 +653,33 @@ mod tests {
         assert_eq!(r1.pow(-2), Ratio::new(16, 9));
     }
 
+    #[test]
+    fn fallible_operations() {
+        assert_eq!(Ratio::try_new(0, 4), Err(IntonationError::NonPositiveRatio));
+        assert_eq!(
+            Ratio::try_new(3, -2),
+            Err(IntonationError::NonPositiveRatio)
+        );
+        assert_eq!(
+            Ratio::new(3, 2).try_div(Ratio::new(5, 4)),
+            Ok(Ratio::new(6, 5))
+        );
+        assert_eq!(
+            Ratio::new(3, 2).try_pow(-19),
+            Err(IntonationError::Overflow)
+        );
+        assert_eq!(
+            Ratio::new(3i64, 2).try_pow(30),
+            Ok(Ratio::new(205_891_132_094_649, 140_737_488_355_328))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "a ratio cannot have a zero denominator")]
+    fn new_panics_with_the_error() {
+        Ratio::new(1, 0);
+    }
+
     #[test]
     fn to_modified_et_interval() {
         let r = Ratio::new(3, 2);
@@ -258,6 +694,24 @@ mod tests {
         assert_eq!(Ratio::from(t), Ratio::new(3, 2));
     }
 
+    #[test]
+    fn beat_rate() {
+        assert_eq!(Ratio::new(3, 2).beat_rate(200.), 0.);
+        assert_eq!(Ratio::new(7, 4).beat_rate(200.), 0.);
+        assert_eq!(Ratio::new(81, 64).beat_rate(256.), 16.);
+        assert_eq!(Ratio::new(17, 16).beat_rate(160.), 10.);
+
+        let et_fifth = 2f64.powf(7. / 12.);
+        let beats = (2. * et_fifth - 3.).abs() * 200.;
+        assert!((Ratio::new(433, 289).beat_rate(200.) - beats).abs() < 0.1);
+    }
+
+    #[test]
+    fn difference_tone() {
+        assert_eq!(Ratio::new(3, 2).difference_tone(200.), 100.);
+        assert_eq!(Ratio::new(1, 1).difference_tone(200.), 0.);
+    }
+
     #[test]
     fn limit() {
         assert_eq!(Ratio::new(3, 2).limit(), 3);
@@ -265,6 +719,57 @@ mod tests {
         assert_eq!(Ratio::new(8, 5).limit(), 5);
     }
 
+    #[test]
+    fn float_conversions() {
+        let r = Ratio::new(7, 4);
+
+        assert_eq!(f64::from(r), 1.75);
+        assert_eq!(f32::from(r), 1.75);
+        assert_eq!(f64::from(&r), 1.75);
+        assert_eq!(r.to_f32(), 1.75);
+        assert!(Ratio::new(81, 80).approx_eq(1.0125, f64::EPSILON));
+        assert!(!Ratio::new(81, 80).approx_eq(1., 0.01));
+    }
+
+    #[test]
+    fn const_constructors() {
+        const THIRD: Ratio<u8> = Ratio::<u8>::const_new(5, 4);
+        const SIXTH: Ratio<i64> = Ratio::<i64>::const_new(10, 12);
+
+        assert_eq!(THIRD, Ratio::new(5, 4));
+        assert_eq!(SIXTH, Ratio::new(5, 3));
+        assert_eq!(
+            SIXTH.const_div(Ratio::<i64>::const_new(5, 4)),
+            Ratio::new(4, 3)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn const_mul_can_overflow() {
+        let r = Ratio::<u8>::const_new(17, 16);
+        let _r2 = r.const_mul(r);
+    }
+
+    #[test]
+    fn factors() {
+        assert_eq!(Ratio::new(1, 1).factors(), vec![]);
+        assert_eq!(Ratio::new(7, 6).factors(), vec![(2, -1), (3, -1), (7, 1)]);
+
+        // 15 digit terms, each the product of two large primes
+        let r: Ratio<i64> = Ratio::new(999_999_937 * 999_983, 999_999_929 * 999_979);
+        assert_eq!(
+            r.factors(),
+            vec![
+                (999_979, -1),
+                (999_983, 1),
+                (999_999_929, -1),
+                (999_999_937, 1)
+            ]
+        );
+        assert_eq!(r.limit(), 999_999_937);
+    }
+
     #[test]
     #[should_panic]
     fn i32_can_overflow() {
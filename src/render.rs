@@ -0,0 +1,91 @@
+//! Offline rendering of anything that can be [played][Play] into WAV files, via
+//! [hound](https://docs.rs/hound).
+use crate::play::{Play, PlaybackConfig, Player, CHANNELS, SAMPLE_RATE};
+use hound::{SampleFormat, WavSpec, WavWriter};
+use std::path::Path;
+
+/// Trait to allow rendering the sounds made by [Play] into a WAV file, rather than
+/// playing them aloud. Implemented for every type that implements [Play].
+pub trait Render {
+    /// Render the same sounds that [Play::play_with] would play with the given
+    /// [PlaybackConfig] into a stereo WAV file at the given path.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rust_intonation::{Ratio, play::PlaybackConfig, render::Render};
+    /// Ratio::new(3, 2)
+    ///     .render_to_wav("fifth.wav", &PlaybackConfig::default())
+    ///     .unwrap();
+    /// ```
+    fn render_to_wav<P: AsRef<Path>>(&self, path: P, config: &PlaybackConfig) -> hound::Result<()>;
+}
+
+impl<T: Play + ?Sized> Render for T {
+    fn render_to_wav<P: AsRef<Path>>(&self, path: P, config: &PlaybackConfig) -> hound::Result<()> {
+        write_wav(path, &render(self, config))
+    }
+}
+
+/// Returns the interleaved stereo samples, at [SAMPLE_RATE], of the sounds that [Play::play_with] would play
+/// with the given [PlaybackConfig].
+pub fn render<T: Play + ?Sized>(playable: &T, config: &PlaybackConfig) -> Vec<f32> {
+    let player = Player::recorder(*config);
+    playable.play(&player);
+    player.samples()
+}
+
+/// Write the given interleaved stereo samples, at [SAMPLE_RATE], into a 16-bit WAV file
+/// at the given path.
+pub fn write_wav<P: AsRef<Path>>(path: P, samples: &[f32]) -> hound::Result<()> {
+    let spec = WavSpec {
+        channels: CHANNELS,
+        sample_rate: SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec)?;
+    for sample in samples {
+        writer.write_sample((sample.clamp(-1., 1.) * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+    use pretty_assertions::assert_eq;
+
+    fn short_config() -> PlaybackConfig {
+        PlaybackConfig {
+            duration: 0.1,
+            gap: 0.05,
+            ..PlaybackConfig::default()
+        }
+    }
+
+    #[test]
+    fn render_ratio() {
+        let samples = render(&Ratio::new(3, 2), &short_config());
+
+        // Two notes in sequence, a gap, and then the dyad
+        assert_eq!(samples.len(), 2 * (4800 * 3 + 2400));
+        assert!(samples.iter().all(|s| s.abs() <= 0.4));
+    }
+
+    #[test]
+    fn render_to_wav() {
+        let path = std::env::temp_dir().join("rust-intonation-render-test.wav");
+        Ratio::new(5, 4)
+            .render_to_wav(&path, &short_config())
+            .unwrap();
+
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().sample_rate, SAMPLE_RATE);
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.len(), 2 * (4800 * 3 + 2400));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
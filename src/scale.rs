@@ -0,0 +1,665 @@
+//! Provides a struct for working with scales made up of JI [Ratios][Ratio] and
+//! tempered pitches, repeating at a period.
+mod comparison;
+mod kbm;
+mod propriety;
+mod scala;
+
+pub use comparison::{DegreeComparison, ScaleComparison};
+pub use kbm::{KbmError, KeyboardMapping};
+pub use propriety::Propriety;
+pub use scala::SclError;
+
+use crate::{diamond::Diamond, lattice::Lattice, ratio::Ratio, temperaments::mos::Mos, Edo};
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::Frequency,
+    play::{Play, Player, ScaleDirection},
+};
+use num::traits::PrimInt;
+use std::{fmt::Display, ops::RangeInclusive};
+
+/// A single degree of a [Scale], given either as a JI ratio or as a size in cents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Degree<T: PrimInt = i32> {
+    Ratio(Ratio<T>),
+    Cents(f64),
+}
+
+impl<T: PrimInt> Degree<T> {
+    /// Returns the size of the degree above the root of the scale, in cents.
+    pub fn cents(&self) -> f64 {
+        match self {
+            Self::Ratio(ratio) => ratio.cents(),
+            Self::Cents(cents) => *cents,
+        }
+    }
+}
+
+impl<T: PrimInt> From<Ratio<T>> for Degree<T> {
+    fn from(ratio: Ratio<T>) -> Self {
+        Self::Ratio(ratio)
+    }
+}
+
+impl<T: PrimInt + Display> Display for Degree<T> {
+    /// Ratios are written as `n/d`, and cents always with a decimal point, as in a
+    /// Scala file.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ratio(ratio) => write!(f, "{}", ratio),
+            Self::Cents(cents) => write!(f, "{:.5}", cents),
+        }
+    }
+}
+
+/// The interval at which a [Scale] repeats.
+///
+/// Given separately from the degrees of the scale, since a [Ratio] is always reduced
+/// to within the octave, and so cannot itself represent the octave, or a larger
+/// period such as the `3/1` tritave.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Period {
+    Ratio(u32, u32),
+    Cents(f64),
+}
+
+impl Period {
+    /// Returns the size of the period, in cents.
+    pub fn cents(&self) -> f64 {
+        match self {
+            Self::Ratio(n, d) => 1200. * (*n as f64 / *d as f64).log2(),
+            Self::Cents(cents) => *cents,
+        }
+    }
+}
+
+impl Default for Period {
+    /// Repeats at the octave.
+    fn default() -> Self {
+        Self::Ratio(2, 1)
+    }
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ratio(n, d) => write!(f, "{}/{}", n, d),
+            Self::Cents(cents) => write!(f, "{:.5}", cents),
+        }
+    }
+}
+
+/// Models a scale as its degrees, in ascending order from the root, repeating at a
+/// period.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, scale::{Degree, Scale}};
+/// let scale = Scale::from_ratios(&[Ratio::new(3, 2), Ratio::new(5, 4)]);
+/// assert_eq!(
+///     scale.degrees,
+///     vec![
+///         Degree::Ratio(Ratio::new(1, 1)),
+///         Degree::Ratio(Ratio::new(5, 4)),
+///         Degree::Ratio(Ratio::new(3, 2))
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scale<T: PrimInt = i32> {
+    /// A one-line description of the scale.
+    pub description: String,
+    /// The degrees of the scale, starting with the root.
+    pub degrees: Vec<Degree<T>>,
+    pub period: Period,
+}
+
+/// Tolerance, in cents, within which two degrees are considered the same pitch.
+const DEGREE_TOLERANCE: f64 = 1e-6;
+
+impl<T: PrimInt> Scale<T> {
+    /// Construct a new [Scale] from the given degrees and period.
+    ///
+    /// The degrees are sorted into ascending order, with duplicates removed, and a
+    /// `1/1` root is added if none is given.
+    pub fn new(description: &str, degrees: Vec<Degree<T>>, period: Period) -> Self {
+        let mut degrees = degrees;
+        degrees.insert(0, Degree::Ratio(Ratio::new(num::one(), num::one())));
+        degrees.sort_by(|a, b| a.cents().total_cmp(&b.cents()));
+        degrees.dedup_by(|a, b| (a.cents() - b.cents()).abs() < DEGREE_TOLERANCE);
+
+        Self {
+            description: description.to_string(),
+            degrees,
+            period,
+        }
+    }
+
+    /// Returns the number of degrees in each period of the scale.
+    pub fn len(&self) -> usize {
+        self.degrees.len()
+    }
+
+    /// Returns whether the scale has no degrees.
+    pub fn is_empty(&self) -> bool {
+        self.degrees.is_empty()
+    }
+
+    /// Returns the given degree of the scale, wrapping around into the periods above
+    /// and below, so that, e.g., degree `-1` is the last degree of the scale.
+    pub fn degree(&self, index: i32) -> Degree<T> {
+        self.degrees[index.rem_euclid(self.len() as i32) as usize]
+    }
+
+    /// Returns the size, in cents, of the given degree above the root, continuing into
+    /// the periods above and below the one the degrees are given in.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale};
+    /// let scale = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+    /// assert!((scale.cents_at(4) - 1586.314).abs() < 0.001);
+    /// assert!((scale.cents_at(-1) - -498.045).abs() < 0.001);
+    /// ```
+    pub fn cents_at(&self, degree: i32) -> f64 {
+        let size = self.degrees.len() as i32;
+        let periods = degree.div_euclid(size) as f64;
+        periods * self.period.cents() + self.degrees[degree.rem_euclid(size) as usize].cents()
+    }
+
+    /// Returns the size, in cents, of each step of the scale, from each degree to the
+    /// next, ending with the step from the last degree up to the period.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Scale};
+    /// let steps = Scale::from_edo(Edo::new(4)).steps();
+    /// assert_eq!(steps, vec![300., 300., 300., 300.]);
+    /// ```
+    pub fn steps(&self) -> Vec<f64> {
+        (0..self.len() as i32)
+            .map(|i| self.cents_at(i + 1) - self.cents_at(i))
+            .collect()
+    }
+
+    /// Returns the mode of the scale starting on the given degree.
+    ///
+    /// Degrees are measured from the new root as ratios when both they and the new
+    /// root are ratios, and the scale repeats at the octave, and otherwise in cents.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale};
+    /// let major = Scale::from_ratios(&[
+    ///     Ratio::new(9, 8),
+    ///     Ratio::new(5, 4),
+    ///     Ratio::new(4, 3),
+    ///     Ratio::new(3, 2),
+    ///     Ratio::new(5, 3),
+    ///     Ratio::new(15, 8),
+    /// ]);
+    /// let minor = major.mode(5);
+    /// assert_eq!(minor.to_string(), "1/1 9/8 6/5 27/20 3/2 8/5 9/5");
+    /// ```
+    pub fn mode(&self, degree: i32) -> Self {
+        let root = self.degree(degree);
+        let octave = self.period == Period::default();
+        let degrees = (0..self.len() as i32)
+            .map(|i| match (self.degree(degree + i), root) {
+                (Degree::Ratio(a), Degree::Ratio(b)) if octave => Degree::Ratio(a / b),
+                (a, b) => Degree::Cents((a.cents() - b.cents()).rem_euclid(self.period.cents())),
+            })
+            .collect();
+
+        Self {
+            description: format!("{}, mode {}", self.description, degree),
+            degrees,
+            period: self.period,
+        }
+    }
+
+    /// Returns every mode of the scale, starting from each of its degrees in turn.
+    pub fn modes(&self) -> Vec<Self> {
+        (0..self.len() as i32).map(|i| self.mode(i)).collect()
+    }
+
+    /// Returns the matrix of intervals, in cents, between the degrees of the scale. Row
+    /// `i` gives the size of the intervals from degree `i` up to each of the next degrees
+    /// in turn, ending with the period, so that each row describes a mode of the scale.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Scale};
+    /// let matrix = Scale::from_edo(Edo::new(3)).interval_matrix();
+    /// assert_eq!(
+    ///     matrix,
+    ///     vec![
+    ///         vec![0., 400., 800., 1200.],
+    ///         vec![0., 400., 800., 1200.],
+    ///         vec![0., 400., 800., 1200.],
+    ///     ]
+    /// );
+    /// ```
+    pub fn interval_matrix(&self) -> Vec<Vec<f64>> {
+        (0..self.len() as i32)
+            .map(|i| {
+                (0..=self.len() as i32)
+                    .map(|j| self.cents_at(i + j) - self.cents_at(i))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the intervals of the [interval matrix][Scale::interval_matrix] as
+    /// [Degrees][Degree], measured as ratios between degrees given as ratios in a scale
+    /// that repeats at the octave, and otherwise in cents, as for [mode][Scale::mode].
+    ///
+    /// The last column of the interval matrix, the period, is left out, as the octave
+    /// cannot be given as a [Ratio].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale, scale::Degree};
+    /// let scale = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+    /// assert_eq!(
+    ///     scale.ratio_matrix()[1],
+    ///     vec![
+    ///         Degree::Ratio(Ratio::new(1, 1)),
+    ///         Degree::Ratio(Ratio::new(6, 5)),
+    ///         Degree::Ratio(Ratio::new(8, 5))
+    ///     ]
+    /// );
+    /// ```
+    pub fn ratio_matrix(&self) -> Vec<Vec<Degree<T>>> {
+        self.modes().into_iter().map(|mode| mode.degrees).collect()
+    }
+
+    /// Returns every pair of degrees given as ratios whose [difference][Ratio::difference]
+    /// is smaller than the given number of cents, as the indices of the two degrees and
+    /// the comma between them, taken upwards from the lower of the two.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale};
+    /// let scale = Scale::from_ratios(&[
+    ///     Ratio::new(10, 9),
+    ///     Ratio::new(9, 8),
+    ///     Ratio::new(5, 4),
+    ///     Ratio::new(81, 64),
+    /// ]);
+    /// assert_eq!(
+    ///     scale.find_commas_between(25.),
+    ///     vec![(1, 2, Ratio::new(81, 80)), (3, 4, Ratio::new(81, 80))]
+    /// );
+    /// ```
+    pub fn find_commas_between(&self, max_cents: f64) -> Vec<(usize, usize, Ratio<T>)> {
+        let mut commas = vec![];
+        for (i, low) in self.degrees.iter().enumerate() {
+            for (j, high) in self.degrees.iter().enumerate().skip(i + 1) {
+                if let (Degree::Ratio(low), Degree::Ratio(high)) = (low, high) {
+                    let difference = high.difference(low);
+                    if difference.abs() < max_cents {
+                        let comma = if difference < 0. {
+                            *low / *high
+                        } else {
+                            *high / *low
+                        };
+                        commas.push((i, j, comma));
+                    }
+                }
+            }
+        }
+        commas
+    }
+
+    /// Construct a new octave-repeating [Scale] from the given ratios.
+    pub fn from_ratios(ratios: &[Ratio<T>]) -> Self {
+        let degrees: Vec<Degree<T>> = ratios.iter().map(|r| Degree::Ratio(*r)).collect();
+        let mut scale = Self::new("", degrees, Period::default());
+        scale.description = format!("{} note JI scale", scale.degrees.len());
+        scale
+    }
+
+    /// Construct a new [Scale] from the ratios found in the region of the lattice
+    /// spanned by the given range of indices along each of its dimensions.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Lattice, LatticeDimension, LatticeDimensionBounds::*, Ratio, scale::Scale};
+    /// let lattice = Lattice::new(vec![
+    ///     LatticeDimension::new(Ratio::new(3, 2), Infinite),
+    ///     LatticeDimension::new(Ratio::new(5, 4), Infinite),
+    /// ]);
+    /// let scale = Scale::from_lattice_region(&lattice, &[-1..=1, 0..=1]);
+    /// assert_eq!(scale.degrees.len(), 6);
+    /// ```
+    pub fn from_lattice_region(lattice: &Lattice<T>, ranges: &[RangeInclusive<i32>]) -> Self {
+        Self::from_ratios(&lattice.region(ranges))
+    }
+}
+
+impl<T: PrimInt + Display> Scale<T> {
+    /// Construct a new [Scale] from the segment of the harmonic series from the `from`th
+    /// up to the `to`th harmonic, reduced to within the octave above the first.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Scale;
+    /// let overtones = Scale::harmonic_segment(8, 16);
+    /// assert_eq!(overtones.description, "harmonics 8-16");
+    /// assert_eq!(overtones.to_string(), "1/1 9/8 5/4 11/8 3/2 13/8 7/4 15/8");
+    /// ```
+    pub fn harmonic_segment(from: T, to: T) -> Self {
+        let ratios: Vec<Ratio<T>> = num::range_inclusive(from, to)
+            .map(|h| Ratio::new(h, from))
+            .collect();
+        let mut scale = Self::from_ratios(&ratios);
+        scale.description = format!("harmonics {}-{}", from, to);
+        scale
+    }
+
+    /// Construct a new [Scale] from the segment of the subharmonic series from the
+    /// `from`th down to the `to`th subharmonic, reduced to within the octave above the
+    /// last, the mirror image of [Scale::harmonic_segment].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Scale;
+    /// let undertones = Scale::subharmonic_segment(4, 8);
+    /// assert_eq!(undertones.to_string(), "1/1 8/7 4/3 8/5");
+    /// ```
+    pub fn subharmonic_segment(from: T, to: T) -> Self {
+        let ratios: Vec<Ratio<T>> = num::range_inclusive(from, to)
+            .map(|h| Ratio::new(to, h))
+            .collect();
+        let mut scale = Self::from_ratios(&ratios);
+        scale.description = format!("subharmonics {}-{}", from, to);
+        scale
+    }
+}
+
+impl Scale<i32> {
+    /// Construct a new [Scale] from every ratio in the given tonality diamond.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{diamond::Diamond, scale::Scale};
+    /// let scale = Scale::from_diamond(&Diamond::<i32>::new(vec![1, 3, 5]));
+    /// assert_eq!(scale.description, "1 3 5 tonality diamond");
+    /// assert_eq!(scale.degrees.len(), 7);
+    /// ```
+    pub fn from_diamond<U: PrimInt>(diamond: &Diamond<U>) -> Self {
+        let ratios: Vec<Ratio<i32>> = diamond.ratios().iter().flatten().copied().collect();
+        let limits: Vec<String> = diamond.limits().iter().map(|l| l.to_string()).collect();
+
+        let mut scale = Self::from_ratios(&ratios);
+        scale.description = format!("{} tonality diamond", limits.join(" "));
+        scale
+    }
+
+    /// Construct a new [Scale] from the degrees of the given
+    /// [moment-of-symmetry scale](crate::temperaments::mos), in cents.
+    pub fn from_mos(mos: &Mos) -> Self {
+        let degrees = mos.degrees.iter().map(|d| Degree::Cents(*d)).collect();
+        let period = mos.steps.iter().sum();
+
+        Self::new(&mos.to_string(), degrees, Period::Cents(period))
+    }
+
+    /// Construct a new [Scale] from every step of the given EDO, in cents.
+    pub fn from_edo(edo: Edo) -> Self {
+        let degrees = (0..edo.divisions)
+            .map(|step| Degree::Cents(step as f64 * edo.step_size()))
+            .collect();
+        let (n, d) = edo.period;
+        let description = format!("{} equal divisions of {}/{}", edo.divisions, n, d);
+
+        Self::new(&description, degrees, Period::Ratio(n, d))
+    }
+}
+
+#[cfg(feature = "audio")]
+impl<T: PrimInt> Play for Scale<T> {
+    /// Plays each degree of the scale, including the period, in the
+    /// [direction][ScaleDirection] given in the player's
+    /// [PlaybackConfig](crate::play::PlaybackConfig).
+    ///
+    /// ## Example
+    ///
+    /// Play a pentatonic scale up and back down at 90 beats per minute
+    ///
+    /// ```rust,no_run
+    /// # use rust_intonation::{play::{Play, PlaybackConfig, ScaleDirection}, Ratio, Scale};
+    /// let scale = Scale::from_ratios(&[
+    ///     Ratio::new(9, 8),
+    ///     Ratio::new(5, 4),
+    ///     Ratio::new(3, 2),
+    ///     Ratio::new(5, 3),
+    /// ]);
+    /// scale.play_with(&PlaybackConfig {
+    ///     scale_direction: ScaleDirection::UpDown,
+    ///     ..PlaybackConfig::default().at_tempo(90.)
+    /// });
+    /// ```
+    fn play(&self, player: &Player) {
+        if self.is_empty() {
+            return;
+        }
+        let top = self.len() as i32;
+        let degrees: Vec<i32> = match player.config.scale_direction {
+            ScaleDirection::Ascending => (0..=top).collect(),
+            ScaleDirection::Descending => (0..=top).rev().collect(),
+            ScaleDirection::UpDown => (0..=top).chain((0..top).rev()).collect(),
+        };
+        let root = Frequency(player.config.root);
+        let freqs: Vec<f32> = degrees
+            .into_iter()
+            .map(|degree| root.transpose(self.cents_at(degree)).0)
+            .collect();
+        player.play_sequence(&freqs);
+    }
+}
+
+impl<T: PrimInt + Display> Display for Scale<T> {
+    /// Lists the degrees of the scale, separated by spaces.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let degrees: Vec<String> = self.degrees.iter().map(|d| d.to_string()).collect();
+        write!(f, "{}", degrees.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn new_sorts_and_dedups_degrees() {
+        let scale: Scale = Scale::new(
+            "test",
+            vec![
+                Degree::Cents(700.),
+                Degree::Ratio(Ratio::new(5, 4)),
+                Degree::Ratio(Ratio::new(10, 8)),
+                Degree::Cents(0.),
+            ],
+            Period::default(),
+        );
+
+        assert_eq!(
+            scale.degrees,
+            vec![
+                Degree::Ratio(Ratio::new(1, 1)),
+                Degree::Ratio(Ratio::new(5, 4)),
+                Degree::Cents(700.)
+            ]
+        );
+    }
+
+    #[test]
+    fn from_edo() {
+        let scale = Scale::from_edo(Edo::with_period(13, (3, 1)));
+
+        assert_eq!(scale.description, "13 equal divisions of 3/1");
+        assert_eq!(scale.degrees.len(), 13);
+        assert_eq!(scale.period, Period::Ratio(3, 1));
+        assert!((scale.period.cents() - 1901.955).abs() < 0.001);
+    }
+
+    #[test]
+    fn commas_across_the_octave_and_skipping_cents() {
+        let scale: Scale = Scale::new(
+            "",
+            vec![
+                Degree::Ratio(Ratio::new(160, 81)),
+                Degree::Cents(5.),
+                Degree::Ratio(Ratio::new(128, 125)),
+            ],
+            Period::default(),
+        );
+
+        assert_eq!(
+            scale.find_commas_between(50.),
+            vec![(0, 2, Ratio::new(128, 125)), (0, 3, Ratio::new(81, 80))]
+        );
+        assert!(scale.find_commas_between(20.).is_empty());
+    }
+
+    #[test]
+    fn degrees_wrap_around() {
+        let scale = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+
+        assert_eq!(scale.len(), 3);
+        assert_eq!(scale.degree(-1), Degree::Ratio(Ratio::new(3, 2)));
+        assert_eq!(scale.degree(4), Degree::Ratio(Ratio::new(5, 4)));
+    }
+
+    #[test]
+    fn steps() {
+        let scale = Scale::from_ratios(&[Ratio::new(9, 8), Ratio::new(5, 4)]);
+        let steps: Vec<f64> = scale.steps().iter().map(|s| s.round()).collect();
+
+        assert_eq!(steps, vec![204., 182., 814.]);
+    }
+
+    #[test]
+    fn modes_of_mixed_scale_use_cents() {
+        let scale: Scale = Scale::new(
+            "mixed",
+            vec![Degree::Ratio(Ratio::new(5, 4)), Degree::Cents(700.)],
+            Period::default(),
+        );
+        let mode = scale.mode(1);
+
+        assert_eq!(mode.description, "mixed, mode 1");
+        assert_eq!(mode.degrees[0], Degree::Ratio(Ratio::new(1, 1)));
+        assert!((mode.degrees[1].cents() - 313.686).abs() < 0.001);
+        assert!((mode.degrees[2].cents() - 813.686).abs() < 0.001);
+        assert_eq!(scale.modes().len(), 3);
+    }
+
+    #[test]
+    fn modes_of_non_octave_scale_use_cents() {
+        let scale: Scale = Scale::new(
+            "tritave",
+            vec![Degree::Ratio(Ratio::new(9, 7))],
+            Period::Ratio(3, 1),
+        );
+        let mode = scale.mode(1);
+
+        assert!(
+            (mode.degrees[1].cents() - (scale.period.cents() - Ratio::new(9, 7).cents())).abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn ratio_matrix_matches_interval_matrix() {
+        let scale: Scale = Scale::new(
+            "mixed",
+            vec![Degree::Ratio(Ratio::new(5, 4)), Degree::Cents(700.)],
+            Period::default(),
+        );
+        let cents = scale.interval_matrix();
+
+        for (row, ratios) in cents.iter().zip(scale.ratio_matrix()) {
+            assert_eq!(ratios.len(), scale.len());
+            for (expected, degree) in row.iter().zip(ratios) {
+                assert!((degree.cents() - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn from_mos() {
+        let mos = crate::temperaments::mos::mos(700., 1200., 5).unwrap();
+        let scale = Scale::from_mos(&mos);
+
+        assert_eq!(scale.description, "2L 3s");
+        assert_eq!(
+            scale.to_string(),
+            "1/1 200.00000 400.00000 700.00000 900.00000"
+        );
+        assert_eq!(scale.period, Period::Cents(1200.));
+    }
+
+    #[test]
+    fn harmonic_segments_mirror_each_other() {
+        let harmonics = Scale::harmonic_segment(6, 12);
+        let subharmonics = Scale::subharmonic_segment(6, 12);
+
+        assert_eq!(harmonics.to_string(), "1/1 7/6 4/3 3/2 5/3 11/6");
+        assert_eq!(subharmonics.to_string(), "1/1 12/11 6/5 4/3 3/2 12/7");
+        assert_eq!(subharmonics.description, "subharmonics 6-12");
+    }
+
+    #[test]
+    fn harmonic_segments_wider_than_an_octave_are_reduced() {
+        let scale = Scale::harmonic_segment(4, 10);
+
+        assert_eq!(scale.to_string(), "1/1 9/8 5/4 3/2 7/4");
+    }
+
+    #[test]
+    fn degree_display() {
+        let ratio: Degree = Degree::Ratio(Ratio::new(7, 4));
+
+        assert_eq!(ratio.to_string(), "7/4");
+        assert_eq!(Degree::<i32>::Cents(100.).to_string(), "100.00000");
+        assert_eq!(Period::default().to_string(), "2/1");
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    fn play_directions() {
+        use crate::play::{PlaybackConfig, CHANNELS, SAMPLE_RATE};
+
+        let config = |scale_direction| PlaybackConfig {
+            scale_direction,
+            ..PlaybackConfig::default().at_tempo(600.)
+        };
+        let scale = Scale::from_ratios(&[Ratio::new(3, 2)]);
+        let up_down = Player::recorder(config(ScaleDirection::UpDown));
+        scale.play(&up_down);
+        let descending = Player::recorder(config(ScaleDirection::Descending));
+        scale.play(&descending);
+        let octave = Player::recorder(config(ScaleDirection::Ascending));
+        Frequency(2. * octave.config.root).play(&octave);
+
+        let note = SAMPLE_RATE as usize / 10 * CHANNELS as usize;
+        assert_eq!(up_down.samples().len(), 5 * note);
+        assert_eq!(descending.samples()[..note], octave.samples()[..]);
+    }
+}
@@ -0,0 +1,145 @@
+use super::Scale;
+use num::traits::PrimInt;
+
+/// Describes how far a degree of one scale is from the nearest degree of another, as
+/// found by [Scale::compare].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DegreeComparison {
+    /// The index of the degree in the scale being compared.
+    pub degree: usize,
+    /// The index of the nearest degree in the other scale, which may be its root in the
+    /// period above.
+    pub nearest: usize,
+    /// The distance, in cents, from the degree to the nearest degree in the other scale.
+    /// Negative when the other scale's degree is flatter.
+    pub difference: f64,
+}
+
+/// Compares the degrees of one scale with the nearest degrees of another, as found by
+/// [Scale::compare], with summary statistics of their differences in cents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleComparison {
+    pub degrees: Vec<DegreeComparison>,
+}
+
+impl ScaleComparison {
+    /// Returns the largest difference, in cents, between any degree and its nearest.
+    pub fn max_difference(&self) -> f64 {
+        self.abs_differences().fold(0., f64::max)
+    }
+
+    /// Returns the mean of the absolute differences, in cents, between each degree and
+    /// its nearest.
+    pub fn mean_difference(&self) -> f64 {
+        self.abs_differences().sum::<f64>() / self.degrees.len() as f64
+    }
+
+    /// Returns the root mean square of the differences, in cents, between each degree
+    /// and its nearest.
+    pub fn rms_difference(&self) -> f64 {
+        let squares: f64 = self.abs_differences().map(|d| d * d).sum();
+        (squares / self.degrees.len() as f64).sqrt()
+    }
+
+    fn abs_differences(&self) -> impl Iterator<Item = f64> + '_ {
+        self.degrees.iter().map(|d| d.difference.abs())
+    }
+}
+
+impl<T: PrimInt> Scale<T> {
+    /// Matches each degree of the scale with the nearest degree of the other scale,
+    /// e.g. a JI scale with the EDO it is to be tempered to, to show how closely the
+    /// other scale approximates it.
+    ///
+    /// An empty scale has no degree nearest to any other, so comparing against one
+    /// matches no degrees.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Ratio, Scale};
+    /// let ji = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+    /// let comparison = ji.compare(&Scale::from_edo(Edo::new(31)));
+    ///
+    /// assert_eq!(comparison.degrees[1].nearest, 10);
+    /// assert!((comparison.degrees[1].difference - 0.783).abs() < 0.001);
+    /// assert!((comparison.max_difference() - 5.181).abs() < 0.001);
+    /// ```
+    pub fn compare<U: PrimInt>(&self, other: &Scale<U>) -> ScaleComparison {
+        if other.is_empty() {
+            return ScaleComparison { degrees: vec![] };
+        }
+        let degrees = self
+            .degrees
+            .iter()
+            .enumerate()
+            .map(|(degree, d)| {
+                let cents = d.cents();
+                let (nearest, difference) = (-1..=other.len() as i32)
+                    .map(|j| (j, other.cents_at(j) - cents))
+                    .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+                    .unwrap();
+                DegreeComparison {
+                    degree,
+                    nearest: nearest.rem_euclid(other.len() as i32) as usize,
+                    difference,
+                }
+            })
+            .collect();
+        ScaleComparison { degrees }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ratio::Ratio, Edo};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn compares_across_the_period() {
+        let ji = Scale::from_ratios(&[Ratio::new(9, 8), Ratio::new(243, 128)]);
+        let comparison = ji.compare(&Scale::from_edo(Edo::new(5)));
+
+        assert_eq!(
+            comparison
+                .degrees
+                .iter()
+                .map(|d| d.nearest)
+                .collect::<Vec<usize>>(),
+            vec![0, 1, 0]
+        );
+        assert!((comparison.degrees[2].difference - 90.225).abs() < 0.001);
+    }
+
+    #[test]
+    fn compares_against_an_empty_scale() {
+        let ji = Scale::from_ratios(&[Ratio::new(3, 2)]);
+        let empty = Scale::<i32> {
+            degrees: vec![],
+            ..Scale::from_ratios(&[])
+        };
+        let comparison = ji.compare(&empty);
+
+        assert_eq!(comparison.degrees, vec![]);
+    }
+
+    #[test]
+    fn summary_statistics() {
+        let comparison = ScaleComparison {
+            degrees: [0., 3., -4.]
+                .iter()
+                .enumerate()
+                .map(|(degree, difference)| DegreeComparison {
+                    degree,
+                    nearest: degree,
+                    difference: *difference,
+                })
+                .collect(),
+        };
+
+        assert_eq!(comparison.max_difference(), 4.);
+        assert!((comparison.mean_difference() - 7. / 3.).abs() < 1e-9);
+        assert!((comparison.rms_difference() - (25f64 / 3.).sqrt()).abs() < 1e-9);
+    }
+}
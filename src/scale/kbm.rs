@@ -0,0 +1,369 @@
+use super::Scale;
+use num::traits::PrimInt;
+use std::{error::Error, fmt::Display, path::Path};
+
+/// Describes why a Scala `.kbm` file could not be read. Line numbers start at 1.
+#[derive(Debug)]
+pub enum KbmError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file ended before the given header field.
+    MissingField(&'static str),
+    /// The given header field, or a mapping entry, could not be parsed.
+    InvalidField {
+        line: usize,
+        field: &'static str,
+        text: String,
+    },
+    /// The file has fewer mapping entries than its map size.
+    MissingMappings { expected: usize, found: usize },
+}
+
+impl Display for KbmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read kbm file: {}", err),
+            Self::MissingField(field) => write!(f, "missing {}", field),
+            Self::InvalidField { line, field, text } => {
+                write!(f, "line {}: invalid {} `{}`", line, field, text)
+            }
+            Self::MissingMappings { expected, found } => {
+                write!(f, "expected {} mapping entries, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for KbmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for KbmError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Models a Scala keyboard mapping, which places the degrees of a [Scale] on the keys of
+/// a MIDI keyboard, and tunes it to a reference frequency.
+///
+/// ## Example
+///
+/// Map a pentatonic scale onto the white keys, leaving out F and B
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, Scale, scale::KeyboardMapping};
+/// let scale = Scale::from_ratios(&[
+///     Ratio::new(9, 8),
+///     Ratio::new(5, 4),
+///     Ratio::new(3, 2),
+///     Ratio::new(5, 3),
+/// ]);
+/// let mapping = KeyboardMapping {
+///     mapping: vec![Some(0), None, Some(1), None, Some(2), None, None, Some(3), None, Some(4), None, None],
+///     octave_degree: 5,
+///     reference_key: 60,
+///     reference_frequency: 260.,
+///     ..KeyboardMapping::default()
+/// };
+///
+/// assert!((mapping.frequency(&scale, 62).unwrap() - 292.5).abs() < 1e-9);
+/// assert_eq!(mapping.frequency(&scale, 65), None);
+/// assert!((mapping.frequency(&scale, 72).unwrap() - 520.).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardMapping {
+    /// The lowest key that is retuned.
+    pub first_key: u8,
+    /// The highest key that is retuned.
+    pub last_key: u8,
+    /// The key the first entry of the mapping is placed on.
+    pub middle_key: u8,
+    /// The key that is tuned to the reference frequency.
+    pub reference_key: u8,
+    /// The frequency, in Hz, of the reference key.
+    pub reference_frequency: f64,
+    /// The scale degree reached after each repetition of the mapping.
+    pub octave_degree: usize,
+    /// The scale degree, if any, of each successive key of the mapping, which repeats
+    /// across the keyboard. An empty mapping places successive degrees on successive
+    /// keys.
+    pub mapping: Vec<Option<usize>>,
+}
+
+impl KeyboardMapping {
+    /// Create a mapping that places successive degrees of a scale on successive keys,
+    /// with the root on the middle key, and tunes the reference key to the given
+    /// frequency.
+    pub fn linear(middle_key: u8, reference_key: u8, reference_frequency: f64) -> Self {
+        Self {
+            first_key: 0,
+            last_key: 127,
+            middle_key,
+            reference_key,
+            reference_frequency,
+            octave_degree: 0,
+            mapping: vec![],
+        }
+    }
+
+    /// Returns the scale degree placed on the given key, counting from the root on the
+    /// middle key, or `None` if the key is unmapped, or outside the retuned range.
+    pub fn degree(&self, key: u8) -> Option<i32> {
+        if key < self.first_key || key > self.last_key {
+            return None;
+        }
+        self.mapped_degree(key)
+    }
+
+    /// Returns the frequency, in Hz, of the given key when playing the given scale, or
+    /// `None` if the key, or the reference key, is unmapped.
+    pub fn frequency<T: PrimInt>(&self, scale: &Scale<T>, key: u8) -> Option<f64> {
+        let degree = self.degree(key)?;
+        let reference = self.mapped_degree(self.reference_key)?;
+        let cents = scale.cents_at(degree) - scale.cents_at(reference);
+
+        Some(self.reference_frequency * 2f64.powf(cents / 1200.))
+    }
+
+    /// Returns the scale degree on the given key, ignoring the retuned range, since the
+    /// reference key need not fall within it.
+    fn mapped_degree(&self, key: u8) -> Option<i32> {
+        let steps = key as i32 - self.middle_key as i32;
+        if self.mapping.is_empty() {
+            return Some(steps);
+        }
+
+        let size = self.mapping.len() as i32;
+        let degree = self.mapping[steps.rem_euclid(size) as usize]?;
+        Some(degree as i32 + steps.div_euclid(size) * self.octave_degree as i32)
+    }
+
+    /// Parse a mapping from the contents of a Scala `.kbm` file.
+    pub fn from_kbm(kbm: &str) -> Result<Self, KbmError> {
+        let mut lines = kbm
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.starts_with('!') && !line.is_empty());
+
+        let mut field = |name: &'static str| {
+            let (line, text) = lines.next().ok_or(KbmError::MissingField(name))?;
+            let value = text.split_whitespace().next().unwrap_or("");
+            Ok::<_, KbmError>((line, value.to_string(), name))
+        };
+        let size: usize = parse_field(field("map size")?)?;
+        let first_key = parse_key(field("first key")?)?;
+        let last_key = parse_key(field("last key")?)?;
+        let middle_key = parse_key(field("middle key")?)?;
+        let reference_key = parse_key(field("reference key")?)?;
+        let reference_frequency = parse_field(field("reference frequency")?)?;
+        let octave_degree = parse_field(field("octave degree")?)?;
+
+        let mapping = lines
+            .take(size)
+            .map(|(line, text)| {
+                let entry = text.split_whitespace().next().unwrap_or("");
+                match entry {
+                    "x" => Ok(None),
+                    _ => parse_field((line, entry.to_string(), "mapping entry")).map(Some),
+                }
+            })
+            .collect::<Result<Vec<Option<usize>>, KbmError>>()?;
+        if mapping.len() < size {
+            return Err(KbmError::MissingMappings {
+                expected: size,
+                found: mapping.len(),
+            });
+        }
+
+        Ok(Self {
+            first_key,
+            last_key,
+            middle_key,
+            reference_key,
+            reference_frequency,
+            octave_degree,
+            mapping,
+        })
+    }
+
+    /// Read a mapping from the Scala `.kbm` file at the given path.
+    pub fn read_kbm<P: AsRef<Path>>(path: P) -> Result<Self, KbmError> {
+        Self::from_kbm(&std::fs::read_to_string(path)?)
+    }
+
+    /// Returns the mapping in the Scala `.kbm` file format, with unmapped keys marked
+    /// with an `x`.
+    pub fn to_kbm(&self) -> String {
+        let mut kbm = format!(
+            "! rust-intonation\n\
+             ! Size of map:\n{}\n\
+             ! First MIDI note number to retune:\n{}\n\
+             ! Last MIDI note number to retune:\n{}\n\
+             ! Middle note where the first entry of the mapping is mapped to:\n{}\n\
+             ! Reference note for which frequency is given:\n{}\n\
+             ! Frequency to tune the above note to:\n{:.6}\n\
+             ! Scale degree to consider as formal octave:\n{}\n\
+             ! Mapping.\n",
+            self.mapping.len(),
+            self.first_key,
+            self.last_key,
+            self.middle_key,
+            self.reference_key,
+            self.reference_frequency,
+            self.octave_degree
+        );
+        for degree in &self.mapping {
+            match degree {
+                Some(degree) => kbm.push_str(&format!("{}\n", degree)),
+                None => kbm.push_str("x\n"),
+            }
+        }
+        kbm
+    }
+
+    /// Write the mapping to a Scala `.kbm` file at the given path.
+    pub fn write_kbm<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_kbm())
+    }
+}
+
+impl Default for KeyboardMapping {
+    /// Places the root of the scale on middle C, with A above it tuned to 440Hz.
+    fn default() -> Self {
+        Self::linear(60, 69, 440.)
+    }
+}
+
+/// Parses a MIDI note number, which must be no greater than 127.
+fn parse_key(field: (usize, String, &'static str)) -> Result<u8, KbmError> {
+    let (line, text, name) = field.clone();
+    match parse_field(field)? {
+        key @ 0..=127 => Ok(key),
+        _ => Err(KbmError::InvalidField {
+            line,
+            field: name,
+            text,
+        }),
+    }
+}
+
+fn parse_field<F: std::str::FromStr>(
+    (line, text, field): (usize, String, &'static str),
+) -> Result<F, KbmError> {
+    text.parse()
+        .map_err(|_| KbmError::InvalidField { line, field, text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ratio::Ratio, Edo};
+    use pretty_assertions::assert_eq;
+
+    fn pentatonic() -> Scale {
+        Scale::from_ratios(&[
+            Ratio::new(9, 8),
+            Ratio::new(5, 4),
+            Ratio::new(3, 2),
+            Ratio::new(5, 3),
+        ])
+    }
+
+    #[test]
+    fn linear_mapping() {
+        let mapping = KeyboardMapping::default();
+        let edo = Scale::from_edo(Edo::new(12));
+
+        assert_eq!(mapping.degree(60), Some(0));
+        assert_eq!(mapping.degree(59), Some(-1));
+        assert!((mapping.frequency(&edo, 69).unwrap() - 440.).abs() < 1e-9);
+        assert!((mapping.frequency(&edo, 60).unwrap() - 261.626).abs() < 0.001);
+    }
+
+    #[test]
+    fn linear_mapping_of_a_smaller_scale_spans_more_octaves() {
+        let mapping = KeyboardMapping::linear(60, 60, 200.);
+
+        assert!((mapping.frequency(&pentatonic(), 65).unwrap() - 400.).abs() < 1e-9);
+        assert!((mapping.frequency(&pentatonic(), 58).unwrap() - 150.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn repeating_mapping() {
+        let mapping = KeyboardMapping {
+            first_key: 48,
+            last_key: 84,
+            middle_key: 60,
+            reference_key: 60,
+            reference_frequency: 200.,
+            octave_degree: 5,
+            mapping: vec![Some(0), None, Some(1), None, Some(2)],
+        };
+
+        assert_eq!(mapping.degree(61), None);
+        assert_eq!(mapping.degree(62), Some(1));
+        assert_eq!(mapping.degree(65), Some(5));
+        assert_eq!(mapping.degree(57), Some(-4));
+        assert_eq!(mapping.degree(40), None);
+        assert!((mapping.frequency(&pentatonic(), 64).unwrap() - 250.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn kbm_round_trip() {
+        let mapping = KeyboardMapping {
+            mapping: vec![Some(0), None, Some(1)],
+            octave_degree: 2,
+            ..KeyboardMapping::default()
+        };
+
+        assert_eq!(
+            KeyboardMapping::from_kbm(&mapping.to_kbm()).unwrap(),
+            mapping
+        );
+    }
+
+    #[test]
+    fn from_kbm() {
+        let kbm = "! test.kbm\n0\n0\n127\n60\n69\n440.0 Hz\n12\n! Mapping.\n";
+
+        assert_eq!(
+            KeyboardMapping::from_kbm(kbm).unwrap(),
+            KeyboardMapping {
+                octave_degree: 12,
+                ..KeyboardMapping::default()
+            }
+        );
+    }
+
+    #[test]
+    fn from_kbm_errors() {
+        assert!(matches!(
+            KeyboardMapping::from_kbm("0\n0\n127\n"),
+            Err(KbmError::MissingField("middle key"))
+        ));
+        assert_eq!(
+            KeyboardMapping::from_kbm("0\n0\n200\n60\n69\n440\n12\n")
+                .unwrap_err()
+                .to_string(),
+            "line 3: invalid last key `200`"
+        );
+        assert!(matches!(
+            KeyboardMapping::from_kbm("2\n0\n127\n60\n69\n440\n12\n0\n"),
+            Err(KbmError::MissingMappings {
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            KeyboardMapping::from_kbm("1\n0\n127\n60\n69\n440\n12\ny\n"),
+            Err(KbmError::InvalidField { line: 8, .. })
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Scale, DEGREE_TOLERANCE};
+use num::traits::PrimInt;
+
+/// Describes whether a scale is proper, in Rothenberg's sense: whether every interval
+/// spanning a given number of steps is no smaller than every interval spanning fewer
+/// steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Propriety {
+    /// Every interval spanning more steps is strictly larger.
+    StrictlyProper,
+    /// Intervals spanning different numbers of steps can be the same size, but none is
+    /// smaller than one spanning fewer steps, as with the tritone of the diatonic scale.
+    Proper,
+    /// Some interval is smaller than one spanning fewer steps.
+    Improper,
+}
+
+impl<T: PrimInt> Scale<T> {
+    /// Returns the [Propriety] of the scale.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale, scale::Propriety};
+    /// let pentatonic = Scale::from_ratios(&[
+    ///     Ratio::new(9, 8),
+    ///     Ratio::new(5, 4),
+    ///     Ratio::new(3, 2),
+    ///     Ratio::new(5, 3),
+    /// ]);
+    /// assert_eq!(pentatonic.propriety(), Propriety::StrictlyProper);
+    /// ```
+    pub fn propriety(&self) -> Propriety {
+        let ranges: Vec<(f64, f64)> = self
+            .interval_classes()
+            .iter()
+            .map(|sizes| {
+                let min = sizes.iter().cloned().fold(f64::MAX, f64::min);
+                let max = sizes.iter().cloned().fold(f64::MIN, f64::max);
+                (min, max)
+            })
+            .collect();
+
+        let mut propriety = Propriety::StrictlyProper;
+        for pair in ranges.windows(2) {
+            let ((_, max), (min, _)) = (pair[0], pair[1]);
+            if max > min + DEGREE_TOLERANCE {
+                return Propriety::Improper;
+            }
+            if max > min - DEGREE_TOLERANCE {
+                propriety = Propriety::Proper;
+            }
+        }
+        propriety
+    }
+
+    /// Returns whether the scale is a constant structure, that is, whether every
+    /// interval between its degrees always spans the same number of steps.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale};
+    /// let pentatonic = Scale::from_ratios(&[
+    ///     Ratio::new(9, 8),
+    ///     Ratio::new(5, 4),
+    ///     Ratio::new(3, 2),
+    ///     Ratio::new(5, 3),
+    /// ]);
+    /// assert!(pentatonic.is_constant_structure());
+    /// ```
+    pub fn is_constant_structure(&self) -> bool {
+        let classes = self.interval_classes();
+        classes.iter().enumerate().all(|(i, sizes)| {
+            classes[i + 1..].iter().all(|other| {
+                sizes
+                    .iter()
+                    .all(|a| other.iter().all(|b| (a - b).abs() > DEGREE_TOLERANCE))
+            })
+        })
+    }
+
+    /// Returns the sizes, in cents, of the intervals spanning each number of steps, from
+    /// one step up to one fewer than the size of the scale.
+    fn interval_classes(&self) -> Vec<Vec<f64>> {
+        let matrix = self.interval_matrix();
+        (1..self.len())
+            .map(|steps| matrix.iter().map(|row| row[steps]).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ratio::Ratio, Edo};
+    use pretty_assertions::assert_eq;
+
+    fn diatonic() -> Scale {
+        Scale::from_mos(&crate::temperaments::mos::mos(700., 1200., 7).unwrap())
+    }
+
+    #[test]
+    fn diatonic_is_proper_but_not_strictly() {
+        let scale = diatonic();
+
+        assert_eq!(scale.propriety(), Propriety::Proper);
+        assert!(!scale.is_constant_structure());
+    }
+
+    #[test]
+    fn edos_are_strictly_proper_constant_structures() {
+        let scale = Scale::from_edo(Edo::new(19));
+
+        assert_eq!(scale.propriety(), Propriety::StrictlyProper);
+        assert!(scale.is_constant_structure());
+    }
+
+    #[test]
+    fn improper_scale() {
+        let scale = Scale::from_ratios(&[Ratio::new(16, 15), Ratio::new(9, 8), Ratio::new(3, 2)]);
+
+        assert_eq!(scale.propriety(), Propriety::Improper);
+    }
+}
@@ -0,0 +1,314 @@
+use super::{Degree, Period, Scale};
+use crate::ratio::Ratio;
+use num::traits::PrimInt;
+use std::{error::Error, fmt::Display, path::Path};
+
+/// Describes why a Scala `.scl` file could not be read. Line numbers start at 1.
+#[derive(Debug)]
+pub enum SclError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file ended before the description or the number of notes.
+    MissingHeader,
+    /// The number of notes was not a non-negative integer.
+    InvalidCount { line: usize, text: String },
+    /// A pitch was neither a ratio of positive integers nor a number of cents.
+    InvalidPitch { line: usize, text: String },
+    /// The file has fewer pitches than it says it does.
+    MissingPitches { expected: usize, found: usize },
+}
+
+impl Display for SclError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read scl file: {}", err),
+            Self::MissingHeader => write!(f, "missing description or number of notes"),
+            Self::InvalidCount { line, text } => {
+                write!(f, "line {}: invalid number of notes `{}`", line, text)
+            }
+            Self::InvalidPitch { line, text } => {
+                write!(f, "line {}: invalid pitch `{}`", line, text)
+            }
+            Self::MissingPitches { expected, found } => {
+                write!(f, "expected {} pitches, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for SclError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SclError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A single pitch line of a `.scl` file, before it is placed in a [Scale].
+enum Pitch {
+    Ratio(i32, i32),
+    Cents(f64),
+}
+
+impl Scale<i32> {
+    /// Parse a scale from the contents of a Scala `.scl` file.
+    ///
+    /// Pitches given as ratios are kept as ratios, unless they fall outside the octave,
+    /// as in a scale with a larger period, in which case they are kept in cents. The
+    /// degrees are sorted into ascending order, and the last pitch is taken as the
+    /// period.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, scale::{Degree, Period, Scale}};
+    /// let scl = "! pelog.scl\n!\nA pelog-like scale\n 3\n!\n 8/7\n 400.0 cents\n 2/1\n";
+    /// let scale = Scale::from_scl(scl).unwrap();
+    ///
+    /// assert_eq!(scale.description, "A pelog-like scale");
+    /// assert_eq!(
+    ///     scale.degrees,
+    ///     vec![
+    ///         Degree::Ratio(Ratio::new(1, 1)),
+    ///         Degree::Ratio(Ratio::new(8, 7)),
+    ///         Degree::Cents(400.)
+    ///     ]
+    /// );
+    /// assert_eq!(scale.period, Period::Ratio(2, 1));
+    /// ```
+    pub fn from_scl(scl: &str) -> Result<Self, SclError> {
+        let mut lines = scl
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.starts_with('!'));
+
+        let (_, description) = lines.next().ok_or(SclError::MissingHeader)?;
+        let (line, count) = lines.next().ok_or(SclError::MissingHeader)?;
+        let count: usize = first_word(count)
+            .parse()
+            .map_err(|_| SclError::InvalidCount {
+                line,
+                text: count.to_string(),
+            })?;
+
+        let pitches = lines
+            .filter(|(_, line)| !line.is_empty())
+            .take(count)
+            .map(|(line, text)| {
+                parse_pitch(first_word(text)).ok_or(SclError::InvalidPitch {
+                    line,
+                    text: text.to_string(),
+                })
+            })
+            .collect::<Result<Vec<Pitch>, SclError>>()?;
+        if pitches.len() < count {
+            return Err(SclError::MissingPitches {
+                expected: count,
+                found: pitches.len(),
+            });
+        }
+
+        let period = match pitches.last() {
+            Some(Pitch::Ratio(n, d)) => Period::Ratio(*n as u32, *d as u32),
+            Some(Pitch::Cents(cents)) => Period::Cents(*cents),
+            None => Period::default(),
+        };
+        let degrees = pitches
+            .iter()
+            .take(count.saturating_sub(1))
+            .map(|pitch| match *pitch {
+                Pitch::Ratio(n, d) if n >= d && (n as i64) < 2 * d as i64 => {
+                    Degree::Ratio(Ratio::new(n, d))
+                }
+                Pitch::Ratio(n, d) => Degree::Cents(1200. * (n as f64 / d as f64).log2()),
+                Pitch::Cents(cents) => Degree::Cents(cents),
+            })
+            .collect();
+
+        Ok(Self::new(description, degrees, period))
+    }
+
+    /// Read a scale from the Scala `.scl` file at the given path.
+    pub fn read_scl<P: AsRef<Path>>(path: P) -> Result<Self, SclError> {
+        Self::from_scl(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// Returns the text of a line up to the first whitespace, since anything after a pitch
+/// or the number of notes is ignored.
+fn first_word(line: &str) -> &str {
+    line.split_whitespace().next().unwrap_or("")
+}
+
+/// Parses a pitch, which is given in cents if it contains a decimal point, and
+/// otherwise as a ratio, or a whole number.
+fn parse_pitch(text: &str) -> Option<Pitch> {
+    if text.contains('.') {
+        return text.parse().ok().map(Pitch::Cents);
+    }
+    let (n, d) = text.split_once('/').unwrap_or((text, "1"));
+    let (n, d): (i32, i32) = (n.parse().ok()?, d.parse().ok()?);
+    (n > 0 && d > 0).then_some(Pitch::Ratio(n, d))
+}
+
+impl<T: PrimInt + Display> Scale<T> {
+    /// Returns the scale in the [Scala](https://www.huygens-fokker.org/scala/scl_format.html)
+    /// `.scl` file format.
+    ///
+    /// Following the format, the root is left out and the period is given as the last
+    /// degree. Degrees given as ratios are written as ratios, and those given in cents
+    /// are written with a decimal point.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, Scale};
+    /// let scale = Scale::from_ratios(&[Ratio::new(5, 4), Ratio::new(3, 2)]);
+    /// assert_eq!(
+    ///     scale.to_scl(),
+    ///     "! rust-intonation\n!\n3 note JI scale\n 3\n!\n 5/4\n 3/2\n 2/1\n"
+    /// );
+    /// ```
+    pub fn to_scl(&self) -> String {
+        let description = self.description.lines().next().unwrap_or("");
+        let degrees = &self.degrees[1..];
+
+        let mut scl = format!("! rust-intonation\n!\n{}\n", description);
+        scl.push_str(&format!(" {}\n!\n", degrees.len() + 1));
+        for degree in degrees {
+            scl.push_str(&format!(" {}\n", degree));
+        }
+        scl.push_str(&format!(" {}\n", self.period));
+        scl
+    }
+
+    /// Write the scale to a Scala `.scl` file at the given path.
+    pub fn write_scl<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_scl())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Edo;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn edo_to_scl() {
+        let scl = Scale::from_edo(Edo::new(5)).to_scl();
+
+        assert_eq!(
+            scl.lines().collect::<Vec<&str>>(),
+            vec![
+                "! rust-intonation",
+                "!",
+                "5 equal divisions of 2/1",
+                " 5",
+                "!",
+                " 240.00000",
+                " 480.00000",
+                " 720.00000",
+                " 960.00000",
+                " 2/1"
+            ]
+        );
+    }
+
+    #[test]
+    fn mixed_degrees_to_scl() {
+        let scale: Scale = Scale::new(
+            "mixed\nsecond line",
+            vec![Degree::Ratio(Ratio::new(7, 4)), Degree::Cents(350.5)],
+            Period::Cents(1210.),
+        );
+
+        assert_eq!(
+            scale.to_scl(),
+            "! rust-intonation\n!\nmixed\n 3\n!\n 350.50000\n 7/4\n 1210.00000\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let scale: Scale = Scale::new(
+            "mixed",
+            vec![Degree::Ratio(Ratio::new(7, 4)), Degree::Cents(350.5)],
+            Period::Ratio(3, 1),
+        );
+
+        assert_eq!(Scale::from_scl(&scale.to_scl()).unwrap(), scale);
+    }
+
+    #[test]
+    fn from_scl_ignores_comments_and_trailing_text() {
+        let scl = "! test.scl\n!\n\n 2 notes\n!\n 5/4 major third\n\n 2 octave\n";
+        let scale = Scale::from_scl(scl).unwrap();
+
+        assert_eq!(scale.description, "");
+        assert_eq!(
+            scale.degrees,
+            vec![
+                Degree::Ratio(Ratio::new(1, 1)),
+                Degree::Ratio(Ratio::new(5, 4))
+            ]
+        );
+        assert_eq!(scale.period, Period::Ratio(2, 1));
+    }
+
+    #[test]
+    fn from_scl_keeps_ratios_outside_the_octave_in_cents() {
+        let scl = "Bohlen-Pierce\n2\n7/3\n3/1";
+        let scale = Scale::from_scl(scl).unwrap();
+
+        assert!((scale.degrees[1].cents() - 1466.871).abs() < 0.001);
+        assert_eq!(scale.period, Period::Ratio(3, 1));
+    }
+
+    #[test]
+    fn from_scl_errors() {
+        assert!(matches!(
+            Scale::from_scl("! only a comment"),
+            Err(SclError::MissingHeader)
+        ));
+        assert!(matches!(
+            Scale::from_scl("test\nmany\n"),
+            Err(SclError::InvalidCount { line: 2, .. })
+        ));
+        assert!(matches!(
+            Scale::from_scl("test\n2\n5/4\n-2/1\n"),
+            Err(SclError::InvalidPitch { line: 4, .. })
+        ));
+        assert!(matches!(
+            Scale::from_scl("test\n3\n5/4\n2/1\n"),
+            Err(SclError::MissingPitches {
+                expected: 3,
+                found: 2
+            })
+        ));
+        assert_eq!(
+            Scale::from_scl("test\n1\nabc\n").unwrap_err().to_string(),
+            "line 3: invalid pitch `abc`"
+        );
+    }
+
+    #[test]
+    fn write_scl() {
+        let path = std::env::temp_dir().join("rust-intonation-scale-test.scl");
+        let scale = Scale::from_ratios(&[Ratio::new(9, 8)]);
+        scale.write_scl(&path).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), scale.to_scl());
+        assert_eq!(Scale::read_scl(&path).unwrap(), scale);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
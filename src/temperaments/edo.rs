@@ -1,71 +1,577 @@
 //! Functions and structs for generating temperaments that are made by equal divisions
-//! of the octave (EDO)
+//! of the octave (EDO), or of any other period interval (EDN)
+use super::{val::Val, Temperament};
 use crate::{
-    interval::Approximate12EDOInterval,
-    play::{play_dyad, Play},
+    diamond::Diamond,
+    error::IntonationError,
+    interval::{interval_name, Approximate12EDOInterval, TwelveEDOComparison},
+    monzo::Monzo,
+    numtheory::primes_up_to,
+    ratio::Ratio,
 };
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::Frequency,
+    play::{Play, Player},
+};
+use num::traits::PrimInt;
+use std::ops::{Add, Neg, Sub};
 
-/// Models an EDO that divides the octave into the given number of equal divisions.
+/// Models a temperament that divides a period interval into the given number of equal
+/// divisions.
+///
+/// The period is given as a pair of integers, rather than a [Ratio], since it is
+/// commonly larger than an octave, e.g. the `3/1` tritave of the Bohlen-Pierce scale.
+///
+/// ## Examples
+///
+/// ```rust
+/// # use rust_intonation::temperaments::EqualDivision;
+/// let bohlen_pierce = EqualDivision::with_period(13, (3, 1));
+/// assert!((bohlen_pierce.step_size() - 146.304).abs() < 0.001);
+/// ```
 #[derive(Clone, Copy, Debug, PartialEq)]
-
-pub struct Edo {
+pub struct EqualDivision {
     pub divisions: u32,
+    pub period: (u32, u32),
 }
 
+/// Models an EDO that divides the octave into the given number of equal divisions.
+pub type Edo = EqualDivision;
+
+/// Describes the approximation of a JI ratio in an EDO as a tuple pair of the
+/// nearest number of steps and the error of that step, given in cents.
+///
+/// The error is negative when the EDO step is flatter than the JI ratio.
+pub type EdoApproximation = (i32, f64);
+
 /// Models an interval of a given number of steps in a specified EDO temperament.
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct EdoInterval<'a> {
-    edo: &'a Edo,
-    steps: u32,
+pub struct EdoInterval {
+    edo: Edo,
+    steps: i32,
     pub cents: f32,
 }
 
-impl Edo {
+impl EqualDivision {
     /// Create a new EDO temperament which divides the octave evenly into a given number of
     /// divisions.
+    ///
+    /// Panics if the number of divisions is 0.
     pub fn new(divisions: u32) -> Self {
-        Self { divisions }
+        Self::with_period(divisions, (2, 1))
+    }
+
+    /// Create a new EDO temperament, as [EqualDivision::new], but returning an
+    /// [IntonationError] rather than panicking if the number of divisions is 0.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, IntonationError};
+    /// assert_eq!(Edo::try_new(12), Ok(Edo::new(12)));
+    /// assert_eq!(Edo::try_new(0), Err(IntonationError::NoDivisions));
+    /// ```
+    pub fn try_new(divisions: u32) -> Result<Self, IntonationError> {
+        Self::try_with_period(divisions, (2, 1))
+    }
+
+    /// Create a new temperament which divides the given period evenly into a given number of
+    /// divisions.
+    ///
+    /// Panics if the number of divisions, or either half of the period, is 0.
+    pub fn with_period(divisions: u32, period: (u32, u32)) -> Self {
+        Self::try_with_period(divisions, period).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a new temperament, as [EqualDivision::with_period], but returning an
+    /// [IntonationError] rather than panicking if the number of divisions, or either
+    /// half of the period, is 0.
+    pub fn try_with_period(divisions: u32, period: (u32, u32)) -> Result<Self, IntonationError> {
+        match (divisions, period) {
+            (0, _) => Err(IntonationError::NoDivisions),
+            (_, (_, 0)) => Err(IntonationError::ZeroDenominator),
+            (_, (0, _)) => Err(IntonationError::NonPositiveRatio),
+            _ => Ok(Self { divisions, period }),
+        }
+    }
+
+    /// Returns the size of the period, in cents.
+    pub fn period_cents(&self) -> f64 {
+        let (n, d) = self.period;
+        1200. * (n as f64 / d as f64).log2()
     }
 
     /// Returns an [interval][EdoInterval] that represents an interval of the given number of steps
     /// in the subject EDO.
-    pub fn interval(&self, steps: u32) -> EdoInterval {
-        EdoInterval::new(self, steps)
+    ///
+    /// A negative number of steps gives a descending interval.
+    pub fn interval(&self, steps: i32) -> EdoInterval {
+        EdoInterval::new(*self, steps)
+    }
+
+    /// Returns the size of a single step of the EDO, in cents.
+    pub fn step_size(&self) -> f64 {
+        self.period_cents() / self.divisions as f64
+    }
+
+    /// Finds the step of the EDO nearest to the given [Ratio], along with the
+    /// error in cents between that step and the JI ratio.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Ratio};
+    /// let (steps, error) = Edo::new(31).approximate(Ratio::new(3, 2));
+    /// assert_eq!(steps, 18);
+    /// assert!((error - -5.181).abs() < 0.001);
+    /// ```
+    pub fn approximate<T: PrimInt>(&self, ratio: Ratio<T>) -> EdoApproximation {
+        let ji_cents = ratio.cents();
+        let steps = (ji_cents / self.step_size()).round();
+
+        (steps as i32, steps * self.step_size() - ji_cents)
+    }
+
+    /// Returns the frequency, in Hz, of every step of the EDO across the given number of
+    /// periods (octaves, for an EDO) above the reference frequency, including the top
+    /// of the last period.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Edo;
+    /// let frequencies = Edo::new(12).frequencies(440., 1);
+    /// assert_eq!(frequencies.len(), 13);
+    /// assert_eq!(frequencies[0], 440.);
+    /// assert!((frequencies[3] - 523.251).abs() < 0.001);
+    /// assert_eq!(frequencies[12], 880.);
+    /// ```
+    pub fn frequencies(&self, reference_hz: f64, octaves: u32) -> Vec<f64> {
+        (0..=(self.divisions * octaves) as i32)
+            .map(|steps| self.interval(steps).frequency(reference_hz))
+            .collect()
+    }
+
+    /// Plays every step of the EDO in ascending order, from the root given in the
+    /// player's [PlaybackConfig](crate::play::PlaybackConfig) up to the top of the
+    /// period.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rust_intonation::{Edo, play::Player};
+    /// Edo::new(31).play_scale(&Player::default());
+    /// ```
+    #[cfg(feature = "audio")]
+    pub fn play_scale(&self, player: &Player) {
+        let freqs: Vec<f32> = self
+            .frequencies(player.config.root as f64, 1)
+            .iter()
+            .map(|f| *f as f32)
+            .collect();
+        player.play_sequence(&freqs);
+    }
+
+    /// Returns the patent [Val] of the EDO for all primes up to the given limit,
+    /// that is, the nearest number of steps to each prime.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Edo;
+    /// assert_eq!(Edo::new(31).patent_val(7).mapping, vec![31, 49, 72, 87]);
+    /// ```
+    pub fn patent_val(&self, limit: u32) -> Val {
+        Val::new(
+            primes_up_to(limit)
+                .iter()
+                .map(|&p| (1200. * (p as f64).log2() / self.step_size()).round() as i32)
+                .collect(),
+        )
+    }
+
+    /// Returns whether the EDO is consistent in the given odd limit, that is, whether
+    /// the nearest approximation of every interval in the odd limit's tonality diamond
+    /// is the same as the difference between the nearest approximations of its
+    /// component odd harmonics.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Edo;
+    /// assert!(Edo::new(12).consistency(9));
+    /// assert!(!Edo::new(12).consistency(11));
+    /// ```
+    pub fn consistency(&self, odd_limit: u32) -> bool {
+        let harmonics: Vec<u32> = odd_harmonics(odd_limit);
+        let nearest_steps = |cents: f64| (cents / self.step_size()).round() as i32;
+        let harmonic_cents = |n: u32| 1200. * (n as f64).log2();
+
+        harmonics.iter().all(|&a| {
+            harmonics.iter().all(|&b| {
+                let direct = nearest_steps(harmonic_cents(a) - harmonic_cents(b));
+                let derived = nearest_steps(harmonic_cents(a)) - nearest_steps(harmonic_cents(b));
+                direct == derived
+            })
+        })
+    }
+
+    /// Returns the largest absolute error, in cents, between a JI ratio in the given
+    /// odd limit's tonality diamond and its nearest approximation in the EDO.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Edo;
+    /// let error = Edo::new(12).max_error(5);
+    /// assert!((error - 15.641).abs() < 0.001);
+    /// ```
+    pub fn max_error(&self, odd_limit: u32) -> f64 {
+        odd_limit_intervals(odd_limit)
+            .iter()
+            .map(|ratio| self.approximate(*ratio).1.abs())
+            .fold(0., f64::max)
+    }
+
+    /// Returns a table of every interval in the given odd limit, ordered by size, paired
+    /// with its [approximation](EqualDivision::approximate) in the EDO.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Ratio};
+    /// let table = Edo::new(12).comparison_table(5);
+    /// let (ratio, (steps, error)) = table[0];
+    /// assert_eq!(ratio, Ratio::new(6, 5));
+    /// assert_eq!(steps, 3);
+    /// assert!((error - -15.641).abs() < 0.001);
+    /// ```
+    pub fn comparison_table(&self, odd_limit: u32) -> Vec<(Ratio<i32>, EdoApproximation)> {
+        let mut intervals = odd_limit_intervals(odd_limit);
+        intervals.sort_by(|a, b| a.cents().total_cmp(&b.cents()));
+
+        intervals
+            .into_iter()
+            .map(|ratio| (ratio, self.approximate(ratio)))
+            .collect()
+    }
+
+    /// Returns the Tenney-weighted RMS error with which the EDO approximates the given
+    /// target ratios, relative to the EDO's step size.
+    ///
+    /// Each ratio's error is given as a fraction of an EDO step, and weighted by dividing
+    /// by the ratio's [Tenney height](Ratio::tenney_height), so that errors in simpler
+    /// ratios count for more. Measuring the error relative to the step size means that
+    /// larger EDOs are not favoured simply for having smaller steps. Unisons are ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Ratio};
+    /// let targets = [Ratio::new(3, 2), Ratio::new(5, 4)];
+    /// assert!(Edo::new(12).weighted_error(&targets) < Edo::new(13).weighted_error(&targets));
+    /// ```
+    pub fn weighted_error<T: PrimInt>(&self, targets: &[Ratio<T>]) -> f64 {
+        let errors: Vec<f64> = targets
+            .iter()
+            .filter(|ratio| ratio.tenney_height() > 0.)
+            .map(|ratio| self.approximate(*ratio).1 / self.step_size() / ratio.tenney_height())
+            .collect();
+
+        if errors.is_empty() {
+            return 0.;
+        }
+
+        (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt()
+    }
+
+    /// Maps a [Ratio] to the number of EDO steps it spans under the EDO's patent
+    /// [Val] in the ratio's prime limit.
+    ///
+    /// Unlike [Edo::approximate], this is the tempered size of the ratio
+    /// built up from its prime factors, which need not be the nearest step.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Ratio};
+    /// let twelve = Edo::new(12);
+    /// assert_eq!(twelve.tempered_steps(Ratio::new(81, 80)), 0);
+    /// ```
+    pub fn tempered_steps<T: PrimInt>(&self, ratio: Ratio<T>) -> i32 {
+        let limit: u32 = num::cast(ratio.limit()).unwrap();
+        self.patent_val(limit).apply(&Monzo::from(ratio))
+    }
+
+    /// Finds the JI commas in the given prime limit that are tempered out by the EDO's
+    /// patent [Val], that is, ratios that map to 0 steps.
+    ///
+    /// Searches all ratios whose numerator is no greater than `max_complexity`, and
+    /// returns them ordered from simplest to most complex.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, Ratio};
+    /// let commas = Edo::new(12).tempered_commas(5, 130);
+    /// assert_eq!(commas, vec![Ratio::new(81, 80), Ratio::new(128, 125)]);
+    /// ```
+    ///
+    /// Panics if `max_complexity` does not fit in an `i32`.
+    pub fn tempered_commas(&self, limit: u32, max_complexity: u32) -> Vec<Ratio<i32>> {
+        self.patent_val(limit).tempered_commas(max_complexity)
+    }
+
+    /// Finds the commas the EDO tempers out, as [Edo::tempered_commas], but returning
+    /// an [IntonationError] rather than panicking if `max_complexity` does not fit in an
+    /// `i32`.
+    pub fn try_tempered_commas(
+        &self,
+        limit: u32,
+        max_complexity: u32,
+    ) -> Result<Vec<Ratio<i32>>, IntonationError> {
+        self.patent_val(limit).try_tempered_commas(max_complexity)
+    }
+
+    /// Returns the [Val] of the EDO for all primes up to the given limit, modified by the
+    /// given warts.
+    ///
+    /// In wart notation, each letter refers to a prime, `a` being 2, `b` being 3, `c` being
+    /// 5, and so on. Each time a prime's letter appears, that prime is mapped to its
+    /// next-best approximation in the EDO, rather than its nearest. An empty string of warts
+    /// gives the [patent val](EqualDivision::patent_val). Primes past the 26th, `z` being
+    /// 101, have no letter, and so are always mapped to their nearest approximation.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Edo;
+    /// let seventeen = Edo::new(17);
+    /// assert_eq!(seventeen.patent_val(5).mapping, vec![17, 27, 39]);
+    /// assert_eq!(seventeen.wart_val("c", 5).mapping, vec![17, 27, 40]);
+    /// ```
+    pub fn wart_val(&self, warts: &str, limit: u32) -> Val {
+        Val::new(
+            primes_up_to(limit)
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| {
+                    let letter = u8::try_from(i)
+                        .ok()
+                        .filter(|&i| i < 26)
+                        .map(|i| (b'a' + i) as char);
+                    let rank =
+                        letter.map_or(0, |letter| warts.chars().filter(|&c| c == letter).count());
+                    let exact = 1200. * (p as f64).log2() / self.step_size();
+                    nth_best_approximation(exact, rank)
+                })
+                .collect(),
+        )
     }
 }
 
-impl<'a> EdoInterval<'a> {
+/// Returns the integer that is the `rank`th nearest to `x`, with 0 being the nearest.
+fn nth_best_approximation(x: f64, rank: usize) -> i32 {
+    let floor = x.floor() as i32;
+    let spread = rank as i32 + 1;
+    let mut candidates: Vec<i32> = (floor - spread..=floor + spread).collect();
+    candidates.sort_by(|a, b| (*a as f64 - x).abs().total_cmp(&(*b as f64 - x).abs()));
+    candidates[rank]
+}
+
+fn odd_harmonics(odd_limit: u32) -> Vec<u32> {
+    (1..=odd_limit).step_by(2).collect()
+}
+
+/// Returns the distinct, non-unison intervals of the tonality diamond for the given odd limit.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, temperaments::edo::odd_limit_intervals};
+/// assert_eq!(
+///     odd_limit_intervals(5),
+///     vec![
+///         Ratio::new(3, 2),
+///         Ratio::new(5, 4),
+///         Ratio::new(4, 3),
+///         Ratio::new(5, 3),
+///         Ratio::new(8, 5),
+///         Ratio::new(6, 5),
+///     ]
+/// );
+/// ```
+pub fn odd_limit_intervals(odd_limit: u32) -> Vec<Ratio<i32>> {
+    if odd_limit == 0 {
+        return vec![];
+    }
+    let unison = Ratio::new(1, 1);
+    let mut intervals: Vec<Ratio<i32>> = vec![];
+
+    for &ratio in Diamond::<i32>::new(odd_harmonics(odd_limit))
+        .ratios()
+        .iter()
+        .flatten()
+    {
+        if ratio != unison && !intervals.contains(&ratio) {
+            intervals.push(ratio);
+        }
+    }
+
+    intervals
+}
+
+/// Ranks every EDO from 1 up to `max_divisions` by how well it approximates the given
+/// target ratios, as measured by [Edo::weighted_error], best first.
+///
+/// [odd_limit_intervals] provides a set of targets for a given odd limit.
+///
+/// ## Example
+///
+/// ```rust
+/// # use rust_intonation::temperaments::edo::{best_edos, odd_limit_intervals};
+/// let ranked = best_edos(&odd_limit_intervals(5), 24);
+/// assert_eq!(ranked[0].0.divisions, 19);
+/// ```
+pub fn best_edos<T: PrimInt>(targets: &[Ratio<T>], max_divisions: u32) -> Vec<(Edo, f64)> {
+    let mut ranked: Vec<(Edo, f64)> = (1..=max_divisions)
+        .map(Edo::new)
+        .map(|edo| (edo, edo.weighted_error(targets)))
+        .collect();
+
+    ranked.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    ranked
+}
+
+impl EdoInterval {
     /// Calculates an interval of the given number of steps in a specified EDO temperament.
     ///
     /// Should generally not be called directly. See [Edo::interval](crate::Edo::interval)
-    pub fn new(edo: &'a Edo, steps: u32) -> EdoInterval<'a> {
-        let cents = 1200. * (steps as f32) / (edo.divisions as f32);
+    pub fn new(edo: Edo, steps: i32) -> Self {
+        let cents = (edo.step_size() * steps as f64) as f32;
         Self { edo, steps, cents }
     }
 
+    /// Returns the [Edo] the interval belongs to.
+    pub fn edo(&self) -> Edo {
+        self.edo
+    }
+
+    /// Returns the number of steps spanned by the interval. Negative for descending intervals.
+    pub fn steps(&self) -> i32 {
+        self.steps
+    }
+
     /// Returns an approximation of the interval in 12 EDO temperament, giving the closest 12 EDO
     /// interval name, and the number of cents by which the subject interval differs from that
     /// interval.
     pub fn to_approximate_12_edo_interval(&self) -> Approximate12EDOInterval {
         (*self).into()
     }
+
+    /// Returns a comparison of the interval to its nearest 12 EDO interval, which, unlike
+    /// [EdoInterval::to_approximate_12_edo_interval], keeps track of the source EDO and step.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Edo, interval::TwelveEDOInterval};
+    /// let comparison = Edo::new(53).interval(31).compare_to_12_edo();
+    /// assert_eq!(comparison.steps, 31);
+    /// assert_eq!(comparison.nearest, TwelveEDOInterval::PerfectFifth);
+    /// ```
+    pub fn compare_to_12_edo(&self) -> TwelveEDOComparison {
+        (*self).into()
+    }
+
+    /// Returns the frequency, in Hz, of the interval above the given reference frequency.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Edo;
+    /// let fifth = Edo::new(12).interval(7);
+    /// assert!((fifth.frequency(440.) - 659.255).abs() < 0.001);
+    /// ```
+    pub fn frequency(&self, reference_hz: f64) -> f64 {
+        let cents = self.edo.step_size() * self.steps as f64;
+        reference_hz * 2f64.powf(cents / 1200.)
+    }
+
+    /// Returns a human-readable name for the interval based on its size. See
+    /// [interval_name](crate::interval::interval_name).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::Edo;
+    /// let thirty_one = Edo::new(31);
+    /// assert_eq!(thirty_one.interval(9).name(), "neutral third");
+    /// assert_eq!(thirty_one.interval(7).name(), "subminor third");
+    /// ```
+    pub fn name(&self) -> String {
+        interval_name(self.cents as f64)
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Play for EdoInterval {
+    fn play(&self, player: &Player) {
+        let root = player.config.root;
+        let edo_freq = Frequency(root).transpose(self.cents as f64).0;
+
+        player.play_dyad(root, edo_freq);
+    }
+}
+
+impl Temperament for EqualDivision {
+    /// Returns the size, in cents, of the steps the ratio is mapped to by the patent
+    /// [Val] in its prime limit. See [EqualDivision::tempered_steps].
+    fn tempered_cents<T: PrimInt>(&self, ratio: Ratio<T>) -> f64 {
+        self.tempered_steps(ratio) as f64 * self.step_size()
+    }
+}
+
+impl Add for EdoInterval {
+    type Output = Self;
+
+    /// Panics if the intervals belong to different EDOs.
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_same_edo(&self, &rhs);
+        Self::new(self.edo, self.steps + rhs.steps)
+    }
 }
 
-impl<'a> Play for EdoInterval<'a> {
-    fn play(&self) {
-        let middle_c = 440. * 2.0_f32.powf(-9. / 12.);
-        let edo_steps: usize = self.steps as usize;
-        let edo_steps = edo_steps as f32;
-        let edo_freq = middle_c * 2f32.powf(edo_steps / self.edo.divisions as f32);
+impl Sub for EdoInterval {
+    type Output = Self;
 
-        play_dyad(middle_c, edo_freq);
+    /// Panics if the intervals belong to different EDOs.
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert_same_edo(&self, &rhs);
+        Self::new(self.edo, self.steps - rhs.steps)
     }
 }
 
+impl Neg for EdoInterval {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(self.edo, -self.steps)
+    }
+}
+
+fn assert_same_edo(a: &EdoInterval, b: &EdoInterval) {
+    assert_eq!(
+        a.edo, b.edo,
+        "cannot combine intervals from different equal divisions"
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::interval::TwelveEDOInterval;
+    use crate::{interval::TwelveEDOInterval, play::MIDDLE_C};
 
     #[test]
     fn interval() {
@@ -74,15 +580,89 @@ mod tests {
         let fifth = twelve.interval(7);
 
         assert_eq!(fifth.steps, 7);
-        assert_eq!(fifth.edo, &twelve);
+        assert_eq!(fifth.edo, twelve);
         assert_eq!(fifth.cents, 700.);
 
         let sixth = twelve.interval(9);
         assert_eq!(sixth.steps, 9);
-        assert_eq!(sixth.edo, &twelve);
+        assert_eq!(sixth.edo, twelve);
         assert_eq!(sixth.cents, 900.);
     }
 
+    #[test]
+    fn interval_outlives_its_edo() {
+        fn fifth() -> EdoInterval {
+            Edo::new(31).interval(18)
+        }
+
+        let fifth = fifth();
+        assert_eq!(fifth.edo, Edo::new(31));
+        assert_eq!(fifth.steps, 18);
+    }
+
+    #[test]
+    fn negative_steps() {
+        let twelve = Edo::new(12);
+
+        let down_a_fifth = twelve.interval(-7);
+        assert_eq!(down_a_fifth.steps(), -7);
+        assert_eq!(down_a_fifth.cents, -700.);
+
+        let approx = down_a_fifth.to_approximate_12_edo_interval();
+        assert_eq!(approx.0, TwelveEDOInterval::PerfectFourth);
+        assert_eq!(approx.1, 0.);
+    }
+
+    #[test]
+    fn name() {
+        let twenty_two = Edo::new(22);
+
+        assert_eq!(twenty_two.interval(0).name(), "unison");
+        assert_eq!(twenty_two.interval(4).name(), "supermajor second");
+        assert_eq!(twenty_two.interval(13).name(), "perfect fifth");
+        assert_eq!(twenty_two.interval(22).name(), "octave");
+        assert_eq!(twenty_two.interval(-13).name(), "descending perfect fifth");
+    }
+
+    #[test]
+    fn frequency() {
+        let twelve = Edo::new(12);
+
+        assert_eq!(twelve.interval(0).frequency(440.), 440.);
+        assert_eq!(twelve.interval(12).frequency(440.), 880.);
+        assert_eq!(twelve.interval(-12).frequency(440.), 220.);
+        assert!((twelve.interval(-9).frequency(440.) - MIDDLE_C).abs() < 1e-9);
+    }
+
+    #[test]
+    fn frequencies() {
+        let bohlen_pierce = EqualDivision::with_period(13, (3, 1));
+        let frequencies = bohlen_pierce.frequencies(100., 2);
+
+        assert_eq!(frequencies.len(), 27);
+        assert_eq!(frequencies[0], 100.);
+        assert!((frequencies[13] - 300.).abs() < 1e-9);
+        assert!((frequencies[26] - 900.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn step_arithmetic() {
+        let thirty_one = Edo::new(31);
+        let fifth = thirty_one.interval(18);
+        let major_third = thirty_one.interval(10);
+
+        assert_eq!(fifth + major_third, thirty_one.interval(28));
+        assert_eq!(fifth - major_third, thirty_one.interval(8));
+        assert_eq!(major_third - fifth, thirty_one.interval(-8));
+        assert_eq!(-fifth, thirty_one.interval(-18));
+    }
+
+    #[test]
+    #[should_panic(expected = "different equal divisions")]
+    fn step_arithmetic_across_edos_panics() {
+        let _ = Edo::new(12).interval(7) + Edo::new(31).interval(18);
+    }
+
     #[test]
     fn non_12_edo() {
         let fifty_three = Edo::new(53);
@@ -90,7 +670,7 @@ mod tests {
         let fifth = fifty_three.interval(31);
 
         assert_eq!(fifth.steps, 31);
-        assert_eq!(fifth.edo, &fifty_three);
+        assert_eq!(fifth.edo, fifty_three);
         assert_eq!(fifth.cents, 701.8868);
     }
 
@@ -112,4 +692,185 @@ mod tests {
         assert_eq!(approx53.0, TwelveEDOInterval::PerfectFifth);
         assert!((approx53.1 - 1.8868).abs() < 0.0001);
     }
+
+    #[test]
+    fn approximate() {
+        let twelve = Edo::new(12);
+        let thirty_one = Edo::new(31);
+
+        let (steps, error) = twelve.approximate(Ratio::new(3, 2));
+        assert_eq!(steps, 7);
+        assert!((error - -1.955).abs() < 0.001);
+
+        let (steps, error) = thirty_one.approximate(Ratio::new(5, 4));
+        assert_eq!(steps, 10);
+        assert!((error - 0.783).abs() < 0.001);
+
+        let (steps, error) = twelve.approximate(Ratio::new(1, 1));
+        assert_eq!(steps, 0);
+        assert_eq!(error, 0.);
+    }
+
+    #[test]
+    fn patent_val() {
+        assert_eq!(Edo::new(12).patent_val(7).mapping, vec![12, 19, 28, 34]);
+        assert_eq!(Edo::new(17).patent_val(5).mapping, vec![17, 27, 39]);
+        assert_eq!(
+            Edo::new(22).patent_val(11).mapping,
+            vec![22, 35, 51, 62, 76]
+        );
+    }
+
+    #[test]
+    fn tempered_steps() {
+        let twelve = Edo::new(12);
+
+        assert_eq!(twelve.tempered_steps(Ratio::new(3, 2)), 7);
+        assert_eq!(twelve.tempered_steps(Ratio::new(81, 80)), 0);
+        assert_eq!(twelve.tempered_steps(Ratio::new(128, 125)), 0);
+        assert_eq!(twelve.tempered_steps(Ratio::new(9, 8)), 2);
+    }
+
+    #[test]
+    fn consistency() {
+        assert!(Edo::new(12).consistency(5));
+        assert!(Edo::new(12).consistency(9));
+        assert!(!Edo::new(12).consistency(11));
+        assert!(Edo::new(31).consistency(9));
+        assert!(!Edo::new(17).consistency(5));
+    }
+
+    #[test]
+    fn max_error() {
+        assert!((Edo::new(12).max_error(5) - 15.641).abs() < 0.001);
+        assert!((Edo::new(53).max_error(5) - 1.408).abs() < 0.001);
+        assert_eq!(Edo::new(12).max_error(1), 0.);
+    }
+
+    #[test]
+    fn equal_division_of_the_tritave() {
+        let bohlen_pierce = EqualDivision::with_period(13, (3, 1));
+
+        let tritave = bohlen_pierce.interval(13);
+        assert!((tritave.cents - 1901.955).abs() < 0.001);
+
+        assert_eq!(bohlen_pierce.patent_val(7).mapping, vec![8, 13, 19, 23]);
+
+        let (steps, error) = bohlen_pierce.approximate(Ratio::new(7, 5));
+        assert_eq!(steps, 4);
+        assert!((error - 2.7).abs() < 0.01);
+    }
+
+    #[test]
+    fn equal_division_of_the_fifth() {
+        let ed_fifth = EqualDivision::with_period(9, (3, 2));
+
+        assert!((ed_fifth.step_size() - 77.995).abs() < 0.001);
+        assert_eq!(ed_fifth.interval(9).cents, ed_fifth.period_cents() as f32);
+    }
+
+    #[test]
+    fn edo_is_an_equal_division_of_the_octave() {
+        assert_eq!(Edo::new(12), EqualDivision::with_period(12, (2, 1)));
+        assert_eq!(Edo::new(12).period_cents(), 1200.);
+    }
+
+    #[test]
+    fn tempered_commas() {
+        let twelve = Edo::new(12);
+        assert_eq!(
+            twelve.tempered_commas(5, 130),
+            vec![Ratio::new(81, 80), Ratio::new(128, 125)]
+        );
+        assert_eq!(twelve.tempered_commas(3, 1000), vec![]);
+
+        let thirty_one = Edo::new(31);
+        let commas = thirty_one.tempered_commas(7, 250);
+        assert!(commas.contains(&Ratio::new(81, 80)));
+        assert!(commas.contains(&Ratio::new(225, 224)));
+        assert!(!commas.contains(&Ratio::new(128, 125)));
+    }
+
+    #[test]
+    fn comparison_table() {
+        let table = Edo::new(31).comparison_table(7);
+        let ratios: Vec<Ratio<i32>> = table.iter().map(|(ratio, _)| *ratio).collect();
+
+        assert_eq!(ratios.len(), 12);
+        assert_eq!(ratios[0], Ratio::new(8, 7));
+        assert_eq!(ratios[11], Ratio::new(7, 4));
+
+        let (_, (steps, error)) = table[11];
+        assert_eq!(steps, 25);
+        assert!((error - -1.084).abs() < 0.001);
+    }
+
+    #[test]
+    fn weighted_error() {
+        let targets = odd_limit_intervals(5);
+
+        assert!(Edo::new(12).weighted_error(&targets) < Edo::new(11).weighted_error(&targets));
+        assert!(Edo::new(53).weighted_error(&targets) < Edo::new(12).weighted_error(&targets));
+        assert_eq!(Edo::new(12).weighted_error(&[Ratio::new(1, 1)]), 0.);
+    }
+
+    #[test]
+    fn best_edos() {
+        let ranked = super::best_edos(&odd_limit_intervals(5), 60);
+        let top: Vec<u32> = ranked
+            .iter()
+            .take(3)
+            .map(|(edo, _)| edo.divisions)
+            .collect();
+
+        assert_eq!(ranked.len(), 60);
+        assert_eq!(top, vec![53, 34, 19]);
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    fn play_scale() {
+        use crate::play::{PlaybackConfig, CHANNELS, SAMPLE_RATE};
+
+        let player = Player::recorder(PlaybackConfig {
+            duration: 0.1,
+            ..PlaybackConfig::default()
+        });
+        Edo::new(5).play_scale(&player);
+
+        let note = SAMPLE_RATE as usize / 10 * CHANNELS as usize;
+        assert_eq!(player.samples().len(), 6 * note);
+    }
+
+    #[test]
+    fn wart_val() {
+        let seventeen = Edo::new(17);
+
+        assert_eq!(seventeen.wart_val("", 5), seventeen.patent_val(5));
+        assert_eq!(seventeen.wart_val("c", 5).mapping, vec![17, 27, 40]);
+        assert_eq!(seventeen.wart_val("cc", 5).mapping, vec![17, 27, 38]);
+
+        let eighteen = Edo::new(18);
+        assert_eq!(eighteen.patent_val(5).mapping, vec![18, 29, 42]);
+        assert_eq!(eighteen.wart_val("b", 5).mapping, vec![18, 28, 42]);
+    }
+
+    #[test]
+    fn wart_val_beyond_the_alphabet() {
+        let twelve = Edo::new(12);
+        let val = twelve.wart_val("z", 103);
+
+        assert_eq!(val.mapping.len(), 27);
+        assert_eq!(val.mapping[26], twelve.patent_val(103).mapping[26]);
+        assert_ne!(val.mapping[25], twelve.patent_val(103).mapping[25]);
+        assert_eq!(twelve.wart_val("", 1000), twelve.patent_val(1000));
+    }
+
+    #[test]
+    fn approximate_can_round_to_the_octave() {
+        let five = Edo::new(5);
+
+        let (steps, _) = five.approximate(Ratio::new(15, 8));
+        assert_eq!(steps, 5);
+    }
 }
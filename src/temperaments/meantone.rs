@@ -0,0 +1,209 @@
+//! Functions and structs for generating fractional-comma meantone temperaments and
+//! historical well temperaments of the 12-tone chromatic scale.
+use crate::{
+    interval::{Approximate12EDOInterval, SYNTONIC_COMMA},
+    ratio::Ratio,
+};
+#[cfg(feature = "audio")]
+use crate::{
+    pitch::Frequency,
+    play::{Play, Player},
+};
+
+/// Models a 12-tone temperament as the size, in cents, of each pitch class above C.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WellTemperament {
+    pub pitches: [f64; 12],
+}
+
+/// Models an interval of a given number of semitones above a root pitch class in a
+/// specified [WellTemperament].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WellTemperamentInterval {
+    temperament: WellTemperament,
+    root: usize,
+    steps: i32,
+    pub cents: f32,
+}
+
+impl WellTemperament {
+    /// Construct a new [WellTemperament] from the size, in cents, of each pitch class above C.
+    pub fn new(pitches: [f64; 12]) -> Self {
+        Self { pitches }
+    }
+
+    /// Create a meantone temperament whose fifths are narrowed by the given fraction of a
+    /// syntonic comma, tuned as a chain of fifths from E♭ to G♯.
+    ///
+    /// ## Example
+    ///
+    /// Quarter-comma meantone has pure major thirds
+    ///
+    /// ```rust
+    /// # use rust_intonation::temperaments::meantone::WellTemperament;
+    /// let quarter_comma = WellTemperament::meantone(1. / 4.);
+    /// assert!((quarter_comma.pitches[4] - 386.314).abs() < 0.001);
+    /// ```
+    pub fn meantone(comma_fraction: f64) -> Self {
+        let fifth = Ratio::new(3, 2).cents() - comma_fraction * SYNTONIC_COMMA.cents();
+        let mut pitches = [0.; 12];
+
+        for fifths in -3i32..=8 {
+            let pitch_class = (7 * fifths).rem_euclid(12) as usize;
+            pitches[pitch_class] = (fifths as f64 * fifth).rem_euclid(1200.);
+        }
+
+        Self { pitches }
+    }
+
+    /// Create quarter-comma meantone, with pure major thirds.
+    pub fn quarter_comma_meantone() -> Self {
+        Self::meantone(1. / 4.)
+    }
+
+    /// Create third-comma meantone, with pure minor thirds.
+    pub fn third_comma_meantone() -> Self {
+        Self::meantone(1. / 3.)
+    }
+
+    /// Create Werckmeister III, with the fifths C–G–D–A and B–F♯ narrowed by a quarter of a
+    /// Pythagorean comma, and all other fifths pure.
+    pub fn werckmeister_iii() -> Self {
+        Self::new([
+            0., 90.225, 192.180, 294.135, 390.225, 498.045, 588.270, 696.090, 792.180, 888.270,
+            996.090, 1092.180,
+        ])
+    }
+
+    /// Create Kirnberger III, with the fifths C–G–D–A–E narrowed by a quarter of a syntonic
+    /// comma, F♯–C♯ narrowed by a schisma, and all other fifths pure.
+    pub fn kirnberger_iii() -> Self {
+        Self::new([
+            0., 90.225, 193.157, 294.135, 386.314, 498.045, 590.224, 696.578, 792.180, 889.735,
+            996.090, 1088.269,
+        ])
+    }
+
+    /// Returns the deviation, in cents, of each pitch class from its 12 EDO equivalent.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::temperaments::meantone::WellTemperament;
+    /// let offsets = WellTemperament::werckmeister_iii().offsets();
+    /// assert!((offsets[7] - -3.91).abs() < 0.001);
+    /// ```
+    pub fn offsets(&self) -> [f64; 12] {
+        let mut offsets = self.pitches;
+        for (i, offset) in offsets.iter_mut().enumerate() {
+            *offset -= 100. * i as f64;
+        }
+        offsets
+    }
+
+    /// Returns an [interval][WellTemperamentInterval] of the given number of semitones
+    /// above the given root pitch class (`0` being C).
+    ///
+    /// A negative number of steps gives a descending interval.
+    pub fn interval(&self, root: usize, steps: i32) -> WellTemperamentInterval {
+        WellTemperamentInterval::new(*self, root, steps)
+    }
+}
+
+impl WellTemperamentInterval {
+    /// Calculates an interval of the given number of semitones above a root pitch class in a
+    /// specified temperament.
+    ///
+    /// Should generally not be called directly. See [WellTemperament::interval]
+    pub fn new(temperament: WellTemperament, root: usize, steps: i32) -> Self {
+        let root = root % 12;
+        let target = root as i32 + steps;
+        let octaves = target.div_euclid(12) as f64;
+        let target = target.rem_euclid(12) as usize;
+        let cents = temperament.pitches[target] - temperament.pitches[root] + 1200. * octaves;
+
+        Self {
+            temperament,
+            root,
+            steps,
+            cents: cents as f32,
+        }
+    }
+
+    /// Returns an approximation of the interval in 12 EDO temperament, giving the closest 12 EDO
+    /// interval name, and the number of cents by which the subject interval differs from that
+    /// interval.
+    pub fn to_approximate_12_edo_interval(&self) -> Approximate12EDOInterval {
+        (*self).into()
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Play for WellTemperamentInterval {
+    fn play(&self, player: &Player) {
+        let root_freq =
+            Frequency(player.config.root).transpose(self.temperament.pitches[self.root]);
+        let interval_freq = root_freq.transpose(self.cents as f64);
+
+        player.play_dyad(root_freq.0, interval_freq.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interval::TwelveEDOInterval;
+
+    #[test]
+    fn quarter_comma_meantone() {
+        let meantone = WellTemperament::quarter_comma_meantone();
+
+        assert_eq!(meantone.pitches[0], 0.);
+        assert!((meantone.pitches[7] - 696.578).abs() < 0.001);
+        assert!((meantone.pitches[4] - 386.314).abs() < 0.001);
+        assert!((meantone.pitches[3] - 310.265).abs() < 0.001);
+        assert!((meantone.pitches[8] - 772.627).abs() < 0.001);
+    }
+
+    #[test]
+    fn third_comma_meantone() {
+        let meantone = WellTemperament::third_comma_meantone();
+        let minor_third = meantone.interval(9, 3);
+
+        assert!((minor_third.cents as f64 - Ratio::new(6, 5).cents()).abs() < 0.001);
+    }
+
+    #[test]
+    fn offsets() {
+        let offsets = WellTemperament::kirnberger_iii().offsets();
+
+        assert_eq!(offsets[0], 0.);
+        assert!((offsets[4] - -13.686).abs() < 0.001);
+    }
+
+    #[test]
+    fn interval() {
+        let werckmeister = WellTemperament::werckmeister_iii();
+
+        let fifth = werckmeister.interval(0, 7);
+        assert!((fifth.cents - 696.09).abs() < 0.001);
+
+        let pure_fifth = werckmeister.interval(4, 7);
+        assert!((pure_fifth.cents - 701.955).abs() < 0.001);
+
+        let wrapping = werckmeister.interval(11, 2);
+        assert!((wrapping.cents - 198.045).abs() < 0.001);
+
+        let descending = werckmeister.interval(0, -5);
+        assert!((descending.cents - -503.91).abs() < 0.001);
+    }
+
+    #[test]
+    fn closest_12_edo() {
+        let meantone = WellTemperament::quarter_comma_meantone();
+        let approx = meantone.interval(0, 4).to_approximate_12_edo_interval();
+
+        assert_eq!(approx.0, TwelveEDOInterval::MajorThird);
+        assert!((approx.1 - -13.686).abs() < 0.001);
+    }
+}
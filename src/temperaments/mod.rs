@@ -1,5 +1,31 @@
 //! Structs and functions for working with other scale temperaments
 
 pub mod edo;
+pub mod meantone;
+pub mod mos;
+pub mod regular;
+pub mod val;
 
-pub use edo::Edo;
+pub use edo::{Edo, EqualDivision};
+pub use meantone::WellTemperament;
+pub use regular::RegularTemperament;
+pub use val::Val;
+
+use crate::ratio::Ratio;
+use num::traits::PrimInt;
+
+/// Trait for tunings that temper JI ratios, mapping each to a tempered size.
+///
+/// ## Example
+///
+/// How big is `7/4` in 22 EDO's patent mapping?
+///
+/// ```rust
+/// # use rust_intonation::{Edo, Ratio, temperaments::Temperament};
+/// let cents = Edo::new(22).tempered_cents(Ratio::new(7, 4));
+/// assert!((cents - 981.818).abs() < 0.001);
+/// ```
+pub trait Temperament {
+    /// Returns the tempered size, in cents, of the given ratio.
+    fn tempered_cents<T: PrimInt>(&self, ratio: Ratio<T>) -> f64;
+}
@@ -0,0 +1,151 @@
+//! Functions and structs for generating moment-of-symmetry (MOS) scales by stacking a
+//! generator within a period.
+use std::fmt::Display;
+
+/// Models a moment-of-symmetry scale, a scale with exactly two step sizes, large and small,
+/// built by stacking a generator within a repeating period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mos {
+    /// The size, in cents, of each scale degree above the root, in ascending order.
+    pub degrees: Vec<f64>,
+    /// The size, in cents, of the step from each degree to the next.
+    pub steps: Vec<f64>,
+    /// The number of large steps in the scale.
+    pub large: usize,
+    /// The number of small steps in the scale.
+    pub small: usize,
+}
+
+/// Tolerance, in cents, within which two steps are considered to be the same size.
+const STEP_TOLERANCE: f64 = 1e-6;
+
+/// Generates the scale formed by stacking `count` notes separated by the given generator
+/// above the root, reduced within the given period. Both the generator and period are
+/// given in cents.
+///
+/// Returns `None` if the resulting scale has more than two step sizes, and so is not a MOS.
+///
+/// ## Example
+///
+/// A stack of seven 700 cent fifths forms the diatonic 5L 2s scale
+///
+/// ```rust
+/// # use rust_intonation::temperaments::mos::mos;
+/// let diatonic = mos(700., 1200., 7).unwrap();
+/// assert_eq!(diatonic.pattern(), "LLLsLLs");
+/// assert_eq!(diatonic.to_string(), "5L 2s");
+/// ```
+///
+/// A JI generator can be given by its size in cents
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, temperaments::mos::mos};
+/// let pentatonic = mos(Ratio::new(3, 2).cents(), 1200., 5).unwrap();
+/// assert_eq!(pentatonic.to_string(), "2L 3s");
+/// assert!(mos(Ratio::new(3, 2).cents(), 1200., 6).is_none());
+/// ```
+pub fn mos(generator: f64, period: f64, count: usize) -> Option<Mos> {
+    let mut degrees: Vec<f64> = (0..count)
+        .map(|k| (k as f64 * generator).rem_euclid(period))
+        .collect();
+    degrees.sort_by(f64::total_cmp);
+
+    let steps: Vec<f64> = degrees
+        .iter()
+        .zip(degrees.iter().skip(1).chain([period].iter()))
+        .map(|(a, b)| b - a)
+        .collect();
+
+    let mut sizes: Vec<f64> = vec![];
+    for step in steps.iter() {
+        if !sizes.iter().any(|s| (s - step).abs() < STEP_TOLERANCE) {
+            sizes.push(*step);
+        }
+    }
+
+    if sizes.len() > 2 {
+        return None;
+    }
+
+    let large_size = sizes.iter().cloned().fold(f64::MIN, f64::max);
+    let large = steps
+        .iter()
+        .filter(|step| (large_size - *step).abs() < STEP_TOLERANCE)
+        .count();
+
+    Some(Mos {
+        small: steps.len() - large,
+        degrees,
+        steps,
+        large,
+    })
+}
+
+impl Mos {
+    /// Returns the sequence of large (`L`) and small (`s`) steps in the scale.
+    pub fn pattern(&self) -> String {
+        let large_size = self.steps.iter().cloned().fold(f64::MIN, f64::max);
+        self.steps
+            .iter()
+            .map(|step| {
+                if (large_size - step).abs() < STEP_TOLERANCE {
+                    'L'
+                } else {
+                    's'
+                }
+            })
+            .collect()
+    }
+}
+
+impl Display for Mos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}L {}s", self.large, self.small)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn diatonic() {
+        let diatonic = mos(700., 1200., 7).unwrap();
+
+        assert_eq!(
+            diatonic.degrees,
+            vec![0., 200., 400., 600., 700., 900., 1100.]
+        );
+        assert_eq!(
+            diatonic.steps,
+            vec![200., 200., 200., 100., 200., 200., 100.]
+        );
+        assert_eq!(diatonic.large, 5);
+        assert_eq!(diatonic.small, 2);
+        assert_eq!(diatonic.pattern(), "LLLsLLs");
+    }
+
+    #[test]
+    fn equal_steps() {
+        let whole_tone = mos(200., 1200., 6).unwrap();
+
+        assert_eq!(whole_tone.large, 6);
+        assert_eq!(whole_tone.small, 0);
+        assert_eq!(whole_tone.pattern(), "LLLLLL");
+    }
+
+    #[test]
+    fn non_octave_period() {
+        let half_octave = mos(105., 600., 6).unwrap();
+
+        assert_eq!(half_octave.degrees.len(), 6);
+        assert_eq!(half_octave.to_string(), "5L 1s");
+        assert_eq!(half_octave.pattern(), "LLLLLs");
+    }
+
+    #[test]
+    fn not_a_mos() {
+        assert!(mos(700., 1200., 6).is_none());
+    }
+}
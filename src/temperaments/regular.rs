@@ -0,0 +1,343 @@
+//! Functions and structs for constructing regular temperaments by tempering out commas.
+use super::{val::Val, Temperament};
+use crate::{
+    math::{integer_kernel, solve},
+    monzo::Monzo,
+    numtheory::primes_up_to,
+    ratio::Ratio,
+};
+use num::traits::PrimInt;
+
+/// Models a regular temperament as a mapping from primes to generators, along with the
+/// size in cents of each generator.
+///
+/// The mapping is given in Hermite normal form, with one [Val] per generator. The first
+/// generator is the period, which divides the octave evenly.
+///
+/// ## Example
+///
+/// Meantone is the 5-limit temperament that tempers out `81/80`
+///
+/// ```rust
+/// # use rust_intonation::{Ratio, temperaments::regular::RegularTemperament};
+/// let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]);
+///
+/// assert_eq!(meantone.mapping[0].mapping, vec![1, 0, -4]);
+/// assert_eq!(meantone.mapping[1].mapping, vec![0, 1, 4]);
+/// assert_eq!(meantone.period(), 1200.);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegularTemperament {
+    pub mapping: Vec<Val>,
+    pub generators: Vec<f64>,
+}
+
+impl RegularTemperament {
+    /// Construct the [RegularTemperament] in the given prime limit that tempers out
+    /// each of the given commas.
+    ///
+    /// See [RegularTemperament::from_mapping] for how the generators are tuned.
+    pub fn new<T: PrimInt>(limit: u32, commas: &[Ratio<T>]) -> Self {
+        let primes = primes_up_to(limit);
+        let commas: Vec<Vec<i64>> = commas
+            .iter()
+            .map(|comma| {
+                let mut exponents = Monzo::from(*comma).exponents;
+                assert!(
+                    exponents.len() <= primes.len(),
+                    "comma {} exceeds the {}-limit",
+                    Monzo::from(*comma),
+                    limit
+                );
+                exponents.resize(primes.len(), 0);
+                exponents.into_iter().map(|e| e as i64).collect()
+            })
+            .collect();
+
+        let mapping = integer_kernel(&commas, primes.len())
+            .into_iter()
+            .map(|row| Val::new(row.into_iter().map(|e| e as i32).collect()))
+            .collect();
+
+        Self::from_mapping(mapping)
+    }
+
+    /// Construct a [RegularTemperament] from a mapping of one [Val] per generator,
+    /// with the period first.
+    ///
+    /// The period is tuned so that octaves are pure, and the remaining generators are
+    /// tuned to minimize the sum of the squared errors of the mapped primes.
+    pub fn from_mapping(mapping: Vec<Val>) -> Self {
+        let periods_per_octave = mapping[0].mapping[0];
+        assert!(
+            periods_per_octave > 0,
+            "the first val of the mapping must map the octave"
+        );
+        let period = 1200. / periods_per_octave as f64;
+
+        let targets: Vec<f64> = mapping[0]
+            .primes()
+            .iter()
+            .zip(mapping[0].mapping.iter())
+            .map(|(&p, &m)| 1200. * (p as f64).log2() - m as f64 * period)
+            .collect();
+
+        let rows: Vec<Vec<f64>> = mapping[1..]
+            .iter()
+            .map(|val| val.mapping.iter().map(|&e| e as f64).collect())
+            .collect();
+
+        let mut generators = vec![period];
+        generators.extend(least_squares(&rows, &targets));
+
+        Self {
+            mapping,
+            generators,
+        }
+    }
+
+    /// Returns a copy of the temperament with the given generator sizes, in cents.
+    pub fn with_generators(&self, generators: Vec<f64>) -> Self {
+        assert_eq!(
+            generators.len(),
+            self.rank(),
+            "a rank {} temperament needs {} generators",
+            self.rank(),
+            self.rank()
+        );
+        Self {
+            mapping: self.mapping.clone(),
+            generators,
+        }
+    }
+
+    /// Returns a copy of the temperament with its generators tuned to their
+    /// Tenney-Euclidean (TE) optimal sizes.
+    ///
+    /// The TE tuning minimizes the sum of the squared errors of the primes, each weighted
+    /// by dividing by `log2(p)`. Unlike the default tuning, octaves are not kept pure.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, temperaments::regular::RegularTemperament};
+    /// let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]).te_tuned();
+    /// let fifth = meantone.tempered_cents(Ratio::new(3, 2));
+    /// assert!((fifth - 697.049).abs() < 0.001);
+    /// ```
+    pub fn te_tuned(&self) -> Self {
+        let weights: Vec<f64> = self.mapping[0]
+            .primes()
+            .iter()
+            .map(|&p| 1. / (p as f64).log2())
+            .collect();
+        let rows: Vec<Vec<f64>> = self
+            .mapping
+            .iter()
+            .map(|val| {
+                val.mapping
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(&e, w)| e as f64 * w)
+                    .collect()
+            })
+            .collect();
+
+        // Every weighted just prime is exactly 1200 cents.
+        let targets = vec![1200.; weights.len()];
+        self.with_generators(least_squares(&rows, &targets))
+    }
+
+    /// Returns the error, in cents, of each tempered prime in the temperament's
+    /// current tuning. Positive errors are sharp of the just prime.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, temperaments::regular::RegularTemperament};
+    /// let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]);
+    /// let errors = meantone.prime_errors();
+    /// assert_eq!(errors[0], 0.);
+    /// assert!((errors[1] - -5.060).abs() < 0.001);
+    /// ```
+    pub fn prime_errors(&self) -> Vec<f64> {
+        self.mapping[0]
+            .primes()
+            .iter()
+            .enumerate()
+            .map(|(j, &p)| {
+                let tempered: f64 = self
+                    .mapping
+                    .iter()
+                    .zip(self.generators.iter())
+                    .map(|(val, size)| val.mapping[j] as f64 * size)
+                    .sum();
+                tempered - 1200. * (p as f64).log2()
+            })
+            .collect()
+    }
+
+    /// Returns the number of generators, including the period, of the temperament.
+    pub fn rank(&self) -> usize {
+        self.mapping.len()
+    }
+
+    /// Returns the size of the period, in cents.
+    pub fn period(&self) -> f64 {
+        self.generators[0]
+    }
+
+    /// Maps a [Ratio] to the number of each generator it is made up of in the temperament.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, temperaments::regular::RegularTemperament};
+    /// let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]);
+    /// assert_eq!(meantone.map(Ratio::new(5, 4)), vec![-6, 4]);
+    /// ```
+    pub fn map<T: PrimInt>(&self, ratio: Ratio<T>) -> Vec<i32> {
+        let monzo = Monzo::from(ratio);
+        self.mapping.iter().map(|val| val.apply(&monzo)).collect()
+    }
+
+    /// Returns the tempered size, in cents, of a [Ratio] in the temperament.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, temperaments::regular::RegularTemperament};
+    /// let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]);
+    /// let fifth = meantone.tempered_cents(Ratio::new(3, 2));
+    /// assert!((fifth - 696.895).abs() < 0.001);
+    /// ```
+    pub fn tempered_cents<T: PrimInt>(&self, ratio: Ratio<T>) -> f64 {
+        self.map(ratio)
+            .iter()
+            .zip(self.generators.iter())
+            .map(|(&count, size)| count as f64 * size)
+            .sum()
+    }
+}
+
+impl Temperament for RegularTemperament {
+    fn tempered_cents<T: PrimInt>(&self, ratio: Ratio<T>) -> f64 {
+        RegularTemperament::tempered_cents(self, ratio)
+    }
+}
+
+impl From<Val> for RegularTemperament {
+    fn from(value: Val) -> Self {
+        Self::from_mapping(vec![value])
+    }
+}
+
+/// Finds the generator sizes `g` minimizing `|g * rows - targets|`, by solving the
+/// normal equations.
+fn least_squares(rows: &[Vec<f64>], targets: &[f64]) -> Vec<f64> {
+    let dot = |a: &[f64], b: &[f64]| -> f64 { a.iter().zip(b).map(|(a, b)| a * b).sum() };
+    let a = rows
+        .iter()
+        .map(|i| rows.iter().map(|k| dot(i, k)).collect())
+        .collect();
+    let b = rows.iter().map(|i| dot(i, targets)).collect();
+
+    solve(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn meantone() {
+        let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]);
+
+        assert_eq!(meantone.rank(), 2);
+        assert_eq!(
+            meantone.mapping,
+            vec![Val::new(vec![1, 0, -4]), Val::new(vec![0, 1, 4])]
+        );
+        assert_eq!(meantone.period(), 1200.);
+        assert!((meantone.generators[1] - 1896.895).abs() < 0.001);
+
+        assert_eq!(meantone.map(Ratio::new(81, 80)), vec![0, 0]);
+        assert_eq!(meantone.tempered_cents(Ratio::new(81, 80)), 0.);
+    }
+
+    #[test]
+    fn tempering_out_enough_commas_gives_an_edo() {
+        let twelve = RegularTemperament::new(5, &[Ratio::new(81, 80), Ratio::new(128, 125)]);
+
+        assert_eq!(twelve.rank(), 1);
+        assert_eq!(twelve.mapping, vec![Val::new(vec![12, 19, 28])]);
+        assert_eq!(twelve.period(), 100.);
+        assert_eq!(twelve.tempered_cents(Ratio::new(3, 2)), 700.);
+    }
+
+    #[test]
+    fn half_octave_period() {
+        let diaschismic = RegularTemperament::new(5, &[Ratio::new(2048, 2025)]);
+
+        assert_eq!(
+            diaschismic.mapping,
+            vec![Val::new(vec![2, 0, 11]), Val::new(vec![0, 1, -2])]
+        );
+        assert_eq!(diaschismic.period(), 600.);
+    }
+
+    #[test]
+    fn septimal_meantone() {
+        let septimal_meantone =
+            RegularTemperament::new(7, &[Ratio::new(81, 80), Ratio::new(126, 125)]);
+
+        assert_eq!(
+            septimal_meantone.mapping,
+            vec![Val::new(vec![1, 0, -4, -13]), Val::new(vec![0, 1, 4, 10])]
+        );
+    }
+
+    #[test]
+    fn te_tuned_meantone() {
+        let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]).te_tuned();
+
+        assert!((meantone.period() - 1201.397).abs() < 0.001);
+        assert!((meantone.tempered_cents(Ratio::new(3, 2)) - 697.049).abs() < 0.001);
+
+        let errors = meantone.prime_errors();
+        assert!((errors[0] - 1.397).abs() < 0.001);
+        assert!((errors[1] - -3.509).abs() < 0.001);
+        assert!((errors[2] - 1.883).abs() < 0.001);
+    }
+
+    #[test]
+    fn te_tuned_val() {
+        let twelve = RegularTemperament::from(Val::new(vec![12, 19, 28])).te_tuned();
+
+        assert_eq!(twelve.rank(), 1);
+        assert!((twelve.period() - 99.870).abs() < 0.001);
+    }
+
+    #[test]
+    fn default_tuning_has_pure_octaves() {
+        let meantone = RegularTemperament::new(5, &[Ratio::new(81, 80)]);
+        let errors = meantone.prime_errors();
+
+        assert_eq!(errors[0], 0.);
+        assert!((errors[1] - -5.060).abs() < 0.001);
+        assert!((errors[2] - 1.265).abs() < 0.001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_generators_checks_rank() {
+        RegularTemperament::new(5, &[Ratio::new(81, 80)]).with_generators(vec![1200.]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn comma_beyond_limit_panics() {
+        RegularTemperament::new(5, &[Ratio::new(64, 63)]);
+    }
+}
@@ -0,0 +1,323 @@
+//! Provides [Vals][Val], mappings from JI primes to steps of a temperament.
+
+use super::{edo::Edo, Temperament};
+use crate::{
+    error::IntonationError,
+    monzo::{primes_for_len, Monzo},
+    numtheory::divide_out,
+    ratio::Ratio,
+};
+use num::traits::PrimInt;
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// Models a val, the number of tempered steps each consecutive prime (starting with 2)
+/// is mapped to.
+///
+/// ## Example
+///
+/// The patent val for 12 EDO in the 5-limit is `<12 19 28]`
+///
+/// ```rust
+/// # use rust_intonation::Edo;
+/// let val = Edo::new(12).patent_val(5);
+/// assert_eq!(val.mapping, vec![12, 19, 28]);
+/// ```
+///
+/// Vals in the same prime limit can be added, subtracted and scaled, e.g. the patent
+/// vals of 12 and 19 EDO add up to that of 31 EDO
+///
+/// ```rust
+/// # use rust_intonation::Edo;
+/// let val = Edo::new(12).patent_val(7) + Edo::new(19).patent_val(7);
+/// assert_eq!(val, Edo::new(31).patent_val(7));
+/// assert_eq!((val * 2).mapping, vec![62, 98, 144, 174]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Val {
+    pub mapping: Vec<i32>,
+}
+
+impl Val {
+    /// Construct a new [Val] from a vector of step counts for consecutive primes.
+    pub fn new(mapping: Vec<i32>) -> Self {
+        Self { mapping }
+    }
+
+    /// Construct a [Val] in the given prime limit from its name in wart notation, the
+    /// number of divisions of the octave followed by any warts.
+    /// See [Edo::wart_val](crate::temperaments::EqualDivision::wart_val).
+    ///
+    /// Panics if the notation does not start with a number of divisions.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::temperaments::val::Val;
+    /// assert_eq!(Val::from_warts("17c", 5).mapping, vec![17, 27, 40]);
+    /// assert_eq!(Val::from_warts("12", 5).mapping, vec![12, 19, 28]);
+    /// ```
+    pub fn from_warts(notation: &str, limit: u32) -> Self {
+        let split = notation
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(notation.len());
+        let (divisions, warts) = notation.split_at(split);
+        let divisions: u32 = divisions
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid wart notation {:?}", notation));
+
+        Edo::new(divisions).wart_val(&warts.to_lowercase(), limit)
+    }
+
+    /// Returns the primes that each entry of the val maps.
+    pub fn primes(&self) -> Vec<u32> {
+        primes_for_len(self.mapping.len())
+    }
+
+    /// Maps a [Monzo] to the number of tempered steps it spans under this val.
+    ///
+    /// Panics if the monzo contains primes beyond the limit of the val.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{monzo::Monzo, temperaments::val::Val};
+    /// let val = Val::new(vec![12, 19, 28]);
+    /// assert_eq!(val.apply(&Monzo::new(vec![-1, 1])), 7);
+    /// assert_eq!(val.apply(&Monzo::new(vec![-4, 4, -1])), 0);
+    /// ```
+    pub fn apply(&self, monzo: &Monzo) -> i32 {
+        assert!(
+            monzo.exponents.len() <= self.mapping.len()
+                || monzo.exponents[self.mapping.len()..]
+                    .iter()
+                    .all(|&e| e == 0),
+            "monzo {} exceeds the prime limit of val {}",
+            monzo,
+            self
+        );
+
+        self.mapping
+            .iter()
+            .zip(monzo.exponents.iter())
+            .map(|(v, m)| v * m)
+            .sum()
+    }
+
+    /// Finds the JI commas in the val's prime limit that it tempers out, that is,
+    /// ratios that map to 0 steps.
+    ///
+    /// Searches all ratios whose numerator is no greater than `max_complexity`, and
+    /// returns them ordered from simplest to most complex.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, temperaments::val::Val};
+    /// let commas = Val::from_warts("17c", 5).tempered_commas(100);
+    /// assert_eq!(commas, vec![Ratio::new(81, 80)]);
+    /// ```
+    ///
+    /// Panics if `max_complexity` does not fit in an `i32`.
+    pub fn tempered_commas(&self, max_complexity: u32) -> Vec<Ratio<i32>> {
+        self.try_tempered_commas(max_complexity)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Finds the commas the val tempers out, as [Val::tempered_commas], but returning
+    /// an [IntonationError] rather than panicking if `max_complexity` does not fit in an
+    /// `i32`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{IntonationError, temperaments::val::Val};
+    /// let val = Val::from_warts("17c", 5);
+    /// assert_eq!(val.try_tempered_commas(u32::MAX), Err(IntonationError::Overflow));
+    /// ```
+    pub fn try_tempered_commas(
+        &self,
+        max_complexity: u32,
+    ) -> Result<Vec<Ratio<i32>>, IntonationError> {
+        let max_complexity =
+            i32::try_from(max_complexity).map_err(|_| IntonationError::Overflow)?;
+        let primes = self.primes();
+        let is_smooth = |n: i32| primes.iter().fold(n, |n, &p| divide_out(n, p as i32).0) == 1;
+
+        let smooth_numbers: Vec<i32> = (1..=max_complexity).filter(|&n| is_smooth(n)).collect();
+
+        let commas = smooth_numbers
+            .iter()
+            .flat_map(|&n| {
+                smooth_numbers
+                    .iter()
+                    .filter(move |&&d| d < n && d > n / 2)
+                    .map(move |&d| (n, d))
+            })
+            .map(|(n, d)| (n, Ratio::new(n, d)))
+            .filter(|(n, ratio)| ratio.numer == *n)
+            .map(|(_, ratio)| ratio)
+            .filter(|ratio| self.apply(&Monzo::from(*ratio)) == 0)
+            .collect();
+        Ok(commas)
+    }
+}
+
+impl Temperament for Val {
+    /// Returns the size, in cents, of the steps the ratio is mapped to, in the equal
+    /// temperament with pure octaves that the val describes.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rust_intonation::{Ratio, temperaments::{Temperament, val::Val}};
+    /// let val = Val::from_warts("17c", 5);
+    /// assert!((val.tempered_cents(Ratio::new(5, 4)) - 423.529).abs() < 0.001);
+    /// ```
+    fn tempered_cents<T: PrimInt>(&self, ratio: Ratio<T>) -> f64 {
+        let step_size = 1200. / self.mapping[0] as f64;
+        self.apply(&Monzo::from(ratio)) as f64 * step_size
+    }
+}
+
+impl Add for Val {
+    type Output = Self;
+
+    /// Panics if the vals are in different prime limits.
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_same_limit(&self, &rhs);
+        Self::new(
+            self.mapping
+                .iter()
+                .zip(rhs.mapping.iter())
+                .map(|(a, b)| a + b)
+                .collect(),
+        )
+    }
+}
+
+impl Sub for Val {
+    type Output = Self;
+
+    /// Panics if the vals are in different prime limits.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for Val {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1
+    }
+}
+
+impl Mul<i32> for Val {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self::new(self.mapping.iter().map(|e| e * rhs).collect())
+    }
+}
+
+fn assert_same_limit(a: &Val, b: &Val) {
+    assert_eq!(
+        a.mapping.len(),
+        b.mapping.len(),
+        "cannot combine vals {} and {} in different prime limits",
+        a,
+        b
+    );
+}
+
+impl Display for Val {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mapping = self
+            .mapping
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "<{}]", mapping)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn apply() {
+        let val = Val::new(vec![31, 49, 72, 87]);
+
+        assert_eq!(val.apply(&Ratio::new(3, 2).into()), 18);
+        assert_eq!(val.apply(&Ratio::new(5, 4).into()), 10);
+        assert_eq!(val.apply(&Ratio::new(7, 4).into()), 25);
+        assert_eq!(val.apply(&Ratio::new(81, 80).into()), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_panics_beyond_limit() {
+        let val = Val::new(vec![12, 19]);
+        val.apply(&Ratio::new(5, 4).into());
+    }
+
+    #[test]
+    fn from_warts() {
+        assert_eq!(Val::from_warts("17c", 5).mapping, vec![17, 27, 40]);
+        assert_eq!(Val::from_warts("18B", 5).mapping, vec![18, 28, 42]);
+        assert_eq!(Val::from_warts("22", 7), Edo::new(22).patent_val(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid wart notation")]
+    fn from_warts_requires_divisions() {
+        Val::from_warts("c", 5);
+    }
+
+    #[test]
+    fn wart_val_tempered_commas() {
+        let commas = Val::from_warts("17c", 5).tempered_commas(130);
+
+        assert!(commas.contains(&Ratio::new(81, 80)));
+        assert!(!Val::from_warts("17", 5)
+            .tempered_commas(130)
+            .contains(&Ratio::new(81, 80)));
+    }
+
+    #[test]
+    fn operators() {
+        let twelve = Val::new(vec![12, 19, 28]);
+        let seven = Val::new(vec![7, 11, 16]);
+
+        assert_eq!(twelve.clone() + seven.clone(), Val::new(vec![19, 30, 44]));
+        assert_eq!(twelve.clone() - seven.clone(), Val::new(vec![5, 8, 12]));
+        assert_eq!(-seven.clone(), Val::new(vec![-7, -11, -16]));
+        assert_eq!(seven * 3, Val::new(vec![21, 33, 48]));
+    }
+
+    #[test]
+    #[should_panic(expected = "in different prime limits")]
+    fn add_panics_across_limits() {
+        let _ = Val::new(vec![12, 19]) + Val::new(vec![12, 19, 28]);
+    }
+
+    #[test]
+    fn tempered_cents() {
+        let val = Edo::new(22).patent_val(7);
+
+        assert_eq!(val.tempered_cents(Ratio::new(7, 4)), 18. * (1200. / 22.));
+        assert_eq!(val.tempered_cents(Ratio::new(1, 1)), 0.);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Val::new(vec![12, 19, 28]).to_string(), "<12 19 28]");
+    }
+}
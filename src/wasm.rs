@@ -0,0 +1,307 @@
+//! JavaScript bindings, via [wasm-bindgen](https://docs.rs/wasm-bindgen), for querying
+//! ratios, scales, diamonds, and lattices from the browser, e.g. to power a lattice
+//! explorer.
+//!
+//! Each wrapper is exported to JavaScript under the name of the type it wraps, e.g.
+//! [JsRatio] as `Ratio`, with methods in camel case. Ratios are over `i32`, whose terms
+//! JavaScript numbers represent exactly. Operations that fail throw a JavaScript
+//! `Error`, with the message of the [IntonationError][crate::IntonationError] or
+//! [SclError][crate::scale::SclError].
+use crate::{
+    diamond::{Diamond, Identity},
+    interval::interval_name,
+    lattice::{region_indices, Lattice, LatticeDimension, LatticeDimensionBounds},
+    monzo::Monzo,
+    ratio::Ratio,
+    scale::Scale,
+    temperaments::Edo,
+};
+use wasm_bindgen::prelude::*;
+
+/// A JI ratio, exported as `Ratio`.
+#[wasm_bindgen(js_name = Ratio)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JsRatio(Ratio<i32>);
+
+#[wasm_bindgen(js_class = Ratio)]
+impl JsRatio {
+    /// Constructs a ratio, normalized into the octave, as [Ratio::try_new].
+    #[wasm_bindgen(constructor)]
+    pub fn new(numer: i32, denom: i32) -> Result<JsRatio, JsError> {
+        Ok(Self(Ratio::try_new(numer, denom)?))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn numer(&self) -> i32 {
+        self.0.numer
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn denom(&self) -> i32 {
+        self.0.denom
+    }
+
+    pub fn cents(&self) -> f64 {
+        self.0.cents()
+    }
+
+    pub fn limit(&self) -> i32 {
+        self.0.limit()
+    }
+
+    #[wasm_bindgen(js_name = tenneyHeight)]
+    pub fn tenney_height(&self) -> f64 {
+        self.0.tenney_height()
+    }
+
+    /// Returns the exponents of the ratio's [Monzo].
+    pub fn monzo(&self) -> Vec<i32> {
+        Monzo::from(self.0).exponents
+    }
+
+    /// Returns the name of the interval, e.g. `"major third"`. See [interval_name].
+    pub fn name(&self) -> String {
+        interval_name(self.0.cents())
+    }
+
+    pub fn mul(&self, other: &JsRatio) -> Result<JsRatio, JsError> {
+        Ok(Self(self.0.try_mul(other.0)?))
+    }
+
+    pub fn div(&self, other: &JsRatio) -> Result<JsRatio, JsError> {
+        Ok(Self(self.0.try_div(other.0)?))
+    }
+
+    pub fn pow(&self, exp: i32) -> Result<JsRatio, JsError> {
+        Ok(Self(self.0.try_pow(exp)?))
+    }
+
+    /// Returns the ratio as `numer/denom`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A scale, exported as `Scale`.
+#[wasm_bindgen(js_name = Scale)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsScale(Scale<i32>);
+
+#[wasm_bindgen(js_class = Scale)]
+impl JsScale {
+    /// Constructs an octave-repeating scale from the given ratios, as
+    /// [Scale::from_ratios].
+    #[wasm_bindgen(js_name = fromRatios)]
+    pub fn from_ratios(ratios: Vec<JsRatio>) -> JsScale {
+        let ratios: Vec<Ratio<i32>> = ratios.iter().map(|ratio| ratio.0).collect();
+        Self(Scale::from_ratios(&ratios))
+    }
+
+    /// Parses a scale from the contents of a Scala `.scl` file, as [Scale::from_scl].
+    #[wasm_bindgen(js_name = fromScl)]
+    pub fn from_scl(scl: &str) -> Result<JsScale, JsError> {
+        Ok(Self(Scale::from_scl(scl)?))
+    }
+
+    #[wasm_bindgen(js_name = fromEdo)]
+    pub fn from_edo(divisions: u32) -> Result<JsScale, JsError> {
+        Ok(Self(Scale::from_edo(Edo::try_new(divisions)?)))
+    }
+
+    #[wasm_bindgen(js_name = fromDiamond)]
+    pub fn from_diamond(diamond: &JsDiamond) -> JsScale {
+        Self(Scale::from_diamond(&diamond.0))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn description(&self) -> String {
+        self.0.description.clone()
+    }
+
+    /// Returns the number of degrees in each period of the scale.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the size, in cents, of each degree above the root.
+    pub fn cents(&self) -> Vec<f64> {
+        self.0.degrees.iter().map(|degree| degree.cents()).collect()
+    }
+
+    /// Returns the size, in cents, of the given degree, continuing into the periods
+    /// above and below, as [Scale::cents_at].
+    #[wasm_bindgen(js_name = centsAt)]
+    pub fn cents_at(&self, degree: i32) -> f64 {
+        self.0.cents_at(degree)
+    }
+
+    /// Returns the size, in cents, of each step between consecutive degrees.
+    pub fn steps(&self) -> Vec<f64> {
+        self.0.steps()
+    }
+
+    /// Returns the scale in the Scala `.scl` format.
+    #[wasm_bindgen(js_name = toScl)]
+    pub fn to_scl(&self) -> String {
+        self.0.to_scl()
+    }
+}
+
+/// A tonality diamond, exported as `Diamond`.
+#[wasm_bindgen(js_name = Diamond)]
+pub struct JsDiamond(Diamond<i32>);
+
+#[wasm_bindgen(js_class = Diamond)]
+impl JsDiamond {
+    /// Constructs a diamond from the given limits, as [Diamond::try_new].
+    #[wasm_bindgen(constructor)]
+    pub fn new(limits: Vec<u32>) -> Result<JsDiamond, JsError> {
+        Ok(Self(Diamond::try_new(limits)?))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn limits(&self) -> Vec<u32> {
+        self.0.limits().to_vec()
+    }
+
+    /// Returns the ratio of the limit at index `numer` over the limit at index `denom`,
+    /// throwing if either is not the index of a limit.
+    pub fn at(&self, numer: usize, denom: usize) -> Result<JsRatio, JsError> {
+        let ratio = self.0.ratios().get(denom).and_then(|row| row.get(numer));
+        ratio
+            .map(|ratio| JsRatio(*ratio))
+            .ok_or_else(|| no_cell(numer, denom))
+    }
+
+    /// Returns the identities of the cell at the same indices as [JsDiamond::at], e.g.
+    /// `"5 over 3"`, throwing if either is not the index of a limit.
+    pub fn identity(&self, numer: usize, denom: usize) -> Result<String, JsError> {
+        let limits = self.0.limits();
+        match (limits.get(numer), limits.get(denom)) {
+            (Some(otonal), Some(utonal)) => Ok(Identity::new(*otonal, *utonal).to_string()),
+            _ => Err(no_cell(numer, denom)),
+        }
+    }
+
+    /// Returns every ratio of the diamond, in the rows of [Diamond::ratios], each of
+    /// which shares a denominator.
+    pub fn ratios(&self) -> Vec<JsRatio> {
+        self.0
+            .ratios()
+            .iter()
+            .flatten()
+            .map(|ratio| JsRatio(*ratio))
+            .collect()
+    }
+
+    /// Returns the diamond laid out as text, as its `Display` implementation does.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A lattice, exported as `Lattice`, built up one dimension at a time.
+#[wasm_bindgen(js_name = Lattice)]
+pub struct JsLattice(Lattice<i32>);
+
+#[wasm_bindgen(js_class = Lattice)]
+impl JsLattice {
+    /// Constructs a lattice with no dimensions, whose only ratio is `1/1`.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> JsLattice {
+        Self(Lattice::new(vec![]))
+    }
+
+    /// Adds a dimension generated by the given ratio, extending infinitely in both
+    /// directions.
+    #[wasm_bindgen(js_name = addDimension)]
+    pub fn add_dimension(&mut self, ratio: &JsRatio) {
+        let dimension = LatticeDimension::new(ratio.0, LatticeDimensionBounds::Infinite);
+        self.0.dimensions.push(dimension);
+    }
+
+    /// Adds a dimension generated by the given ratio, wrapping around within the
+    /// inclusive range of indices `[low, high]`.
+    #[wasm_bindgen(js_name = addBoundedDimension)]
+    pub fn add_bounded_dimension(&mut self, ratio: &JsRatio, low: i32, high: i32) {
+        let bounds = LatticeDimensionBounds::RangeBounded(low, high);
+        self.0
+            .dimensions
+            .push(LatticeDimension::new(ratio.0, bounds));
+    }
+
+    /// Returns the ratio at the given index along each dimension, as [Lattice::try_at].
+    pub fn at(&self, indices: &[i32]) -> Result<JsRatio, JsError> {
+        Ok(JsRatio(self.0.try_at(indices)?))
+    }
+
+    /// Returns every ratio in the region spanned by the inclusive range of indices
+    /// from `lows[i]` to `highs[i]` along each dimension `i`, as [Lattice::region],
+    /// throwing if any of them overflows.
+    pub fn region(&self, lows: &[i32], highs: &[i32]) -> Result<Vec<JsRatio>, JsError> {
+        let ranges: Vec<_> = lows.iter().zip(highs).map(|(a, b)| *a..=*b).collect();
+        region_indices(&ranges)
+            .iter()
+            .map(|indices| self.at(indices))
+            .collect()
+    }
+}
+
+/// The error thrown when a diamond has no cell at the given indices.
+fn no_cell(numer: usize, denom: usize) -> JsError {
+    JsError::new(&format!(
+        "the diamond has no cell at indices ({}, {})",
+        numer, denom
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ratio_queries() {
+        let third = JsRatio::new(10, 4).unwrap();
+        let fifth = JsRatio::new(3, 2).unwrap();
+
+        assert_eq!((third.numer(), third.denom()), (5, 4));
+        assert_eq!(third.monzo(), vec![-2, 0, 1]);
+        assert_eq!(third.name(), "major third");
+        assert_eq!(third.mul(&fifth).unwrap().display(), "15/8");
+    }
+
+    #[test]
+    fn scale_from_ratios() {
+        let scale = JsScale::from_ratios(vec![
+            JsRatio::new(5, 4).unwrap(),
+            JsRatio::new(3, 2).unwrap(),
+        ]);
+
+        assert_eq!(scale.length(), 3);
+        assert!((scale.cents_at(3) - 1200.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn diamond_ratios() {
+        let diamond = JsDiamond::new(vec![1, 3, 5]).unwrap();
+
+        assert_eq!(diamond.ratios().len(), 9);
+        assert_eq!(diamond.at(1, 0).unwrap(), JsRatio::new(3, 2).unwrap());
+        assert_eq!(diamond.identity(2, 1).unwrap(), "5 over 3");
+    }
+
+    #[test]
+    fn lattice_region() {
+        let mut lattice = JsLattice::new();
+        lattice.add_dimension(&JsRatio::new(3, 2).unwrap());
+        lattice.add_bounded_dimension(&JsRatio::new(5, 4).unwrap(), 0, 1);
+
+        assert_eq!(lattice.at(&[1, 2]).unwrap().display(), "3/2");
+        assert_eq!(lattice.region(&[-1, 0], &[1, 1]).unwrap().len(), 6);
+    }
+}